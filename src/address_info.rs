@@ -0,0 +1,247 @@
+//! Address inspection result types
+//!
+//! This module provides `ValidateAddressResult`, returned by
+//! `validateaddress`, and `GetAddressInfoResult`, returned by
+//! `getaddressinfo`.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::bip32::{DerivationPath, Fingerprint};
+use bitcoin::{Address, ScriptBuf};
+use serde::{Deserialize, Serialize};
+
+/// Result of `validateaddress`
+///
+/// Only `isvalid` is set for every address; the other fields describe
+/// either a valid address or the reason an address is invalid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidateAddressResult {
+    /// Whether the address is valid
+    pub isvalid: bool,
+    /// The validated address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address<NetworkUnchecked>>,
+    /// Output script of the address
+    #[serde(rename = "scriptPubKey", default, skip_serializing_if = "Option::is_none")]
+    pub script_pubkey: Option<ScriptBuf>,
+    /// Whether the address is a P2SH or P2WSH address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isscript: Option<bool>,
+    /// Whether the address is a witness address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iswitness: Option<bool>,
+    /// Witness version, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_version: Option<u8>,
+    /// Witness program, as hex, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_program: Option<String>,
+    /// Why the address is invalid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Indexes of the characters likely to be wrong, for invalid addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_locations: Option<Vec<usize>>,
+}
+
+impl ValidateAddressResult {
+    /// Returns the indexes of the characters likely to be wrong, empty if
+    /// none are known
+    pub fn error_locations(&self) -> &[usize] { self.error_locations.as_deref().unwrap_or(&[]) }
+}
+
+/// Result of `getaddressinfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetAddressInfoResult {
+    /// The address
+    pub address: Address<NetworkUnchecked>,
+    /// Output script of the address
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: ScriptBuf,
+    /// Whether the wallet can spend from the address
+    pub ismine: bool,
+    /// Whether the address is watch-only
+    pub iswatchonly: bool,
+    /// Whether the wallet knows how to spend from the address
+    pub solvable: bool,
+    /// Descriptor of the address, if solvable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Wallet descriptor the address was derived from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_desc: Option<String>,
+    /// Whether the address is a P2SH or P2WSH address
+    pub isscript: bool,
+    /// Whether the address is a change address
+    pub ischange: bool,
+    /// Whether the address is a witness address
+    pub iswitness: bool,
+    /// Witness version, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_version: Option<u8>,
+    /// Witness program, as hex, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_program: Option<String>,
+    /// Type of the redeem or witness script, for script addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    /// Redeem or witness script, for script addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<ScriptBuf>,
+    /// Public keys of a multisig script, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkeys: Option<Vec<String>>,
+    /// Number of signatures a multisig script requires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sigsrequired: Option<u32>,
+    /// Public key, as hex, for single-key addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    /// Address embedded in a P2SH address, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedded: Option<Box<EmbeddedAddressInfo>>,
+    /// Whether the public key is compressed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iscompressed: Option<bool>,
+    /// Creation time of the key, in seconds since epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// HD derivation path of the key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdkeypath: Option<DerivationPath>,
+    /// Hash160 of the HD seed, for legacy wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdseedid: Option<String>,
+    /// Fingerprint of the master key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdmasterfingerprint: Option<Fingerprint>,
+    /// Labels of the address
+    pub labels: Vec<String>,
+}
+
+impl GetAddressInfoResult {
+    /// Returns whether the address belongs to the wallet, spendable or
+    /// watch-only
+    pub fn is_wallet_address(&self) -> bool { self.ismine || self.iswatchonly }
+}
+
+/// Information on the address embedded in a P2SH address
+///
+/// Has the fields of `GetAddressInfoResult` except the wallet relation and
+/// key metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddedAddressInfo {
+    /// The embedded address
+    pub address: Address<NetworkUnchecked>,
+    /// Output script of the embedded address
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: ScriptBuf,
+    /// Whether the address is a P2SH or P2WSH address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isscript: Option<bool>,
+    /// Whether the address is a witness address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iswitness: Option<bool>,
+    /// Witness version, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_version: Option<u8>,
+    /// Witness program, as hex, for witness addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_program: Option<String>,
+    /// Type of the witness script, for script addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    /// Witness script, for script addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<ScriptBuf>,
+    /// Public keys of a multisig script, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkeys: Option<Vec<String>>,
+    /// Number of signatures a multisig script requires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sigsrequired: Option<u32>,
+    /// Public key, as hex, for single-key addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    /// Whether the public key is compressed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iscompressed: Option<bool>,
+    /// Labels of the address
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_address() {
+        let value = json!({
+            "isvalid": true,
+            "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "scriptPubKey": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+            "isscript": false,
+            "iswitness": true,
+            "witness_version": 0,
+            "witness_program": "e8df018c7e326cc253faac7e46cdc51e68542c42",
+        });
+        let result: ValidateAddressResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(result.isvalid);
+        assert!(result.error_locations().is_empty());
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+        let invalid = json!({
+            "isvalid": false,
+            "error": "Invalid Bech32 checksum",
+            "error_locations": [4, 17],
+        });
+        let result: ValidateAddressResult = serde_json::from_value(invalid.clone()).unwrap();
+        assert_eq!(result.error_locations(), [4, 17]);
+        assert_eq!(serde_json::to_value(&result).unwrap(), invalid);
+    }
+
+    #[test]
+    fn test_get_address_info() {
+        let value = json!({
+            "address": "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN",
+            "scriptPubKey": "a914bcfeb728b584253d5f3f70bcb780e9ef218a68f487",
+            "ismine": true,
+            "iswatchonly": false,
+            "solvable": true,
+            "desc": "sh(wpkh([d34db33f/49h/0h/0h/0/0]03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd))#00000000",
+            "parent_desc": "sh(wpkh(xpub/0/*))#00000000",
+            "isscript": true,
+            "ischange": false,
+            "iswitness": false,
+            "script": "witness_v0_keyhash",
+            "hex": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+            "embedded": {
+                "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                "scriptPubKey": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                "isscript": false,
+                "iswitness": true,
+                "witness_version": 0,
+                "witness_program": "e8df018c7e326cc253faac7e46cdc51e68542c42",
+                "pubkey": "03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd",
+                "iscompressed": true,
+            },
+            "iscompressed": true,
+            "timestamp": 1_700_000_000,
+            "hdkeypath": "m/49h/0h/0h/0/0",
+            "hdmasterfingerprint": "d34db33f",
+            "labels": [""],
+        });
+        let info: GetAddressInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(info.is_wallet_address());
+        assert_eq!(info.hdkeypath, Some("m/49'/0'/0'/0/0".parse().unwrap()));
+        assert_eq!(info.hdmasterfingerprint, Some("d34db33f".parse().unwrap()));
+        assert_eq!(info.embedded.as_ref().unwrap().witness_version, Some(0));
+
+        let mut round_trip = serde_json::to_value(&info).unwrap();
+        assert_eq!(round_trip["hdkeypath"], json!("49'/0'/0'/0/0"));
+        round_trip["hdkeypath"] = value["hdkeypath"].clone();
+        assert_eq!(round_trip, value);
+    }
+}
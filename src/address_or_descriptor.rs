@@ -0,0 +1,379 @@
+//! Address or descriptor type for Bitcoin RPC APIs
+//!
+//! This module provides the `AddressOrDescriptor` enum for the scan targets of
+//! `scantxoutset`, `scanblocks` and `importdescriptors`, which take either a
+//! bare address string or a `{"desc": ..., "range": ...}` object, and the
+//! `DescriptorWithRange` and `DescriptorRange` these methods and
+//! `deriveaddresses` share.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::Address;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Range of HD chain indexes to derive from a ranged descriptor
+///
+/// Serializes as the end index (`1000`, meaning `[0, 1000]`) or as
+/// `[begin, end]`. Deserializing checks the range as Bitcoin Core does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum DescriptorRange {
+    /// Indexes from 0 up to and including the end index
+    End(u32),
+    /// Indexes from begin up to and including end
+    Bounds(u32, u32),
+}
+
+/// Error returned for a descriptor range Bitcoin Core rejects
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InvalidRangeError {
+    /// A negative index
+    #[error("Range should be greater or equal than 0, got {0}")]
+    Negative(i64),
+    /// An index beyond the non-hardened derivation indexes
+    #[error("End of range is too high: {0}")]
+    TooHigh(i64),
+    /// An end before the begin
+    #[error("Range specified as [begin,end] must not have begin after end: [{begin},{end}]")]
+    Reversed {
+        /// First index
+        begin: i64,
+        /// Last index
+        end: i64,
+    },
+    /// A range of more than `DescriptorRange::MAX_SIZE` indexes
+    #[error("Range is too large: [{begin},{end}]")]
+    TooLarge {
+        /// First index
+        begin: i64,
+        /// Last index
+        end: i64,
+    },
+}
+
+/// Range parameter of `deriveaddresses`, `importdescriptors` and
+/// `scantxoutset`
+pub type DerivationRange = DescriptorRange;
+
+impl DescriptorRange {
+    /// Highest index of a range; indexes from 2^31 on are hardened
+    pub const MAX_INDEX: u32 = 0x7fff_ffff;
+
+    /// Most indexes a range may span
+    ///
+    /// This is the limit Bitcoin Core's `ParseDescriptorRange` enforces
+    /// (`end - begin >= 1000000` is rejected as "Range is too large"), so any
+    /// range accepted here is accepted by the node. Clients wanting a tighter
+    /// bound, such as 10,000 indexes, should check `size()` themselves.
+    pub const MAX_SIZE: u32 = 1_000_000;
+
+    /// Creates a `[begin, end]` range, checking it
+    pub fn new(begin: u32, end: u32) -> std::result::Result<Self, InvalidRangeError> {
+        let range = Self::Bounds(begin, end);
+        range.validate()?;
+        Ok(range)
+    }
+
+    /// Returns the first and last index
+    pub fn bounds(&self) -> (u32, u32) {
+        match *self {
+            Self::End(end) => (0, end),
+            Self::Bounds(begin, end) => (begin, end),
+        }
+    }
+
+    /// Returns the number of indexes in a valid range
+    pub fn size(&self) -> u64 {
+        let (begin, end) = self.bounds();
+        u64::from(end.saturating_sub(begin)) + 1
+    }
+
+    /// Checks that begin is not after end, both are non-hardened indexes and
+    /// the range spans at most `MAX_SIZE` indexes
+    pub fn validate(&self) -> std::result::Result<(), InvalidRangeError> {
+        let (begin, end) = self.bounds();
+        Self::check(i64::from(begin), i64::from(end)).map(|_| ())
+    }
+
+    fn check(begin: i64, end: i64) -> std::result::Result<(u32, u32), InvalidRangeError> {
+        if begin < 0 || end < 0 {
+            return Err(InvalidRangeError::Negative(begin.min(end)));
+        }
+        if end > i64::from(Self::MAX_INDEX) {
+            return Err(InvalidRangeError::TooHigh(end));
+        }
+        if begin > end {
+            return Err(InvalidRangeError::Reversed { begin, end });
+        }
+        if end - begin >= i64::from(Self::MAX_SIZE) {
+            return Err(InvalidRangeError::TooLarge { begin, end });
+        }
+        // Both fit: 0 <= begin <= end <= MAX_INDEX
+        Ok((begin as u32, end as u32))
+    }
+}
+
+impl<'de> Deserialize<'de> for DescriptorRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            End(i64),
+            Bounds(i64, i64),
+        }
+
+        let range = match Repr::deserialize(deserializer)? {
+            Repr::End(end) => Self::check(0, end).map(|(_, end)| Self::End(end)),
+            Repr::Bounds(begin, end) =>
+                Self::check(begin, end).map(|(begin, end)| Self::Bounds(begin, end)),
+        };
+        range.map_err(de::Error::custom)
+    }
+}
+
+/// Output descriptor with an optional range to derive
+///
+/// Serializes as `{"desc": ..., "range": ...}`, the form `scantxoutset` and
+/// `importdescriptors` take, so it can be flattened into request objects.
+/// Deserializes from that object or from a bare descriptor string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct DescriptorWithRange {
+    /// Descriptor string, e.g. `addr(bc1q...)` or `wpkh(xpub.../0/*)`
+    pub desc: String,
+    /// Range to derive, for ranged descriptors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<DescriptorRange>,
+}
+
+impl DescriptorWithRange {
+    /// Creates a descriptor without a range
+    pub fn new(desc: impl Into<String>) -> Self { Self { desc: desc.into(), range: None } }
+
+    /// Sets the range to derive
+    pub fn with_range(mut self, range: DescriptorRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Returns the `deriveaddresses` parameters, `[desc]` or `[desc, range]`
+    pub fn to_deriveaddresses_params(&self) -> Vec<Value> {
+        let mut params = vec![Value::String(self.desc.clone())];
+        if let Some(range) = self.range {
+            params.push(serde_json::to_value(range).expect("range serializes"));
+        }
+        params
+    }
+}
+
+impl<'de> Deserialize<'de> for DescriptorWithRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Object {
+            desc: String,
+            #[serde(default)]
+            range: Option<DescriptorRange>,
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::String(desc) => Ok(Self::new(desc)),
+            value => {
+                let Object { desc, range } =
+                    Object::deserialize(value).map_err(de::Error::custom)?;
+                Ok(Self { desc, range })
+            }
+        }
+    }
+}
+
+/// Represents either an address or an output descriptor
+///
+/// Deserializing a string that is not an address yields a descriptor without
+/// a range, as Core reads bare strings as descriptors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddressOrDescriptor {
+    /// Address, sent as a bare string
+    Address(Address<NetworkUnchecked>),
+    /// Output descriptor, sent as a `{"desc": ..., "range": ...}` object
+    Descriptor(DescriptorWithRange),
+}
+
+impl AddressOrDescriptor {
+    /// Creates a descriptor without a range
+    pub fn descriptor(desc: impl Into<String>) -> Self {
+        Self::Descriptor(DescriptorWithRange::new(desc))
+    }
+
+    /// Creates a ranged descriptor
+    pub fn ranged_descriptor(desc: impl Into<String>, range: DescriptorRange) -> Self {
+        Self::Descriptor(DescriptorWithRange::new(desc).with_range(range))
+    }
+
+    /// Returns true if this represents an address
+    pub fn is_address(&self) -> bool { matches!(self, Self::Address(_)) }
+
+    /// Returns true if this represents a descriptor
+    pub fn is_descriptor(&self) -> bool { matches!(self, Self::Descriptor(_)) }
+
+    /// Returns the address if this is an Address variant, otherwise None
+    pub fn as_address(&self) -> Option<&Address<NetworkUnchecked>> {
+        if let Self::Address(address) = self {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the descriptor string if this is a Descriptor variant, otherwise None
+    pub fn as_descriptor(&self) -> Option<&str> {
+        if let Self::Descriptor(descriptor) = self {
+            Some(&descriptor.desc)
+        } else {
+            None
+        }
+    }
+}
+
+impl Serialize for AddressOrDescriptor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Address(address) => address.serialize(serializer),
+            Self::Descriptor(descriptor) => descriptor.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressOrDescriptor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if let Some(address) = value.as_str().and_then(|text| text.parse().ok()) {
+            return Ok(Self::Address(address));
+        }
+        DescriptorWithRange::deserialize(value).map(Self::Descriptor).map_err(de::Error::custom)
+    }
+}
+
+impl From<Address<NetworkUnchecked>> for AddressOrDescriptor {
+    fn from(address: Address<NetworkUnchecked>) -> Self { Self::Address(address) }
+}
+
+impl From<Address> for AddressOrDescriptor {
+    fn from(address: Address) -> Self { Self::Address(address.into_unchecked()) }
+}
+
+impl From<DescriptorWithRange> for AddressOrDescriptor {
+    fn from(descriptor: DescriptorWithRange) -> Self { Self::Descriptor(descriptor) }
+}
+
+impl From<AddressOrDescriptor> for DescriptorWithRange {
+    /// Converts an address into an `addr(...)` descriptor, which is how
+    /// `scantxoutset` and `importdescriptors` take addresses
+    fn from(target: AddressOrDescriptor) -> Self {
+        match target {
+            AddressOrDescriptor::Address(address) =>
+                Self::new(format!("addr({})", address.assume_checked())),
+            AddressOrDescriptor::Descriptor(descriptor) => descriptor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+    const DESC: &str = "wpkh(xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8/0/*)";
+
+    #[test]
+    fn test_address_or_descriptor_serialize() {
+        let address: AddressOrDescriptor =
+            ADDRESS.parse::<Address<NetworkUnchecked>>().unwrap().into();
+        assert!(address.is_address());
+        assert_eq!(serde_json::to_value(&address).unwrap(), json!(ADDRESS));
+
+        let desc = AddressOrDescriptor::descriptor(DESC);
+        assert_eq!(desc.as_descriptor(), Some(DESC));
+        assert_eq!(serde_json::to_value(&desc).unwrap(), json!({ "desc": DESC }));
+
+        let ranged = AddressOrDescriptor::ranged_descriptor(DESC, DescriptorRange::Bounds(5, 10));
+        assert_eq!(
+            serde_json::to_value(&ranged).unwrap(),
+            json!({ "desc": DESC, "range": [5, 10] })
+        );
+        let ranged = AddressOrDescriptor::ranged_descriptor(DESC, DescriptorRange::End(100));
+        assert_eq!(serde_json::to_value(&ranged).unwrap(), json!({ "desc": DESC, "range": 100 }));
+    }
+
+    #[test]
+    fn test_address_or_descriptor_deserialize() {
+        let parse = |value| serde_json::from_value::<AddressOrDescriptor>(value).unwrap();
+        assert_eq!(parse(json!(ADDRESS)).as_address(), Some(&ADDRESS.parse().unwrap()));
+        assert_eq!(parse(json!("addr(x)")), AddressOrDescriptor::descriptor("addr(x)"));
+        assert_eq!(
+            parse(json!({ "desc": DESC, "range": [0, 9] })),
+            AddressOrDescriptor::ranged_descriptor(DESC, DescriptorRange::Bounds(0, 9))
+        );
+        assert!(serde_json::from_value::<AddressOrDescriptor>(json!(1)).is_err());
+        assert!(serde_json::from_value::<AddressOrDescriptor>(json!({ "range": 5 })).is_err());
+    }
+
+    #[test]
+    fn test_descriptor_range_validation() {
+        assert_eq!(DescriptorRange::new(5, 10), Ok(DescriptorRange::Bounds(5, 10)));
+        assert_eq!(
+            DescriptorRange::new(10, 5),
+            Err(InvalidRangeError::Reversed { begin: 10, end: 5 })
+        );
+        assert_eq!(
+            DescriptorRange::End(0x8000_0000).validate(),
+            Err(InvalidRangeError::TooHigh(0x8000_0000))
+        );
+        assert_eq!(DescriptorRange::End(7).bounds(), (0, 7));
+        assert_eq!(DescriptorRange::End(7).size(), 8);
+        assert_eq!(DerivationRange::new(1, 1_000_000).map(|range| range.size()), Ok(1_000_000));
+        assert_eq!(
+            DerivationRange::new(0, 1_000_000),
+            Err(InvalidRangeError::TooLarge { begin: 0, end: 1_000_000 })
+        );
+
+        let parse = |value| serde_json::from_value::<DescriptorRange>(value);
+        assert_eq!(parse(json!(1000)).unwrap(), DescriptorRange::End(1000));
+        assert_eq!(parse(json!([2, 2])).unwrap(), DescriptorRange::Bounds(2, 2));
+        for invalid in
+            [json!(-1), json!([-1, 5]), json!([5, 2]), json!(2147483648u64), json!(1_000_000)]
+        {
+            assert!(parse(invalid).is_err());
+        }
+
+        let err =
+            serde_json::from_value::<DescriptorWithRange>(json!({ "desc": DESC, "range": [9, 1] }))
+                .unwrap_err();
+        assert!(err.to_string().contains("must not have begin after end"), "{}", err);
+    }
+
+    #[test]
+    fn test_descriptor_with_range() {
+        let descriptor = DescriptorWithRange::new(DESC).with_range(DescriptorRange::End(99));
+        assert_eq!(descriptor.to_deriveaddresses_params(), vec![json!(DESC), json!(99)]);
+        assert_eq!(DescriptorWithRange::new(DESC).to_deriveaddresses_params(), vec![json!(DESC)]);
+
+        // Flattened into an importdescriptors request
+        #[derive(Serialize)]
+        struct Request {
+            #[serde(flatten)]
+            descriptor: DescriptorWithRange,
+            timestamp: &'static str,
+        }
+        let request = Request { descriptor, timestamp: "now" };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "desc": DESC, "range": 99, "timestamp": "now" })
+        );
+
+        let address =
+            AddressOrDescriptor::from(ADDRESS.parse::<Address<NetworkUnchecked>>().unwrap());
+        assert_eq!(DescriptorWithRange::from(address).desc, format!("addr({})", ADDRESS));
+    }
+}
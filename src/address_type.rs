@@ -0,0 +1,81 @@
+//! Address type parameter
+//!
+//! This module provides the `AddressType` enum for the `address_type`
+//! arguments of `getnewaddress`, `getrawchangeaddress`, `createmultisig` and
+//! `addmultisigaddress`, and the `-addresstype` style options.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Type of address a wallet derives, as named by Bitcoin Core
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressType {
+    /// Base58 P2PKH address
+    Legacy,
+    /// P2WPKH or P2WSH nested in P2SH
+    P2shSegwit,
+    /// Native segwit v0 address (P2WPKH or P2WSH)
+    Bech32,
+    /// Segwit v1 (taproot) address
+    Bech32m,
+}
+
+impl AddressType {
+    /// All address types
+    pub const ALL: [AddressType; 4] =
+        [AddressType::Legacy, AddressType::P2shSegwit, AddressType::Bech32, AddressType::Bech32m];
+
+    /// Returns the name Bitcoin Core uses for the address type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Legacy => "legacy",
+            Self::P2shSegwit => "p2sh-segwit",
+            Self::Bech32 => "bech32",
+            Self::Bech32m => "bech32m",
+        }
+    }
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Error returned when parsing an unknown address type name
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown address type: {0:?}")]
+pub struct ParseAddressTypeError(String);
+
+impl FromStr for AddressType {
+    type Err = ParseAddressTypeError;
+
+    /// Parses a name exactly as Bitcoin Core does, which is case-sensitive
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|address_type| address_type.as_str() == s)
+            .ok_or_else(|| ParseAddressTypeError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_type_parse_and_display() {
+        for address_type in AddressType::ALL {
+            assert_eq!(address_type.to_string().parse::<AddressType>(), Ok(address_type));
+            let json = serde_json::to_string(&address_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", address_type));
+            assert_eq!(serde_json::from_str::<AddressType>(&json).unwrap(), address_type);
+        }
+        assert_eq!("p2sh-segwit".parse::<AddressType>(), Ok(AddressType::P2shSegwit));
+        assert!("p2sh_segwit".parse::<AddressType>().is_err());
+        assert!("Bech32".parse::<AddressType>().is_err());
+        assert!(serde_json::from_str::<AddressType>("\"taproot\"").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Serde functions for amounts nested in containers
+//!
+//! `bitcoin::amount::serde::as_btc` only handles a field that is itself an
+//! `Amount` or `SignedAmount`; inside a `Vec`, map or tuple, amounts fall
+//! back to bitcoin's derived serde, which writes integer satoshis. The
+//! `as_btc` module here writes every amount in a container in BTC, as Bitcoin
+//! Core expects, through the `BtcDenominated` trait.
+
+use std::collections::BTreeMap;
+
+use bitcoin::{Amount, SignedAmount};
+use serde_json::{Number, Value};
+
+/// Values that serialize with every amount they contain in BTC
+///
+/// Implemented for amounts, the containers generated code uses, and the
+/// plain leaf types they may hold alongside amounts.
+pub trait BtcDenominated: Sized {
+    /// Returns the JSON value, with amounts in BTC
+    fn to_btc_value(&self) -> Value;
+
+    /// Parses a JSON value with amounts in BTC
+    fn from_btc_value(value: Value) -> Result<Self, String>;
+}
+
+impl BtcDenominated for Amount {
+    fn to_btc_value(&self) -> Value {
+        Number::from_f64(self.to_btc()).map_or(Value::Null, Value::Number)
+    }
+
+    fn from_btc_value(value: Value) -> Result<Self, String> {
+        let btc = value.as_f64().ok_or_else(|| format!("expected an amount, got {}", value))?;
+        Amount::from_btc(btc).map_err(|err| err.to_string())
+    }
+}
+
+impl BtcDenominated for SignedAmount {
+    fn to_btc_value(&self) -> Value {
+        Number::from_f64(self.to_btc()).map_or(Value::Null, Value::Number)
+    }
+
+    fn from_btc_value(value: Value) -> Result<Self, String> {
+        let btc = value.as_f64().ok_or_else(|| format!("expected an amount, got {}", value))?;
+        SignedAmount::from_btc(btc).map_err(|err| err.to_string())
+    }
+}
+
+impl<T: BtcDenominated> BtcDenominated for Option<T> {
+    fn to_btc_value(&self) -> Value {
+        self.as_ref().map_or(Value::Null, BtcDenominated::to_btc_value)
+    }
+
+    fn from_btc_value(value: Value) -> Result<Self, String> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_btc_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: BtcDenominated> BtcDenominated for Vec<T> {
+    fn to_btc_value(&self) -> Value {
+        Value::Array(self.iter().map(BtcDenominated::to_btc_value).collect())
+    }
+
+    fn from_btc_value(value: Value) -> Result<Self, String> {
+        match value {
+            Value::Array(items) => items.into_iter().map(T::from_btc_value).collect(),
+            value => Err(format!("expected an array, got {}", value)),
+        }
+    }
+}
+
+impl<T: BtcDenominated> BtcDenominated for BTreeMap<String, T> {
+    fn to_btc_value(&self) -> Value {
+        Value::Object(self.iter().map(|(key, value)| (key.clone(), value.to_btc_value())).collect())
+    }
+
+    fn from_btc_value(value: Value) -> Result<Self, String> {
+        match value {
+            Value::Object(members) => members
+                .into_iter()
+                .map(|(key, value)| Ok((key, T::from_btc_value(value)?)))
+                .collect(),
+            value => Err(format!("expected an object, got {}", value)),
+        }
+    }
+}
+
+macro_rules! btc_denominated_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: BtcDenominated),+> BtcDenominated for ($($name,)+) {
+            fn to_btc_value(&self) -> Value {
+                Value::Array(vec![$(self.$index.to_btc_value()),+])
+            }
+
+            fn from_btc_value(value: Value) -> Result<Self, String> {
+                const LEN: usize = [$($index),+].len();
+                match value {
+                    Value::Array(items) if items.len() == LEN => {
+                        let mut items = items.into_iter();
+                        Ok(($($name::from_btc_value(items.next().unwrap_or_default())?,)+))
+                    }
+                    value => Err(format!("expected an array of {} items, got {}", LEN, value)),
+                }
+            }
+        }
+    };
+}
+
+btc_denominated_tuple!(A 0);
+btc_denominated_tuple!(A 0, B 1);
+btc_denominated_tuple!(A 0, B 1, C 2);
+btc_denominated_tuple!(A 0, B 1, C 2, D 3);
+
+macro_rules! btc_denominated_plain {
+    ($($type_:ty),+) => {
+        $(
+            impl BtcDenominated for $type_ {
+                fn to_btc_value(&self) -> Value {
+                    serde_json::to_value(self).unwrap_or(Value::Null)
+                }
+
+                fn from_btc_value(value: Value) -> Result<Self, String> {
+                    serde_json::from_value(value).map_err(|err| err.to_string())
+                }
+            }
+        )+
+    };
+}
+
+btc_denominated_plain!(String, bool, u64, i64, f64, Value);
+
+/// Serde functions for containers of amounts, writing each amount in BTC
+///
+/// Use with `#[serde(with = "bitcoin_rpc_types::amount::as_btc")]` on fields
+/// such as `Vec<Amount>` or `BTreeMap<String, Amount>`, optional or not.
+pub mod as_btc {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    use super::BtcDenominated;
+
+    /// Serializes a value with its amounts in BTC
+    pub fn serialize<T: BtcDenominated, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        value.to_btc_value().serialize(serializer)
+    }
+
+    /// Deserializes a value with its amounts in BTC
+    pub fn deserialize<'de, T: BtcDenominated, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<T, D::Error> {
+        T::from_btc_value(Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_nested_amounts_in_btc() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Params {
+            #[serde(with = "as_btc")]
+            amounts: BTreeMap<String, Amount>,
+            #[serde(default, skip_serializing_if = "Option::is_none", with = "as_btc")]
+            fees: Option<Vec<SignedAmount>>,
+            #[serde(with = "as_btc")]
+            pair: (Amount, String),
+        }
+
+        let params = Params {
+            amounts: [("bc1qaddress".to_string(), Amount::from_sat(150_000_000))]
+                .into_iter()
+                .collect(),
+            fees: Some(vec![SignedAmount::from_sat(-1_000)]),
+            pair: (Amount::ONE_SAT, "label".to_string()),
+        };
+        let value = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "amounts": { "bc1qaddress": 1.5 },
+                "fees": [-0.00001],
+                "pair": [0.00000001, "label"],
+            })
+        );
+        assert_eq!(serde_json::from_value::<Params>(value).unwrap(), params);
+
+        let value = json!({ "amounts": {}, "pair": [1, "label"] });
+        assert_eq!(serde_json::from_value::<Params>(value).unwrap().fees, None);
+        assert!(serde_json::from_value::<Params>(
+            json!({ "amounts": { "a": -1 }, "pair": [1, ""] })
+        )
+        .is_err());
+        assert!(serde_json::from_value::<Params>(json!({ "amounts": {}, "pair": [1] })).is_err());
+    }
+}
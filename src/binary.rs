@@ -0,0 +1,63 @@
+//! Binary schema serialization
+//!
+//! Encodes an `ApiDefinition` as CBOR. The binary form is considerably
+//! smaller and faster to load than the JSON dump, which makes it suitable as
+//! an on-disk cache for tools that load the schema on every invocation.
+
+use crate::types::{ApiDefinition, Result, SchemaError};
+
+impl ApiDefinition {
+    /// Encodes this definition as CBOR
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).map_err(|e| SchemaError::Binary(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decodes a definition previously encoded with `to_binary`
+    pub fn from_binary(bytes: &[u8]) -> Result<Self> {
+        ciborium::from_reader(bytes).map_err(|e| SchemaError::Binary(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BtcArgument, BtcMethod, BtcResult};
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblockhash".to_string(),
+            BtcMethod {
+                name: "getblockhash".to_string(),
+                description: "Returns hash of block in best-block-chain at height provided."
+                    .to_string(),
+                argument_names: vec!["height".to_string()],
+                arguments: vec![BtcArgument {
+                    names: vec!["height".to_string()],
+                    description: "The height index".to_string(),
+                    type_str: Some(vec!["".to_string(), "numeric".to_string()]),
+                    required: true,
+                    type_: "number".to_string(),
+                    ..BtcArgument::default()
+                }],
+                results: vec![BtcResult { type_: "hex".to_string(), ..BtcResult::default() }],
+                ..BtcMethod::default()
+            },
+        );
+
+        let bytes = api_def.to_binary().unwrap();
+        assert_eq!(ApiDefinition::from_binary(&bytes).unwrap(), api_def);
+        assert!(bytes.len() < serde_json::to_vec(&api_def).unwrap().len());
+    }
+
+    #[test]
+    fn test_from_binary_invalid() {
+        match ApiDefinition::from_binary(b"not cbor").unwrap_err() {
+            SchemaError::Binary(_) => {}
+            _ => panic!("Expected binary decoding error"),
+        }
+    }
+}
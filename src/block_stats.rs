@@ -0,0 +1,342 @@
+//! `getblockstats` result and argument types
+//!
+//! This module provides `GetBlockStatsResult`, returned by `getblockstats`,
+//! and the `StatsField` enum for its `stats` argument. Every statistic is
+//! optional because the result only has the fields `stats` selects. Amounts
+//! are in satoshis and fee rates in sat/vB, as Bitcoin Core reports them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::{Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Result of `getblockstats`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetBlockStatsResult {
+    /// Average fee
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub avgfee: Option<Amount>,
+    /// Average fee rate, in sat/vB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avgfeerate: Option<u64>,
+    /// Average transaction size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avgtxsize: Option<u64>,
+    /// Block hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Fee rates at the 10th, 25th, 50th, 75th and 90th percentiles of
+    /// weight, in sat/vB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feerate_percentiles: Option<[u64; 5]>,
+    /// Height of the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Number of inputs, excluding the coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ins: Option<u64>,
+    /// Highest fee
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub maxfee: Option<Amount>,
+    /// Highest fee rate, in sat/vB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxfeerate: Option<u64>,
+    /// Largest transaction size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxtxsize: Option<u64>,
+    /// Median fee
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub medianfee: Option<Amount>,
+    /// Median time past of the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediantime: Option<u64>,
+    /// Median transaction size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediantxsize: Option<u64>,
+    /// Lowest fee
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub minfee: Option<Amount>,
+    /// Lowest fee rate, in sat/vB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minfeerate: Option<u64>,
+    /// Smallest transaction size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mintxsize: Option<u64>,
+    /// Number of outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outs: Option<u64>,
+    /// Block subsidy
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub subsidy: Option<Amount>,
+    /// Total size of segwit transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swtotal_size: Option<u64>,
+    /// Total weight of segwit transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swtotal_weight: Option<u64>,
+    /// Number of segwit transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swtxs: Option<u64>,
+    /// Block timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<u64>,
+    /// Total value of the outputs, excluding the coinbase
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub total_out: Option<Amount>,
+    /// Total size of the transactions, excluding the coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<u64>,
+    /// Total weight of the transactions, excluding the coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_weight: Option<u64>,
+    /// Total fee
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub totalfee: Option<Amount>,
+    /// Number of transactions, including the coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txs: Option<u64>,
+    /// Change in the number of unspent outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utxo_increase: Option<i64>,
+    /// Change in the size of the UTXO set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utxo_size_inc: Option<i64>,
+    /// Change in the number of unspent outputs, excluding unspendable
+    /// outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utxo_increase_actual: Option<i64>,
+    /// Change in the size of the UTXO set, excluding unspendable outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utxo_size_inc_actual: Option<i64>,
+}
+
+/// Statistic of `getblockstats`, for selecting fields with `stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsField {
+    /// `avgfee`
+    Avgfee,
+    /// `avgfeerate`
+    Avgfeerate,
+    /// `avgtxsize`
+    Avgtxsize,
+    /// `blockhash`
+    Blockhash,
+    /// `feerate_percentiles`
+    FeeratePercentiles,
+    /// `height`
+    Height,
+    /// `ins`
+    Ins,
+    /// `maxfee`
+    Maxfee,
+    /// `maxfeerate`
+    Maxfeerate,
+    /// `maxtxsize`
+    Maxtxsize,
+    /// `medianfee`
+    Medianfee,
+    /// `mediantime`
+    Mediantime,
+    /// `mediantxsize`
+    Mediantxsize,
+    /// `minfee`
+    Minfee,
+    /// `minfeerate`
+    Minfeerate,
+    /// `mintxsize`
+    Mintxsize,
+    /// `outs`
+    Outs,
+    /// `subsidy`
+    Subsidy,
+    /// `swtotal_size`
+    SwtotalSize,
+    /// `swtotal_weight`
+    SwtotalWeight,
+    /// `swtxs`
+    Swtxs,
+    /// `time`
+    Time,
+    /// `total_out`
+    TotalOut,
+    /// `total_size`
+    TotalSize,
+    /// `total_weight`
+    TotalWeight,
+    /// `totalfee`
+    Totalfee,
+    /// `txs`
+    Txs,
+    /// `utxo_increase`
+    UtxoIncrease,
+    /// `utxo_size_inc`
+    UtxoSizeInc,
+    /// `utxo_increase_actual`
+    UtxoIncreaseActual,
+    /// `utxo_size_inc_actual`
+    UtxoSizeIncActual,
+}
+
+impl StatsField {
+    /// All statistics, in result order
+    pub const ALL: [StatsField; 31] = [
+        StatsField::Avgfee,
+        StatsField::Avgfeerate,
+        StatsField::Avgtxsize,
+        StatsField::Blockhash,
+        StatsField::FeeratePercentiles,
+        StatsField::Height,
+        StatsField::Ins,
+        StatsField::Maxfee,
+        StatsField::Maxfeerate,
+        StatsField::Maxtxsize,
+        StatsField::Medianfee,
+        StatsField::Mediantime,
+        StatsField::Mediantxsize,
+        StatsField::Minfee,
+        StatsField::Minfeerate,
+        StatsField::Mintxsize,
+        StatsField::Outs,
+        StatsField::Subsidy,
+        StatsField::SwtotalSize,
+        StatsField::SwtotalWeight,
+        StatsField::Swtxs,
+        StatsField::Time,
+        StatsField::TotalOut,
+        StatsField::TotalSize,
+        StatsField::TotalWeight,
+        StatsField::Totalfee,
+        StatsField::Txs,
+        StatsField::UtxoIncrease,
+        StatsField::UtxoSizeInc,
+        StatsField::UtxoIncreaseActual,
+        StatsField::UtxoSizeIncActual,
+    ];
+
+    /// Returns the name Bitcoin Core uses for the statistic
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Avgfee => "avgfee",
+            Self::Avgfeerate => "avgfeerate",
+            Self::Avgtxsize => "avgtxsize",
+            Self::Blockhash => "blockhash",
+            Self::FeeratePercentiles => "feerate_percentiles",
+            Self::Height => "height",
+            Self::Ins => "ins",
+            Self::Maxfee => "maxfee",
+            Self::Maxfeerate => "maxfeerate",
+            Self::Maxtxsize => "maxtxsize",
+            Self::Medianfee => "medianfee",
+            Self::Mediantime => "mediantime",
+            Self::Mediantxsize => "mediantxsize",
+            Self::Minfee => "minfee",
+            Self::Minfeerate => "minfeerate",
+            Self::Mintxsize => "mintxsize",
+            Self::Outs => "outs",
+            Self::Subsidy => "subsidy",
+            Self::SwtotalSize => "swtotal_size",
+            Self::SwtotalWeight => "swtotal_weight",
+            Self::Swtxs => "swtxs",
+            Self::Time => "time",
+            Self::TotalOut => "total_out",
+            Self::TotalSize => "total_size",
+            Self::TotalWeight => "total_weight",
+            Self::Totalfee => "totalfee",
+            Self::Txs => "txs",
+            Self::UtxoIncrease => "utxo_increase",
+            Self::UtxoSizeInc => "utxo_size_inc",
+            Self::UtxoIncreaseActual => "utxo_increase_actual",
+            Self::UtxoSizeIncActual => "utxo_size_inc_actual",
+        }
+    }
+}
+
+impl fmt::Display for StatsField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Error returned when parsing an unknown statistic name
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown block statistic: {0:?}")]
+pub struct ParseStatsFieldError(String);
+
+impl FromStr for StatsField {
+    type Err = ParseStatsFieldError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str() == s)
+            .ok_or_else(|| ParseStatsFieldError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_get_block_stats() {
+        let value = json!({
+            "avgfee": 3_120,
+            "feerate_percentiles": [10, 12, 15, 20, 35],
+            "height": 840_000,
+            "subsidy": 312_500_000,
+            "utxo_increase": -1_250,
+        });
+        let stats: GetBlockStatsResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(stats.avgfee, Some(Amount::from_sat(3_120)));
+        assert_eq!(stats.feerate_percentiles, Some([10, 12, 15, 20, 35]));
+        assert_eq!(stats.utxo_increase, Some(-1_250));
+        assert_eq!(stats.txs, None);
+        assert_eq!(serde_json::to_value(&stats).unwrap(), value);
+    }
+
+    #[test]
+    fn test_stats_field_names() {
+        let stats = serde_json::to_value(GetBlockStatsResult {
+            feerate_percentiles: Some([0; 5]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(stats.get(StatsField::FeeratePercentiles.as_str()).is_some());
+        for field in StatsField::ALL {
+            assert_eq!(field.to_string().parse::<StatsField>(), Ok(field));
+            assert_eq!(serde_json::to_value(field).unwrap(), json!(field.as_str()));
+        }
+        assert!("total_fee".parse::<StatsField>().is_err());
+    }
+}
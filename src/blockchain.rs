@@ -0,0 +1,315 @@
+//! Blockchain result types
+//!
+//! This module provides `GetBlockchainInfoResult`, returned by
+//! `getblockchaininfo`, `GetBlockHeaderResult`, returned by
+//! `getblockheader`, and `ChainTip`, returned by `getchaintips`.
+
+use bitcoin::block::{Header, Version};
+use bitcoin::consensus::encode;
+use bitcoin::hashes::Hash;
+use bitcoin::pow::{CompactTarget, Target, Work};
+use bitcoin::{BlockHash, TxMerkleNode};
+use serde::{Deserialize, Serialize};
+
+use crate::chain_name::ChainName;
+use crate::warnings::Warnings;
+
+/// Serde functions for `CompactTarget` as the 8 hex digits of `bits` fields
+pub(crate) mod compact_target_hex {
+    use bitcoin::pow::CompactTarget;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        bits: &CompactTarget,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:08x}", bits.to_consensus()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<CompactTarget, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        CompactTarget::from_unprefixed_hex(&hex)
+            .map_err(|_| de::Error::custom(format!("invalid bits: {:?}", hex)))
+    }
+
+    /// Serde functions for `Option<CompactTarget>`
+    pub mod opt {
+        use bitcoin::pow::CompactTarget;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            bits: &Option<CompactTarget>,
+            serializer: S,
+        ) -> std::result::Result<S::Ok, S::Error> {
+            match bits {
+                Some(bits) => super::serialize(bits, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Option<CompactTarget>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] CompactTarget);
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(bits)| bits))
+        }
+    }
+}
+
+/// Result of `getblockchaininfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockchainInfoResult {
+    /// Network name
+    pub chain: ChainName,
+    /// Height of the most-work fully-validated chain
+    pub blocks: u32,
+    /// Height of the most-work header chain
+    pub headers: u32,
+    /// Hash of the tip of the active chain
+    pub bestblockhash: BlockHash,
+    /// Compact target of the tip (Bitcoin Core 29+)
+    #[serde(default, with = "compact_target_hex::opt", skip_serializing_if = "Option::is_none")]
+    pub bits: Option<CompactTarget>,
+    /// Target of the tip (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+    /// Difficulty of the tip
+    pub difficulty: f64,
+    /// Timestamp of the tip
+    pub time: u64,
+    /// Median time past of the tip
+    pub mediantime: u64,
+    /// Estimated fraction of verified blocks, from 0 to 1
+    pub verificationprogress: f64,
+    /// Whether the node is in initial block download
+    pub initialblockdownload: bool,
+    /// Total work of the active chain
+    pub chainwork: Work,
+    /// Size of the block and undo files on disk, in bytes
+    pub size_on_disk: u64,
+    /// Whether old blocks are pruned
+    pub pruned: bool,
+    /// Height of the lowest stored block, if pruned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pruneheight: Option<u32>,
+    /// Whether pruning is automatic, if pruned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automatic_pruning: Option<bool>,
+    /// Target size of the block files in bytes, if pruning is automatic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prune_target_size: Option<u64>,
+    /// Block signing challenge, as hex, on signet (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signet_challenge: Option<String>,
+    /// Node warnings
+    pub warnings: Warnings,
+}
+
+/// Result of `getblockheader` with `verbose` set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockHeaderResult {
+    /// Block hash
+    pub hash: BlockHash,
+    /// Number of confirmations, or -1 if the block is not in the active chain
+    pub confirmations: i32,
+    /// Height of the block
+    pub height: u32,
+    /// Block version
+    pub version: i32,
+    /// Block version, as hex
+    #[serde(rename = "versionHex")]
+    pub version_hex: String,
+    /// Merkle root of the transactions
+    pub merkleroot: TxMerkleNode,
+    /// Block timestamp
+    pub time: u32,
+    /// Median time past of the block
+    pub mediantime: u64,
+    /// Nonce
+    pub nonce: u32,
+    /// Compact target
+    #[serde(with = "compact_target_hex")]
+    pub bits: CompactTarget,
+    /// Target (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+    /// Difficulty
+    pub difficulty: f64,
+    /// Total work of the chain up to the block
+    pub chainwork: Work,
+    /// Number of transactions in the block
+    #[serde(rename = "nTx")]
+    pub n_tx: u32,
+    /// Hash of the previous block, absent for the genesis block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previousblockhash: Option<BlockHash>,
+    /// Hash of the next block in the active chain, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nextblockhash: Option<BlockHash>,
+}
+
+impl GetBlockHeaderResult {
+    /// Returns the consensus header the result describes
+    pub fn to_header(&self) -> Header {
+        Header {
+            version: Version::from_consensus(self.version),
+            prev_blockhash: self.previousblockhash.unwrap_or(BlockHash::all_zeros()),
+            merkle_root: self.merkleroot,
+            time: self.time,
+            bits: self.bits,
+            nonce: self.nonce,
+        }
+    }
+}
+
+/// Result of `getblockheader`, in the form selected by `verbose`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetBlockHeaderResponse {
+    /// Decoded header, for `verbose=true`
+    Verbose(Box<GetBlockHeaderResult>),
+    /// Serialized header, as hex, for `verbose=false`
+    Hex(String),
+}
+
+impl GetBlockHeaderResponse {
+    /// Returns the consensus header, or `None` if the hex form does not
+    /// decode
+    pub fn header(&self) -> Option<Header> {
+        match self {
+            Self::Verbose(result) => Some(result.to_header()),
+            Self::Hex(hex) => encode::deserialize_hex(hex).ok(),
+        }
+    }
+}
+
+/// Entry of `getchaintips`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainTip {
+    /// Height of the tip
+    pub height: u32,
+    /// Block hash of the tip
+    pub hash: BlockHash,
+    /// Length of the branch connecting the tip to the active chain, 0 for the
+    /// active tip
+    pub branchlen: u32,
+    /// Validation status of the tip
+    pub status: ChainTipStatus,
+}
+
+/// Validation status of a chain tip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChainTipStatus {
+    /// Tip of the active chain
+    Active,
+    /// Fully validated branch that is not part of the active chain
+    ValidFork,
+    /// Branch whose blocks are all available but not fully validated
+    ValidHeaders,
+    /// Branch with valid headers but missing blocks
+    HeadersOnly,
+    /// Branch containing at least one invalid block
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    #[test]
+    fn test_get_blockchain_info() {
+        let value = json!({
+            "chain": "main",
+            "blocks": 840_000,
+            "headers": 840_000,
+            "bestblockhash": HASH,
+            "bits": "17034219",
+            "target": "0000000000000000000342190000000000000000000000000000000000000000",
+            "difficulty": 86388558925171.02,
+            "time": 1_713_571_767,
+            "mediantime": 1_713_570_208,
+            "verificationprogress": 0.9999,
+            "initialblockdownload": false,
+            "chainwork": "0000000000000000000000000000000000000000753bdab0e0d745453677442b",
+            "size_on_disk": 650_000_000_000u64,
+            "pruned": true,
+            "pruneheight": 830_000,
+            "automatic_pruning": true,
+            "prune_target_size": 5_000_000_000u64,
+            "warnings": [],
+        });
+        let info: GetBlockchainInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(info.chain, ChainName::Main);
+        assert_eq!(info.bits, Some(CompactTarget::from_consensus(0x17034219)));
+        assert_eq!(info.target, info.bits.map(Target::from_compact));
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+
+        let mut old = value;
+        let fields = old.as_object_mut().unwrap();
+        fields.remove("bits");
+        fields.remove("target");
+        fields.insert("warnings".to_string(), json!(""));
+        let info: GetBlockchainInfoResult = serde_json::from_value(old.clone()).unwrap();
+        assert_eq!(info.bits, None);
+        assert!(info.warnings.is_empty());
+
+        old["bits"] = json!("zz");
+        assert!(serde_json::from_value::<GetBlockchainInfoResult>(old).is_err());
+    }
+
+    #[test]
+    fn test_get_block_header() {
+        let value = json!({
+            "hash": HASH,
+            "confirmations": 840_001,
+            "height": 0,
+            "version": 1,
+            "versionHex": "00000001",
+            "merkleroot": "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b",
+            "time": 1_231_006_505,
+            "mediantime": 1_231_006_505,
+            "nonce": 2_083_236_893,
+            "bits": "1d00ffff",
+            "difficulty": 1.0,
+            "chainwork": "0000000000000000000000000000000000000000000000000000000100010001",
+            "nTx": 1,
+            "nextblockhash": "00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048",
+        });
+        let response: GetBlockHeaderResponse = serde_json::from_value(value.clone()).unwrap();
+        let header = response.header().unwrap();
+        assert_eq!(header.block_hash().to_string(), HASH);
+        assert_eq!(serde_json::to_value(&response).unwrap(), value);
+
+        let hex = encode::serialize_hex(&header);
+        let response: GetBlockHeaderResponse = serde_json::from_value(json!(hex)).unwrap();
+        assert_eq!(response, GetBlockHeaderResponse::Hex(hex));
+        assert_eq!(response.header(), Some(header));
+        assert_eq!(GetBlockHeaderResponse::Hex("00".to_string()).header(), None);
+    }
+
+    #[test]
+    fn test_chain_tips() {
+        let value = json!([
+            { "height": 840_000, "hash": HASH, "branchlen": 0, "status": "active" },
+            { "height": 839_990, "hash": HASH, "branchlen": 1, "status": "valid-fork" },
+            { "height": 839_000, "hash": HASH, "branchlen": 2, "status": "headers-only" },
+        ]);
+        let tips: Vec<ChainTip> = serde_json::from_value(value.clone()).unwrap();
+        let statuses: Vec<_> = tips.iter().map(|tip| tip.status).collect();
+        assert_eq!(
+            statuses,
+            [ChainTipStatus::Active, ChainTipStatus::ValidFork, ChainTipStatus::HeadersOnly]
+        );
+        assert_eq!(serde_json::to_value(&tips).unwrap(), value);
+        assert!(serde_json::from_value::<ChainTipStatus>(json!("unknown")).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+//! Builder APIs for schema types
+//!
+//! Fluent builders for `BtcMethod`, `BtcArgument` and `BtcResult` that
+//! validate the assembled value on `build()`. They are the preferred way to
+//! write schema fixtures by hand.
+
+use crate::category::Category;
+use crate::lint::ensure_valid_method;
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcMethod, BtcResult, Result, SchemaError};
+use crate::version::CoreVersion;
+
+/// Builder for `BtcArgument`
+#[derive(Debug, Clone)]
+pub struct BtcArgumentBuilder {
+    argument: BtcArgument,
+}
+
+impl BtcArgumentBuilder {
+    /// Starts building an argument with the given primary name and type
+    pub fn new(name: impl Into<String>, type_: impl Into<String>) -> Self {
+        Self {
+            argument: BtcArgument {
+                names: vec![name.into()],
+                type_: type_.into(),
+                ..BtcArgument::default()
+            },
+        }
+    }
+
+    /// Adds an alternative name for the argument
+    pub fn alias(mut self, name: impl Into<String>) -> Self {
+        self.argument.names.push(name.into());
+        self
+    }
+
+    /// Sets the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.argument.description = description.into();
+        self
+    }
+
+    /// Sets the one-line description
+    pub fn oneline_description(mut self, description: impl Into<String>) -> Self {
+        self.argument.oneline_description = description.into();
+        self
+    }
+
+    /// Sets whether the argument can also be passed positionally
+    pub fn also_positional(mut self, also_positional: bool) -> Self {
+        self.argument.also_positional = also_positional;
+        self
+    }
+
+    /// Sets the type string representation
+    pub fn type_str<I, S>(mut self, type_str: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.argument.type_str = Some(type_str.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether the argument is required
+    pub fn required(mut self, required: bool) -> Self {
+        self.argument.required = required;
+        self
+    }
+
+    /// Sets whether the argument is hidden from documentation
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.argument.hidden = hidden;
+        self
+    }
+
+    /// Sets the value used when the argument is omitted
+    pub fn default(mut self, value: impl Into<serde_json::Value>) -> Self {
+        self.argument.default = Some(value.into());
+        self
+    }
+
+    /// Sets the inclusive numeric bounds
+    pub fn range(mut self, minimum: f64, maximum: f64) -> Self {
+        self.argument.minimum = Some(minimum);
+        self.argument.maximum = Some(maximum);
+        self
+    }
+
+    /// Sets the smallest accepted numeric value
+    pub fn minimum(mut self, minimum: f64) -> Self {
+        self.argument.minimum = Some(minimum);
+        self
+    }
+
+    /// Sets the largest accepted numeric value
+    pub fn maximum(mut self, maximum: f64) -> Self {
+        self.argument.maximum = Some(maximum);
+        self
+    }
+
+    /// Restricts the argument to the given values
+    pub fn allowed_values<I, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<serde_json::Value>,
+    {
+        self.argument.allowed_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Appends a field of an option object, or the element of an array
+    pub fn inner(mut self, inner: BtcArgument) -> Self {
+        self.argument.inner.push(inner);
+        self
+    }
+
+    /// Marks the argument as deprecated with an explanatory note
+    pub fn deprecated(mut self, note: impl Into<String>) -> Self {
+        self.argument.deprecated = true;
+        self.argument.deprecation_note = Some(note.into());
+        self
+    }
+
+    /// Validates and returns the argument
+    ///
+    /// Fails if any name or the type is empty, the bounds are inverted, or the
+    /// default violates the argument's own constraints.
+    pub fn build(self) -> Result<BtcArgument> {
+        if self.argument.names.iter().any(String::is_empty) {
+            return Err(SchemaError::InvalidDefinition("argument name is empty".to_string()));
+        }
+        if self.argument.type_.is_empty() {
+            return Err(SchemaError::InvalidDefinition(format!(
+                "argument {:?} has no type",
+                self.argument.names[0]
+            )));
+        }
+        if let (Some(minimum), Some(maximum)) = (self.argument.minimum, self.argument.maximum) {
+            if minimum > maximum {
+                return Err(SchemaError::InvalidDefinition(format!(
+                    "argument {:?} has minimum {} above maximum {}",
+                    self.argument.names[0], minimum, maximum
+                )));
+            }
+        }
+        if let Some(default) = &self.argument.default {
+            self.argument
+                .validate_value(default)
+                .map_err(|err| SchemaError::InvalidDefinition(format!("invalid default: {err}")))?;
+        }
+        Ok(self.argument)
+    }
+}
+
+/// Builder for `BtcResult`
+#[derive(Debug, Clone)]
+pub struct BtcResultBuilder {
+    result: BtcResult,
+}
+
+impl BtcResultBuilder {
+    /// Starts building a result of the given type
+    pub fn new(type_: impl Into<String>) -> Self {
+        Self { result: BtcResult { type_: type_.into(), ..BtcResult::default() } }
+    }
+
+    /// Sets the key name of the result within its parent object
+    pub fn key_name(mut self, key_name: impl Into<String>) -> Self {
+        self.result.key_name = key_name.into();
+        self
+    }
+
+    /// Marks the key name as a placeholder for data-dependent keys
+    pub fn dynamic_key(mut self, dynamic_key: bool) -> Self {
+        self.result.dynamic_key = dynamic_key;
+        self
+    }
+
+    /// Sets whether the result is optional
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.result.optional = optional;
+        self
+    }
+
+    /// Sets the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.result.description = description.into();
+        self
+    }
+
+    /// Sets whether to skip type checking for this result
+    pub fn skip_type_check(mut self, skip_type_check: bool) -> Self {
+        self.result.skip_type_check = skip_type_check;
+        self
+    }
+
+    /// Sets the condition for when this result is present
+    pub fn condition(mut self, condition: impl Into<String>) -> Self {
+        self.result.condition = condition.into();
+        self
+    }
+
+    /// Appends a nested result
+    pub fn inner(mut self, inner: BtcResult) -> Self {
+        self.result.inner.push(inner);
+        self
+    }
+
+    /// Validates and returns the result
+    ///
+    /// Fails if the type is empty or if two fields of an object share a key name.
+    pub fn build(self) -> Result<BtcResult> {
+        if self.result.type_.is_empty() {
+            return Err(SchemaError::InvalidDefinition("result has no type".to_string()));
+        }
+        if self.result.rpc_type() == RpcType::Object {
+            for (index, field) in self.result.inner.iter().enumerate() {
+                let duplicate = !field.key_name.is_empty()
+                    && self.result.inner[..index].iter().any(|f| f.key_name == field.key_name);
+                if duplicate {
+                    return Err(SchemaError::InvalidDefinition(format!(
+                        "duplicate result key {:?}",
+                        field.key_name
+                    )));
+                }
+            }
+        }
+        Ok(self.result)
+    }
+}
+
+/// Builder for `BtcMethod`
+///
+/// `argument_names` is derived from the added arguments.
+#[derive(Debug, Clone)]
+pub struct BtcMethodBuilder {
+    method: BtcMethod,
+}
+
+impl BtcMethodBuilder {
+    /// Starts building a method with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { method: BtcMethod { name: name.into(), ..BtcMethod::default() } }
+    }
+
+    /// Sets the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.method.description = description.into();
+        self
+    }
+
+    /// Sets the example usage text
+    pub fn examples(mut self, examples: impl Into<String>) -> Self {
+        self.method.examples = examples.into();
+        self
+    }
+
+    /// Appends an argument
+    pub fn argument(mut self, argument: BtcArgument) -> Self {
+        if let Some(name) = argument.names.first() {
+            self.method.argument_names.push(name.clone());
+        }
+        self.method.arguments.push(argument);
+        self
+    }
+
+    /// Appends a result
+    pub fn result(mut self, result: BtcResult) -> Self {
+        self.method.results.push(result);
+        self
+    }
+
+    /// Sets the category
+    pub fn category(mut self, category: Category) -> Self {
+        self.method.category = Some(category);
+        self
+    }
+
+    /// Sets the first Bitcoin Core version providing the method
+    pub fn introduced_in(mut self, version: CoreVersion) -> Self {
+        self.method.introduced_in = Some(version);
+        self
+    }
+
+    /// Sets the first Bitcoin Core version no longer providing the method
+    pub fn removed_in(mut self, version: CoreVersion) -> Self {
+        self.method.removed_in = Some(version);
+        self
+    }
+
+    /// Marks the method as deprecated with an explanatory note
+    pub fn deprecated(mut self, note: impl Into<String>) -> Self {
+        self.method.deprecated = true;
+        self.method.deprecation_note = Some(note.into());
+        self
+    }
+
+    /// Validates and returns the method
+    ///
+    /// Fails if the name is empty, the version range is inverted, or the
+    /// method has any error-level lint finding.
+    pub fn build(self) -> Result<BtcMethod> {
+        if self.method.name.is_empty() {
+            return Err(SchemaError::InvalidDefinition("method name is empty".to_string()));
+        }
+        if let (Some(introduced), Some(removed)) =
+            (self.method.introduced_in, self.method.removed_in)
+        {
+            if removed <= introduced {
+                return Err(SchemaError::InvalidDefinition(format!(
+                    "{}: removed_in {} is not after introduced_in {}",
+                    self.method.name, removed, introduced
+                )));
+            }
+        }
+
+        ensure_valid_method(&self.method)?;
+        Ok(self.method)
+    }
+}
+
+impl BtcArgument {
+    /// Starts building an argument with the given primary name and type
+    pub fn builder(name: impl Into<String>, type_: impl Into<String>) -> BtcArgumentBuilder {
+        BtcArgumentBuilder::new(name, type_)
+    }
+}
+
+impl BtcResult {
+    /// Starts building a result of the given type
+    pub fn builder(type_: impl Into<String>) -> BtcResultBuilder { BtcResultBuilder::new(type_) }
+}
+
+impl BtcMethod {
+    /// Starts building a method with the given name
+    pub fn builder(name: impl Into<String>) -> BtcMethodBuilder { BtcMethodBuilder::new(name) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_method() {
+        let method = BtcMethod::builder("getblock")
+            .description("Get block information")
+            .category(Category::Blockchain)
+            .argument(
+                BtcArgument::builder("blockhash", "hex")
+                    .description("The block hash")
+                    .required(true)
+                    .build()
+                    .unwrap(),
+            )
+            .argument(
+                BtcArgument::builder("verbosity", "number")
+                    .alias("verbose")
+                    .default(1)
+                    .build()
+                    .unwrap(),
+            )
+            .result(
+                BtcResult::builder("object")
+                    .condition("for verbosity = 1")
+                    .inner(BtcResult::builder("hex").key_name("hash").build().unwrap())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(method.argument_names, vec!["blockhash", "verbosity"]);
+        assert_eq!(method.arguments[1].names, vec!["verbosity", "verbose"]);
+        assert_eq!(method.arguments[1].default, Some(serde_json::json!(1)));
+        assert!(method.arguments[0].required);
+        assert_eq!(method.results[0].inner[0].key_name, "hash");
+        assert_eq!(method.category, Some(Category::Blockchain));
+    }
+
+    #[test]
+    fn test_build_argument_validation() {
+        assert!(BtcArgument::builder("", "string").build().is_err());
+        assert!(BtcArgument::builder("label", "").build().is_err());
+        assert!(BtcArgument::builder("label", "string").alias("").build().is_err());
+        assert!(BtcArgument::builder("verbosity", "number").range(3.0, 0.0).build().is_err());
+        assert!(BtcArgument::builder("verbosity", "number")
+            .range(0.0, 3.0)
+            .default(5)
+            .build()
+            .is_err());
+
+        let mode = BtcArgument::builder("estimate_mode", "string")
+            .allowed_values(["unset", "economical", "conservative"])
+            .default("unset")
+            .build()
+            .unwrap();
+        assert_eq!(mode.allowed_values.len(), 3);
+
+        let options = BtcArgument::builder("options", "object")
+            .inner(BtcArgument::builder("add_inputs", "boolean").default(true).build().unwrap())
+            .inner(BtcArgument::builder("changeAddress", "string").build().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(options.inner[1].names, vec!["changeAddress"]);
+    }
+
+    #[test]
+    fn test_build_result_validation() {
+        assert!(BtcResult::builder("").build().is_err());
+
+        let duplicate = BtcResult::builder("object")
+            .inner(BtcResult::builder("number").key_name("size").build().unwrap())
+            .inner(BtcResult::builder("number").key_name("size").build().unwrap())
+            .build();
+        assert!(matches!(duplicate, Err(SchemaError::InvalidDefinition(_))));
+    }
+
+    #[test]
+    fn test_build_method_validation() {
+        assert!(BtcMethod::builder("").build().is_err());
+
+        let duplicate_args = BtcMethod::builder("send")
+            .argument(BtcArgument::builder("outputs", "array").build().unwrap())
+            .argument(BtcArgument::builder("outputs", "array").build().unwrap())
+            .build();
+        assert!(duplicate_args.is_err());
+
+        let inverted = BtcMethod::builder("getinfo")
+            .introduced_in(CoreVersion::V28)
+            .removed_in(CoreVersion::V25)
+            .build();
+        assert!(inverted.is_err());
+    }
+}
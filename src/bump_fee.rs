@@ -0,0 +1,116 @@
+//! `bumpfee` and `psbtbumpfee` types
+//!
+//! Both methods take `BumpFeeOptions` and return a `BumpFeeResult`:
+//! `bumpfee` signs and broadcasts the replacement and reports its txid,
+//! while `psbtbumpfee` returns it as an unsigned PSBT.
+
+use bitcoin::{Amount, Txid};
+use serde::{Deserialize, Serialize};
+
+use crate::fee_rate::FeeRateParam;
+use crate::fund::EstimateMode;
+use crate::outputs::TxOutput;
+use crate::psbt::PsbtString;
+
+/// Options of `bumpfee` and `psbtbumpfee`
+///
+/// `None` and empty fields are omitted, leaving Bitcoin Core's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BumpFeeOptions {
+    /// Confirmation target in blocks for fee estimation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conf_target: Option<u32>,
+    /// Fee rate of the replacement, sent in sat/vB
+    #[serde(
+        default,
+        with = "crate::fee_rate::as_sat_per_vb::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate: Option<FeeRateParam>,
+    /// Signal BIP125 replaceability of the replacement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaceable: Option<bool>,
+    /// Fee estimate mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_mode: Option<EstimateMode>,
+    /// Outputs replacing those of the original transaction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<TxOutput>,
+    /// Index of the original change output to keep as change (Bitcoin Core
+    /// 27+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_change_index: Option<u32>,
+}
+
+/// Result of `bumpfee` and `psbtbumpfee`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BumpFeeResult {
+    /// Transaction id of the replacement, from `bumpfee`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txid: Option<Txid>,
+    /// The replacement as a PSBT, from `psbtbumpfee`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psbt: Option<PsbtString>,
+    /// Fee of the original transaction
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub origfee: Amount,
+    /// Fee of the replacement
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub fee: Amount,
+    /// Errors encountered while bumping
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl BumpFeeResult {
+    /// Returns how much higher the replacement fee is, or `None` if it is
+    /// not
+    pub fn fee_increase(&self) -> Option<Amount> { self.fee.checked_sub(self.origfee) }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_bump_fee_options() {
+        let options = BumpFeeOptions {
+            fee_rate: FeeRateParam::from_sat_per_vb(12.5),
+            estimate_mode: Some(EstimateMode::Conservative),
+            outputs: vec![TxOutput::Data(vec![0xde, 0xad])],
+            original_change_index: Some(1),
+            ..Default::default()
+        };
+        let value = json!({
+            "fee_rate": 12.5,
+            "estimate_mode": "conservative",
+            "outputs": [{ "data": "dead" }],
+            "original_change_index": 1,
+        });
+        assert_eq!(serde_json::to_value(&options).unwrap(), value);
+        assert_eq!(serde_json::from_value::<BumpFeeOptions>(value).unwrap(), options);
+    }
+
+    #[test]
+    fn test_bump_fee_result() {
+        let value = json!({ "txid": TXID, "origfee": 0.00000141, "fee": 0.00001763, "errors": [] });
+        let result: BumpFeeResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(result.fee_increase(), Some(Amount::from_sat(1_622)));
+        assert_eq!(result.psbt, None);
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+        let value = json!({
+            "psbt": "cHNidP8BAAoCAAAAAAAAAAAAAAAA",
+            "origfee": 0.00000141,
+            "fee": 0.00001763,
+            "errors": [],
+        });
+        let result: BumpFeeResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(result.txid, None);
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+    }
+}
@@ -0,0 +1,560 @@
+//! Positional versus named parameter serialization
+//!
+//! Bitcoin Core accepts JSON-RPC parameters either as an array in argument
+//! order or as an object keyed by argument name. `BtcMethod::call_plan` picks
+//! the form for a given set of arguments so clients do not each reimplement
+//! the rules, and `params_to_named` and `params_to_positional` convert
+//! between the two forms. `positional_params` and `named_params` serialize
+//! argument values that may be omitted.
+//!
+//! All of them lay arguments out the same way: `null` stands for an omitted
+//! argument, trailing omitted arguments are dropped, and omitted arguments
+//! before the last given one are filled with their default. An omitted
+//! argument without a known default is never sent as `null`, which some
+//! methods treat differently from an absent argument; `call_plan` switches
+//! to named parameters instead, and the positional conversions fail.
+//! Errors are reported as `SchemaError::InvalidParams`.
+
+use serde_json::{Map, Value};
+
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcMethod, Result, SchemaError};
+use crate::validate::{child_pointer, SchemaNode, ValidationError, ValidationErrorKind};
+
+/// How to send a method's parameters
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallPlan {
+    /// Parameters as a JSON array, in argument order
+    Positional(Vec<Value>),
+    /// Parameters as a JSON object keyed by primary argument name
+    Named(Map<String, Value>),
+}
+
+impl CallPlan {
+    /// Returns the `params` value of the JSON-RPC request
+    pub fn into_params(self) -> Value {
+        match self {
+            Self::Positional(values) => Value::Array(values),
+            Self::Named(map) => Value::Object(map),
+        }
+    }
+}
+
+/// An argument's value, as laid out for a call
+struct Slot<'a> {
+    /// Primary name of the argument
+    name: &'a str,
+    /// Given value, `None` when omitted
+    value: Option<Value>,
+    /// Value Bitcoin Core uses when the argument is omitted, if known
+    default: Option<&'a Value>,
+}
+
+/// Lays slots out positionally, dropping trailing omitted arguments and
+/// filling earlier ones with their default
+///
+/// Fails with the index of the first omitted argument that has no default.
+fn positional(slots: &[Slot<'_>]) -> std::result::Result<Vec<Value>, usize> {
+    let provided = slots.iter().rposition(|slot| slot.value.is_some()).map_or(0, |last| last + 1);
+    slots[..provided]
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| slot.value.clone().or_else(|| slot.default.cloned()).ok_or(index))
+        .collect()
+}
+
+/// Lays slots out by name, leaving omitted arguments out
+fn named(slots: Vec<Slot<'_>>) -> Map<String, Value> {
+    slots.into_iter().filter_map(|slot| Some((slot.name.to_string(), slot.value?))).collect()
+}
+
+/// Sends slots positionally when every gap can be filled, and by name otherwise
+fn plan(slots: Vec<Slot<'_>>) -> CallPlan {
+    match positional(&slots) {
+        Ok(values) => CallPlan::Positional(values),
+        Err(_) => CallPlan::Named(named(slots)),
+    }
+}
+
+impl CallPlan {
+    /// Plans parameters given by name, for arguments whose defaults are not
+    /// known, such as the fields of generated params structs
+    ///
+    /// `names` lists the primary argument names in order; parameters under
+    /// other names are dropped. The plan is positional unless an omitted
+    /// argument precedes a given one.
+    pub fn from_named(names: &[&str], mut params: Map<String, Value>) -> Self {
+        plan(
+            names
+                .iter()
+                .map(|name| Slot {
+                    name,
+                    value: params.remove(*name).filter(|value| !value.is_null()),
+                    default: None,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Where a named parameter goes
+pub(crate) enum NamedParam<'a> {
+    /// A top-level argument, by index
+    Argument(usize),
+    /// A field of the `object_named_params` argument at the index
+    Field(usize, &'a BtcArgument),
+}
+
+impl BtcMethod {
+    /// Plans how to send the given parameters, keyed by argument name
+    ///
+    /// Any name of an argument may be used. Fields of an `object_named_params`
+    /// argument (e.g. the `options` of `send`) may also be given at the top
+    /// level and are folded into that object. A name shared by a top-level
+    /// argument and such a field goes to the top-level argument only when the
+    /// field is marked `also_positional`, as in Bitcoin Core.
+    ///
+    /// Values equal to an argument's default are omitted. The plan is
+    /// positional when every argument before the last provided one is either
+    /// provided or has a known default to fill in, and named otherwise.
+    ///
+    /// Fails on unknown names, an argument given twice, or a missing
+    /// required argument.
+    pub fn call_plan(&self, params: &Map<String, Value>) -> Result<CallPlan> {
+        let mut values = self.named_values(params)?;
+        for (value, arg) in values.iter_mut().zip(&self.arguments) {
+            if value.is_some() && value.as_ref() == arg.default.as_ref() {
+                *value = None;
+            }
+        }
+        Ok(plan(self.slots(values)))
+    }
+
+    /// Serializes argument values, in argument order, as positional parameters
+    ///
+    /// `None` or `null` stands for an omitted argument. Fails on surplus
+    /// values, a missing required argument, or an omitted argument without
+    /// a known default before the last given one.
+    pub fn positional_params(&self, args: Vec<Option<Value>>) -> Result<Vec<Value>> {
+        let values = self.positional_values(args)?;
+        self.positional(values)
+    }
+
+    /// Serializes argument values, in argument order, as named parameters
+    ///
+    /// `None` or `null` stands for an omitted argument, whose key is left
+    /// out. Fails on surplus values or a missing required argument.
+    pub fn named_params(&self, args: Vec<Option<Value>>) -> Result<Map<String, Value>> {
+        let values = self.positional_values(args)?;
+        Ok(named(self.slots(values)))
+    }
+
+    /// Converts positional parameters into named ones, keyed by primary argument name
+    ///
+    /// `null` values stand for omitted arguments and are left out. Fails on
+    /// surplus parameters or a missing required argument.
+    pub fn params_to_named(&self, params: Vec<Value>) -> Result<Map<String, Value>> {
+        self.named_params(params.into_iter().map(Some).collect())
+    }
+
+    /// Converts named parameters into positional ones, in argument order
+    ///
+    /// Names are resolved as in `call_plan`; values equal to the default are
+    /// kept. Fails where `call_plan` would switch to named parameters.
+    pub fn params_to_positional(&self, params: Map<String, Value>) -> Result<Vec<Value>> {
+        let values = self.named_values(&params)?;
+        self.positional(values)
+    }
+
+    /// Resolves a parameter name to its argument or `object_named_params` field
+    pub(crate) fn named_param(&self, name: &str) -> Option<NamedParam<'_>> {
+        let field = self
+            .arguments
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.rpc_type() == RpcType::ObjectNamedParams)
+            .find_map(|(index, arg)| {
+                Some((index, arg.inner.iter().find(|field| field.names.iter().any(|n| n == name))?))
+            });
+        match (self.argument_index(name), field) {
+            (Some(index), Some((_, field))) if field.also_positional =>
+                Some(NamedParam::Argument(index)),
+            (_, Some((index, field))) => Some(NamedParam::Field(index, field)),
+            (Some(index), None) => Some(NamedParam::Argument(index)),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the schema a named parameter is checked against
+    pub(crate) fn named_param_schema(&self, name: &str) -> Option<&BtcArgument> {
+        match self.named_param(name)? {
+            NamedParam::Argument(index) => Some(&self.arguments[index]),
+            NamedParam::Field(_, field) => Some(field),
+        }
+    }
+
+    pub(crate) fn argument_index(&self, name: &str) -> Option<usize> {
+        self.arguments.iter().position(|arg| arg.names.iter().any(|n| n == name))
+    }
+
+    fn slots(&self, values: Vec<Option<Value>>) -> Vec<Slot<'_>> {
+        values
+            .into_iter()
+            .zip(&self.arguments)
+            .map(|(value, arg)| Slot {
+                name: arg.names.first().map(String::as_str).unwrap_or_default(),
+                value,
+                default: arg.default.as_ref(),
+            })
+            .collect()
+    }
+
+    fn positional(&self, values: Vec<Option<Value>>) -> Result<Vec<Value>> {
+        positional(&self.slots(values)).map_err(|index| {
+            let arg = &self.arguments[index];
+            let name = arg.names.first().cloned().unwrap_or_default();
+            self.params_error(String::new(), ValidationErrorKind::PositionalGap(name), Some(arg))
+        })
+    }
+
+    /// Assigns values given in argument order to arguments
+    fn positional_values(&self, args: Vec<Option<Value>>) -> Result<Vec<Option<Value>>> {
+        if args.len() > self.arguments.len() {
+            let kind = ValidationErrorKind::TooManyParams {
+                max: self.arguments.len(),
+                actual: args.len(),
+            };
+            return Err(self.params_error(String::new(), kind, None));
+        }
+        let mut values: Vec<Option<Value>> =
+            args.into_iter().map(|value| value.filter(|value| !value.is_null())).collect();
+        values.resize(self.arguments.len(), None);
+        self.check_required(&values)?;
+        Ok(values)
+    }
+
+    /// Assigns named parameters to arguments, in argument order
+    fn named_values(&self, params: &Map<String, Value>) -> Result<Vec<Option<Value>>> {
+        let mut values: Vec<Option<Value>> = vec![None; self.arguments.len()];
+        let mut fields = Vec::new();
+        for (name, value) in params.iter().filter(|(_, value)| !value.is_null()) {
+            let pointer = child_pointer("", name);
+            match self.named_param(name) {
+                Some(NamedParam::Argument(index)) => {
+                    if values[index].replace(value.clone()).is_some() {
+                        let kind = ValidationErrorKind::DuplicateArgument(name.clone());
+                        let arg = &self.arguments[index];
+                        return Err(self.params_error(pointer, kind, Some(arg)));
+                    }
+                }
+                Some(NamedParam::Field(index, field)) => fields.push((name, value, index, field)),
+                None => {
+                    let kind = ValidationErrorKind::UnknownArgument(name.clone());
+                    return Err(self.params_error(pointer, kind, None));
+                }
+            }
+        }
+        // Fields given at the top level are merged into their object after
+        // it is assigned, so the result does not depend on key order
+        for (name, value, index, field) in fields {
+            let object = values[index].get_or_insert_with(|| Value::Object(Map::new()));
+            let inserted = match object {
+                Value::Object(object) if !field.names.iter().any(|n| object.contains_key(n)) => {
+                    let field_name = field.names.first().cloned().unwrap_or_default();
+                    object.insert(field_name, value.clone());
+                    true
+                }
+                _ => false,
+            };
+            if !inserted {
+                let kind = ValidationErrorKind::DuplicateArgument(name.clone());
+                return Err(self.params_error(child_pointer("", name), kind, Some(field)));
+            }
+        }
+        self.check_required(&values)?;
+        Ok(values)
+    }
+
+    fn check_required(&self, values: &[Option<Value>]) -> Result<()> {
+        match self.arguments.iter().zip(values).find(|(arg, value)| arg.required && value.is_none())
+        {
+            Some((arg, _)) => {
+                let name = arg.names.first().cloned().unwrap_or_default();
+                let kind = ValidationErrorKind::MissingArgument(name);
+                Err(self.params_error(String::new(), kind, Some(arg)))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn params_error(
+        &self,
+        pointer: String,
+        kind: ValidationErrorKind,
+        arg: Option<&BtcArgument>,
+    ) -> SchemaError {
+        SchemaError::InvalidParams(vec![ValidationError {
+            method: Some(self.name.clone()),
+            pointer,
+            kind,
+            schema: arg.cloned().map(SchemaNode::Argument),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn arg(name: &str, type_: &str, required: bool, default: Option<Value>) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            default,
+            ..BtcArgument::default()
+        }
+    }
+
+    fn params(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("params must be an object"),
+        }
+    }
+
+    fn getblock() -> BtcMethod {
+        BtcMethod {
+            name: "getblock".to_string(),
+            arguments: vec![
+                arg("blockhash", "hex", true, None),
+                arg("verbosity", "number", false, Some(json!(1))),
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    fn listunspent() -> BtcMethod {
+        BtcMethod {
+            name: "listunspent".to_string(),
+            arguments: vec![
+                arg("minconf", "number", false, Some(json!(1))),
+                arg("maxconf", "number", false, Some(json!(9999999))),
+                arg("addresses", "array", false, None),
+                arg("include_unsafe", "boolean", false, Some(json!(true))),
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    #[test]
+    fn test_call_plan_positional_and_trailing_defaults() {
+        let plan = getblock().call_plan(&params(json!({ "blockhash": "00ff", "verbosity": 2 })));
+        assert_eq!(plan.unwrap(), CallPlan::Positional(vec![json!("00ff"), json!(2)]));
+
+        let plan = getblock().call_plan(&params(json!({ "blockhash": "00ff", "verbosity": 1 })));
+        assert_eq!(plan.unwrap().into_params(), json!(["00ff"]));
+
+        // Gaps with known defaults are filled in
+        let plan = listunspent().call_plan(&params(json!({ "addresses": ["bc1q"] }))).unwrap();
+        assert_eq!(plan, CallPlan::Positional(vec![json!(1), json!(9999999), json!(["bc1q"])]));
+    }
+
+    #[test]
+    fn test_call_plan_named_when_gap_has_no_default() {
+        let plan = listunspent().call_plan(&params(json!({ "include_unsafe": false }))).unwrap();
+        assert_eq!(plan.into_params(), json!({ "include_unsafe": false }));
+    }
+
+    #[test]
+    fn test_call_plan_named_params_fields() {
+        let send = BtcMethod {
+            name: "send".to_string(),
+            arguments: vec![
+                arg("outputs", "array", true, None),
+                arg("conf_target", "number", false, None),
+                BtcArgument {
+                    inner: vec![
+                        arg("add_inputs", "boolean", false, None),
+                        BtcArgument {
+                            also_positional: true,
+                            ..arg("conf_target", "number", false, None)
+                        },
+                    ],
+                    ..arg("options", "object_named_params", false, None)
+                },
+            ],
+            ..BtcMethod::default()
+        };
+
+        let plan = send
+            .call_plan(&params(json!({ "outputs": [], "add_inputs": true, "conf_target": 6 })))
+            .unwrap();
+        assert_eq!(plan.into_params(), json!([[], 6, { "add_inputs": true }]));
+    }
+
+    #[test]
+    fn test_call_plan_from_named() {
+        let names = ["blockhash", "verbosity", "fee_rate"];
+        let plan =
+            CallPlan::from_named(&names, params(json!({ "blockhash": "00", "verbosity": 2 })));
+        assert_eq!(plan, CallPlan::Positional(vec![json!("00"), json!(2)]));
+
+        // Without known defaults, any gap switches to named parameters
+        let plan =
+            CallPlan::from_named(&names, params(json!({ "blockhash": "00", "fee_rate": 1 })));
+        assert_eq!(plan.into_params(), json!({ "blockhash": "00", "fee_rate": 1 }));
+    }
+
+    #[test]
+    fn test_call_plan_also_positional() {
+        // A field sharing a name with a top-level argument stays in the
+        // options object unless it is marked `also_positional`
+        let mut send = BtcMethod {
+            name: "send".to_string(),
+            arguments: vec![
+                arg("outputs", "array", true, None),
+                arg("conf_target", "number", false, None),
+                BtcArgument {
+                    inner: vec![arg("conf_target", "number", false, None)],
+                    ..arg("options", "object_named_params", false, None)
+                },
+            ],
+            ..BtcMethod::default()
+        };
+        let given = params(json!({ "outputs": [], "conf_target": 6 }));
+        assert_eq!(
+            send.call_plan(&given).unwrap().into_params(),
+            json!({ "outputs": [], "options": { "conf_target": 6 } })
+        );
+
+        send.arguments[2].inner[0].also_positional = true;
+        assert_eq!(send.call_plan(&given).unwrap().into_params(), json!([[], 6]));
+    }
+
+    #[test]
+    fn test_call_plan_merges_fields_into_options() {
+        let send = BtcMethod {
+            name: "send".to_string(),
+            arguments: vec![
+                arg("outputs", "array", true, None),
+                BtcArgument {
+                    inner: vec![
+                        arg("add_inputs", "boolean", false, None),
+                        arg("fee_rate", "amount", false, None),
+                    ],
+                    ..arg("options", "object_named_params", false, None)
+                },
+            ],
+            ..BtcMethod::default()
+        };
+
+        let given = json!({ "outputs": [], "fee_rate": 1, "options": { "add_inputs": true } });
+        let plan = send.call_plan(&params(given)).unwrap();
+        assert_eq!(plan.into_params(), json!([[], { "add_inputs": true, "fee_rate": 1 }]));
+
+        let given = json!({ "outputs": [], "fee_rate": 1, "options": { "fee_rate": 2 } });
+        assert_eq!(
+            error_kind(send.call_plan(&params(given))),
+            ValidationErrorKind::DuplicateArgument("fee_rate".to_string())
+        );
+    }
+
+    fn error_kind(result: Result<impl std::fmt::Debug>) -> ValidationErrorKind {
+        match result.unwrap_err() {
+            SchemaError::InvalidParams(errors) => errors[0].kind.clone(),
+            err => panic!("expected invalid params, got {}", err),
+        }
+    }
+
+    #[test]
+    fn test_call_plan_errors() {
+        assert_eq!(
+            error_kind(getblock().call_plan(&params(json!({ "verbosity": 2 })))),
+            ValidationErrorKind::MissingArgument("blockhash".to_string())
+        );
+        assert_eq!(
+            error_kind(getblock().call_plan(&params(json!({ "blockhash": null })))),
+            ValidationErrorKind::MissingArgument("blockhash".to_string())
+        );
+        let err =
+            getblock().call_plan(&params(json!({ "blockhash": "00", "foo": 1 }))).unwrap_err();
+        let SchemaError::InvalidParams(errors) = err else {
+            panic!("expected invalid params");
+        };
+        assert_eq!(errors[0].kind, ValidationErrorKind::UnknownArgument("foo".to_string()));
+        assert_eq!(errors[0].pointer, "/foo");
+        assert_eq!(errors[0].method.as_deref(), Some("getblock"));
+    }
+
+    #[test]
+    fn test_positional_and_named_params() {
+        let method = listunspent();
+        let args = vec![Some(json!(0)), None, Some(json!(["bc1q"])), None];
+        assert_eq!(
+            method.positional_params(args.clone()).unwrap(),
+            vec![json!(0), json!(9999999), json!(["bc1q"])]
+        );
+        assert_eq!(
+            Value::Object(method.named_params(args).unwrap()),
+            json!({ "minconf": 0, "addresses": ["bc1q"] })
+        );
+        assert!(method.positional_params(vec![None; 4]).unwrap().is_empty());
+
+        // Gaps without a default are never sent as null
+        assert_eq!(
+            error_kind(method.positional_params(vec![None, None, None, Some(json!(false))])),
+            ValidationErrorKind::PositionalGap("addresses".to_string())
+        );
+        assert_eq!(
+            method.named_params(vec![None, None, Some(Value::Null), Some(json!(false))]).unwrap(),
+            params(json!({ "include_unsafe": false }))
+        );
+
+        assert_eq!(
+            error_kind(method.positional_params(vec![None; 5])),
+            ValidationErrorKind::TooManyParams { max: 4, actual: 5 }
+        );
+        assert!(getblock().positional_params(vec![]).is_err());
+        assert!(getblock().named_params(vec![None, Some(json!(2))]).is_err());
+        assert_eq!(
+            getblock().positional_params(vec![Some(json!("00")), None]).unwrap(),
+            vec![json!("00")]
+        );
+    }
+
+    #[test]
+    fn test_params_to_named() {
+        let named = listunspent().params_to_named(vec![json!(0), Value::Null, json!(["bc1q"])]);
+        assert_eq!(Value::Object(named.unwrap()), json!({ "minconf": 0, "addresses": ["bc1q"] }));
+
+        assert!(listunspent().params_to_named(vec![Value::Null; 5]).is_err());
+        assert!(getblock().params_to_named(vec![]).is_err());
+        assert!(getblock().params_to_named(vec![Value::Null, json!(2)]).is_err());
+    }
+
+    #[test]
+    fn test_params_to_positional() {
+        let positional =
+            listunspent().params_to_positional(params(json!({ "include_unsafe": false })));
+        assert_eq!(
+            error_kind(positional),
+            ValidationErrorKind::PositionalGap("addresses".to_string())
+        );
+        let positional = listunspent().params_to_positional(params(json!({ "maxconf": 10 })));
+        assert_eq!(positional.unwrap(), vec![json!(1), json!(10)]);
+
+        // Values equal to the default are kept
+        let positional =
+            getblock().params_to_positional(params(json!({ "blockhash": "00", "verbosity": 1 })));
+        assert_eq!(positional.unwrap(), vec![json!("00"), json!(1)]);
+
+        assert!(listunspent().params_to_positional(Map::new()).unwrap().is_empty());
+        assert!(getblock().params_to_positional(params(json!({ "verbosity": 2 }))).is_err());
+
+        let named = listunspent().params_to_named(vec![json!(6), json!(10)]).unwrap();
+        assert_eq!(listunspent().params_to_positional(named).unwrap(), vec![json!(6), json!(10)]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Method category type
+//!
+//! This module provides the `Category` enum used to group methods the same
+//! way `bitcoin-cli help` does.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::{ApiDefinition, BtcMethod};
+
+/// Category a Bitcoin RPC method is listed under in `help`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// Chain state and block queries
+    Blockchain,
+    /// Node control (`stop`, `uptime`, `logging`, ...)
+    Control,
+    /// Block generation on test networks
+    Generating,
+    /// Mining and block templates
+    Mining,
+    /// Peer-to-peer networking
+    Network,
+    /// Raw transaction and PSBT handling
+    Rawtransactions,
+    /// External signer integration
+    Signer,
+    /// Miscellaneous utilities
+    Util,
+    /// Wallet functionality
+    Wallet,
+    /// ZMQ notification introspection
+    Zmq,
+    /// Hidden test and debugging methods
+    Hidden,
+}
+
+impl Category {
+    /// All categories, in `help` order
+    pub const ALL: [Category; 11] = [
+        Category::Blockchain,
+        Category::Control,
+        Category::Generating,
+        Category::Mining,
+        Category::Network,
+        Category::Rawtransactions,
+        Category::Signer,
+        Category::Util,
+        Category::Wallet,
+        Category::Zmq,
+        Category::Hidden,
+    ];
+
+    /// Returns the lowercase category name as used in schema files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blockchain => "blockchain",
+            Self::Control => "control",
+            Self::Generating => "generating",
+            Self::Mining => "mining",
+            Self::Network => "network",
+            Self::Rawtransactions => "rawtransactions",
+            Self::Signer => "signer",
+            Self::Util => "util",
+            Self::Wallet => "wallet",
+            Self::Zmq => "zmq",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Error returned when parsing an unknown category name
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown method category: {0:?}")]
+pub struct ParseCategoryError(String);
+
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+
+    /// Parses a category name case-insensitively (e.g. `Rawtransactions` from `help`)
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseCategoryError(s.to_string()))
+    }
+}
+
+impl ApiDefinition {
+    /// Returns the methods in a category, ordered by name
+    pub fn methods_in_category(&self, category: Category) -> impl Iterator<Item = &BtcMethod> {
+        self.rpcs.values().filter(move |method| method.category == Some(category))
+    }
+
+    /// Groups the categorized methods by category
+    ///
+    /// Methods without a category are not included.
+    pub fn methods_by_category(&self) -> BTreeMap<Category, Vec<&BtcMethod>> {
+        let mut groups: BTreeMap<Category, Vec<&BtcMethod>> = BTreeMap::new();
+        for method in self.rpcs.values() {
+            if let Some(category) = method.category {
+                groups.entry(category).or_default().push(method);
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str, category: Option<Category>) -> BtcMethod {
+        BtcMethod { name: name.to_string(), category, ..BtcMethod::default() }
+    }
+
+    #[test]
+    fn test_category_parse_and_display() {
+        assert_eq!("Rawtransactions".parse::<Category>().unwrap(), Category::Rawtransactions);
+        assert_eq!("wallet".parse::<Category>().unwrap(), Category::Wallet);
+        assert!("lightning".parse::<Category>().is_err());
+        assert_eq!(Category::Zmq.to_string(), "zmq");
+        assert_eq!(serde_json::to_string(&Category::Blockchain).unwrap(), "\"blockchain\"");
+    }
+
+    #[test]
+    fn test_methods_in_category() {
+        let mut api_def = ApiDefinition::new();
+        for method in [
+            method("getblock", Some(Category::Blockchain)),
+            method("getbalance", Some(Category::Wallet)),
+            method("getblockcount", Some(Category::Blockchain)),
+            method("echo", None),
+        ] {
+            api_def.rpcs.insert(method.name.clone(), method);
+        }
+
+        let names: Vec<&str> = api_def
+            .methods_in_category(Category::Blockchain)
+            .map(|method| method.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["getblock", "getblockcount"]);
+
+        let groups = api_def.methods_by_category();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&Category::Wallet][0].name, "getbalance");
+    }
+}
@@ -0,0 +1,157 @@
+//! Chain name type
+//!
+//! This module provides the `ChainName` enum for the `chain` field of
+//! `getblockchaininfo` and `getmininginfo` and the `-chain` option. Names not
+//! known to this crate, such as those of chains added by newer nodes, are
+//! kept in `ChainName::Other`.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::Network;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Name of the chain a node runs on, as Bitcoin Core reports it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChainName {
+    /// `main`
+    Main,
+    /// `test` (testnet3)
+    Test,
+    /// `testnet4`
+    Testnet4,
+    /// `signet`
+    Signet,
+    /// `regtest`
+    Regtest,
+    /// A chain name not known to this crate
+    Other(String),
+}
+
+impl ChainName {
+    /// Returns the name Bitcoin Core uses for the chain
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Main => "main",
+            Self::Test => "test",
+            Self::Testnet4 => "testnet4",
+            Self::Signet => "signet",
+            Self::Regtest => "regtest",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Returns whether the chain is one of the known chains
+    pub fn is_known(&self) -> bool { !matches!(self, Self::Other(_)) }
+
+    /// Returns the network of the chain, or None for an unknown chain
+    pub fn network(&self) -> Option<Network> {
+        match self {
+            Self::Main => Some(Network::Bitcoin),
+            Self::Test => Some(Network::Testnet),
+            Self::Testnet4 => Some(Network::Testnet4),
+            Self::Signet => Some(Network::Signet),
+            Self::Regtest => Some(Network::Regtest),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ChainName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<&str> for ChainName {
+    fn from(name: &str) -> Self {
+        match name {
+            "main" => Self::Main,
+            "test" => Self::Test,
+            "testnet4" => Self::Testnet4,
+            "signet" => Self::Signet,
+            "regtest" => Self::Regtest,
+            name => Self::Other(name.to_string()),
+        }
+    }
+}
+
+impl FromStr for ChainName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Ok(Self::from(s)) }
+}
+
+impl From<Network> for ChainName {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::Bitcoin => Self::Main,
+            Network::Testnet => Self::Test,
+            Network::Testnet4 => Self::Testnet4,
+            Network::Signet => Self::Signet,
+            Network::Regtest => Self::Regtest,
+        }
+    }
+}
+
+/// Error returned when converting an unknown chain into a `Network`
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown chain: {0:?}")]
+pub struct UnknownChainError(String);
+
+impl TryFrom<ChainName> for Network {
+    type Error = UnknownChainError;
+
+    fn try_from(chain: ChainName) -> std::result::Result<Self, Self::Error> {
+        chain.network().ok_or_else(|| UnknownChainError(chain.to_string()))
+    }
+}
+
+impl Serialize for ChainName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from(name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_name_network() {
+        for network in [
+            Network::Bitcoin,
+            Network::Testnet,
+            Network::Testnet4,
+            Network::Signet,
+            Network::Regtest,
+        ] {
+            let chain = ChainName::from(network);
+            assert_eq!(chain.to_string(), network.to_core_arg());
+            assert_eq!(Network::try_from(chain), Ok(network));
+        }
+
+        let other: ChainName = "testnet5".parse().unwrap();
+        assert_eq!(other, ChainName::Other("testnet5".to_string()));
+        assert!(!other.is_known());
+        assert_eq!(other.network(), None);
+        assert!(Network::try_from(other).is_err());
+    }
+
+    #[test]
+    fn test_chain_name_serde() {
+        assert_eq!(serde_json::to_string(&ChainName::Testnet4).unwrap(), "\"testnet4\"");
+        assert_eq!(serde_json::from_str::<ChainName>("\"main\"").unwrap(), ChainName::Main);
+        assert_eq!(
+            serde_json::from_str::<ChainName>("\"mutinynet\"").unwrap(),
+            ChainName::Other("mutinynet".to_string())
+        );
+    }
+}
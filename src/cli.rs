@@ -0,0 +1,159 @@
+//! `bitcoin-cli` style parameter coercion
+//!
+//! `bitcoin-cli` receives every parameter as a string and decides per
+//! parameter whether to send it as a JSON string or parse it as JSON. This
+//! module makes the same decision from the argument's schema type, so REPLs
+//! and command-line tools built on this crate accept the same input.
+
+use serde_json::{Map, Value};
+
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcMethod, Result, SchemaError};
+
+impl BtcArgument {
+    /// Converts a command-line string into a JSON value of this argument's type
+    ///
+    /// String and hex arguments are taken verbatim. Arguments of any other
+    /// type are parsed as JSON (`1`, `true`, `[..]`, `{..}`); unions that
+    /// include a string fall back to the verbatim string when the text is not
+    /// valid JSON.
+    pub fn coerce(&self, text: &str) -> Result<Value> {
+        let rpc_type = self.rpc_type();
+        let alternatives = rpc_type.alternatives();
+        if alternatives.iter().all(RpcType::is_string) {
+            return Ok(Value::String(text.to_string()));
+        }
+
+        match serde_json::from_str(text) {
+            Ok(value) => Ok(value),
+            Err(_) if alternatives.iter().any(RpcType::is_string) =>
+                Ok(Value::String(text.to_string())),
+            Err(e) => Err(SchemaError::InvalidValue(format!(
+                "{}: cannot parse {:?} as {}: {}",
+                self.names.first().map(String::as_str).unwrap_or_default(),
+                text,
+                rpc_type,
+                e
+            ))),
+        }
+    }
+}
+
+impl BtcMethod {
+    /// Converts `bitcoin-cli` arguments into the `params` of a request
+    ///
+    /// Without `named`, the arguments are positional and an array is returned.
+    /// With `named` (`bitcoin-cli -named`), each `name=value` argument is
+    /// converted according to the argument, or `object_named_params` field,
+    /// with that name, and an object is returned. As in `bitcoin-cli`,
+    /// arguments without `=` are positional and are sent in the `args` key.
+    ///
+    /// Fails on surplus positional arguments, unknown names, a name given
+    /// twice, or a value that cannot be parsed.
+    pub fn coerce_cli_args<S: AsRef<str>>(&self, args: &[S], named: bool) -> Result<Value> {
+        let invalid =
+            |reason: String| SchemaError::InvalidValue(format!("{}: {}", self.name, reason));
+        let mut positional = Vec::new();
+        let mut object = Map::new();
+
+        for arg in args {
+            let arg = arg.as_ref();
+            let Some((name, text)) = arg.split_once('=').filter(|_| named) else {
+                let schema = self.arguments.get(positional.len()).ok_or_else(|| {
+                    invalid(format!("expected at most {} parameters", self.arguments.len()))
+                })?;
+                positional.push(schema.coerce(arg)?);
+                continue;
+            };
+
+            let schema = self
+                .named_param_schema(name)
+                .ok_or_else(|| invalid(format!("unknown argument {:?}", name)))?;
+            if object.insert(name.to_string(), schema.coerce(text)?).is_some() {
+                return Err(invalid(format!("argument {:?} given more than once", name)));
+            }
+        }
+
+        if !named {
+            return Ok(Value::Array(positional));
+        }
+        if !positional.is_empty() {
+            if object.contains_key("args") {
+                return Err(invalid("argument \"args\" given more than once".to_string()));
+            }
+            object.insert("args".to_string(), Value::Array(positional));
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn arg(name: &str, type_: &str) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            ..BtcArgument::default()
+        }
+    }
+
+    #[test]
+    fn test_coerce() {
+        assert_eq!(arg("address", "string").coerce("true").unwrap(), json!("true"));
+        assert_eq!(arg("hexstring", "hex").coerce("00ff").unwrap(), json!("00ff"));
+        assert_eq!(arg("verbosity", "number").coerce("2").unwrap(), json!(2));
+        assert_eq!(arg("amount", "amount").coerce("0.1").unwrap(), json!(0.1));
+        assert_eq!(arg("replaceable", "boolean").coerce("false").unwrap(), json!(false));
+        assert_eq!(arg("txids", "array").coerce(r#"["aa"]"#).unwrap(), json!(["aa"]));
+        assert_eq!(arg("options", "object").coerce("null").unwrap(), Value::Null);
+        assert!(matches!(
+            arg("verbosity", "number").coerce("two"),
+            Err(SchemaError::InvalidValue(_))
+        ));
+
+        let union = BtcArgument {
+            type_str: Some(vec!["".to_string(), "string or numeric".to_string()]),
+            ..arg("hash_or_height", "string")
+        };
+        assert_eq!(union.coerce("100").unwrap(), json!(100));
+        assert_eq!(union.coerce(r#""00ff""#).unwrap(), json!("00ff"));
+        assert_eq!(union.coerce("00ff").unwrap(), json!("00ff"));
+    }
+
+    #[test]
+    fn test_coerce_cli_args() {
+        let send = BtcMethod {
+            name: "send".to_string(),
+            arguments: vec![
+                arg("outputs", "array"),
+                arg("conf_target", "number"),
+                BtcArgument {
+                    inner: vec![arg("add_inputs", "boolean"), arg("change_address", "string")],
+                    ..arg("options", "object_named_params")
+                },
+            ],
+            ..BtcMethod::default()
+        };
+
+        let params = send.coerce_cli_args(&[r#"[{"bc1q": 0.1}]"#, "6"], false).unwrap();
+        assert_eq!(params, json!([[{ "bc1q": 0.1 }], 6]));
+
+        let params = send
+            .coerce_cli_args(&["conf_target=6", "add_inputs=true", "change_address=1"], true)
+            .unwrap();
+        assert_eq!(params, json!({ "conf_target": 6, "add_inputs": true, "change_address": "1" }));
+
+        let params = send.coerce_cli_args(&["[]", "conf_target=6"], true).unwrap();
+        assert_eq!(params, json!({ "conf_target": 6, "args": [[]] }));
+
+        // Without -named, `=` has no special meaning
+        assert!(send.coerce_cli_args(&["conf_target=6"], false).is_err());
+        assert!(send.coerce_cli_args(&["[]", "1", "{}", "x"], false).is_err());
+        assert!(send.coerce_cli_args(&["foo=1"], true).is_err());
+        assert!(send.coerce_cli_args(&["conf_target=1", "conf_target=2"], true).is_err());
+    }
+}
@@ -0,0 +1,997 @@
+//! Rust source generation from the schema
+//!
+//! Turns `BtcResult` trees into Rust struct and enum definitions with serde
+//! attributes, arguments into params structs, and an `ApiDefinition` into a
+//! client trait, so downstream crates share one generator instead of each
+//! reimplementing it. Leaf types are resolved through a `TypeMapper`, usually
+//! `TypeMapping`; nested objects become their own structs, named after their
+//! parent and key. From an `ApiDefinitionSet`, one set of types covers
+//! several Core versions, with version-specific fields gated on the version
+//! features.
+//!
+//! Output is Rust source as a `String`, ready to be written to a file and
+//! formatted.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::definition_set::ApiDefinitionSet;
+use crate::fee_rate::FeeRateUnit;
+use crate::ident::RustIdent;
+use crate::rpc_type::RpcType;
+use crate::rustdoc::doc_comment;
+use crate::type_mapping::{child_path, TypeMapper, FEE_RATE_PARAM};
+use crate::types::{ApiDefinition, BtcMethod, BtcResult};
+use crate::version::CoreVersion;
+
+/// Derives added to every generated type
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]";
+
+/// Generates the Rust types for a result tree
+///
+/// The root type is named `type_name`: a struct for objects with described
+/// fields, or a type alias otherwise, whose elements are named
+/// `{type_name}Item`. Nested objects become structs named after their parent
+/// and key (`GetblockResponse` → `GetblockResponseTx`).
+///
+/// Node paths passed to the mapper are relative to `result`; use
+/// `BtcMethod::result_types` for paths that start with the method name.
+pub fn result_types(type_name: &str, result: &BtcResult, mapper: &dyn TypeMapper) -> String {
+    let generator = Generator { mapper, gates: BTreeMap::new(), items: Vec::new() };
+    generator.root(type_name, "", result)
+}
+
+impl BtcMethod {
+    /// Returns the name of the generated response type, e.g. `GetblockResponse`
+    pub fn response_type_name(&self) -> String {
+        format!("{}Response", RustIdent::pascal_case(&self.name))
+    }
+
+    /// Generates the Rust types for the method's response
+    ///
+    /// A method with alternative results (e.g. per verbosity) gets an
+    /// untagged enum with one variant per alternative, in schema order;
+    /// variants are named after the verbosity level where there is one.
+    pub fn result_types(&self, mapper: &dyn TypeMapper) -> String {
+        let variants = variant_names(&self.results)
+            .into_iter()
+            .zip(&self.results)
+            .map(|(name, result)| Variant { name, result, gate: None, fields: BTreeMap::new() })
+            .collect();
+        self.response_types(variants, mapper)
+    }
+
+    /// Generates the response types from the named alternatives
+    fn response_types(&self, mut variants: Vec<Variant<'_>>, mapper: &dyn TypeMapper) -> String {
+        let type_name = self.response_type_name();
+        match variants.as_mut_slice() {
+            [] =>
+                return format!("/// Response of `{}`\npub type {} = ();\n", self.name, type_name),
+            [variant] if variant.gate.is_none() => {
+                let gates = std::mem::take(&mut variant.fields);
+                let generator = Generator { mapper, gates, items: Vec::new() };
+                return generator.root(&type_name, &self.name, variant.result);
+            }
+            _ => {}
+        }
+
+        let mut generator = Generator { mapper, gates: BTreeMap::new(), items: Vec::new() };
+        let mut item = format!("/// Response of `{}`\n", self.name);
+        writeln!(item, "{}\n#[serde(untagged)]\npub enum {} {{", DERIVES, type_name).unwrap();
+        for variant in variants {
+            item.push_str(&doc_comment(&variant.result.condition, "    "));
+            if let Some(gate) = &variant.gate {
+                writeln!(item, "    {}", gate).unwrap();
+            }
+            if variant.result.rpc_type() == RpcType::None {
+                writeln!(item, "    {},", variant.name).unwrap();
+            } else {
+                generator.gates = variant.fields;
+                let variant_type = format!("{}{}", type_name, variant.name);
+                let rust = generator.rust_type(&variant_type, &self.name, variant.result);
+                writeln!(item, "    {}({}),", variant.name, rust).unwrap();
+            }
+        }
+        item.push_str("}\n");
+        generator.items.insert(0, item);
+        generator.items.join("\n")
+    }
+}
+
+impl ApiDefinitionSet {
+    /// Generates the Rust types for a method's response, covering every
+    /// version in the set that has the method
+    ///
+    /// Each version's results are merged into one set of types. Fields and
+    /// enum variants that only some of those versions have are gated on the
+    /// consuming crate's version features (`#[cfg(feature = "v28")]`,
+    /// `#[cfg(any(feature = "v27", feature = "v28"))]`). Alternatives are
+    /// matched by variant name and fields by key; where a node's definition
+    /// differs between versions, the newest one is used.
+    ///
+    /// Returns `None` if no version has the method.
+    pub fn result_types(&self, method: &str, mapper: &dyn TypeMapper) -> Option<String> {
+        let history: Vec<(CoreVersion, &BtcMethod)> = self.method_history(method).collect();
+        let (_, newest) = history.last()?;
+
+        // (name, merged result, versions with the variant, versions with each field)
+        type Merged = (String, BtcResult, BTreeSet<CoreVersion>, Presence);
+        let mut merged: Vec<Merged> = Vec::new();
+        for (version, btc_method) in &history {
+            for (name, result) in
+                variant_names(&btc_method.results).into_iter().zip(&btc_method.results)
+            {
+                let index = match merged.iter().position(|(merged_name, ..)| *merged_name == name) {
+                    Some(index) => {
+                        merge(&mut merged[index].1, result);
+                        index
+                    }
+                    None => {
+                        merged.push((name, result.clone(), BTreeSet::new(), Presence::new()));
+                        merged.len() - 1
+                    }
+                };
+                let (_, _, versions, presence) = &mut merged[index];
+                versions.insert(*version);
+                record_presence(result, method, *version, presence);
+            }
+        }
+
+        let all: BTreeSet<CoreVersion> = history.iter().map(|(version, _)| *version).collect();
+        let variants = merged
+            .iter()
+            .map(|(name, result, versions, presence)| Variant {
+                name: name.clone(),
+                result,
+                gate: (*versions != all).then(|| feature_gate(versions)),
+                fields: presence
+                    .iter()
+                    .filter(|(_, present)| *present != versions)
+                    .map(|(path, present)| (path.clone(), feature_gate(present)))
+                    .collect(),
+            })
+            .collect();
+        Some(newest.response_types(variants, mapper))
+    }
+}
+
+/// One alternative of a response, with the `#[cfg(..)]` attributes that
+/// gate it and its fields (by node path)
+struct Variant<'a> {
+    name: String,
+    result: &'a BtcResult,
+    gate: Option<String>,
+    fields: BTreeMap<String, String>,
+}
+
+/// Versions in which each keyed node path appears
+type Presence = BTreeMap<String, BTreeSet<CoreVersion>>;
+
+/// Names the enum variants of alternative results, after the verbosity
+/// level where there is one
+fn variant_names(results: &[BtcResult]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (index, result) in results.iter().enumerate() {
+        let name = match result.parsed_condition().verbosity() {
+            Some(level) if !names.contains(&format!("Verbosity{}", level)) =>
+                format!("Verbosity{}", level),
+            _ => format!("Variant{}", index),
+        };
+        names.push(name);
+    }
+    names
+}
+
+/// Merges a newer definition of a result node into an older one
+///
+/// Children are matched by key, or by position among the unkeyed children;
+/// children only in the older definition are kept. A node whose type changed
+/// is replaced outright.
+fn merge(older: &mut BtcResult, newer: &BtcResult) {
+    if older.rpc_type() != newer.rpc_type() {
+        *older = newer.clone();
+        return;
+    }
+    let mut inner = std::mem::take(&mut older.inner);
+    let mut unkeyed = 0;
+    for node in &newer.inner {
+        let matching = if node.key_name.is_empty() {
+            unkeyed += 1;
+            inner.iter_mut().filter(|older| older.key_name.is_empty()).nth(unkeyed - 1)
+        } else {
+            inner.iter_mut().find(|older| older.key_name == node.key_name)
+        };
+        match matching {
+            Some(older) => merge(older, node),
+            None => inner.push(node.clone()),
+        }
+    }
+    *older = BtcResult { inner, ..newer.clone() };
+}
+
+/// Records the version in which each keyed node below `result` appears
+fn record_presence(result: &BtcResult, path: &str, version: CoreVersion, presence: &mut Presence) {
+    for node in &result.inner {
+        let path = child_path(path, &node.key_name);
+        if !node.key_name.is_empty() {
+            presence.entry(path.clone()).or_default().insert(version);
+        }
+        record_presence(node, &path, version, presence);
+    }
+}
+
+/// Formats the `#[cfg(..)]` attribute enabling an item for the given versions
+fn feature_gate(versions: &BTreeSet<CoreVersion>) -> String {
+    let features: BTreeSet<String> =
+        versions.iter().map(|version| format!("feature = \"v{}\"", version.major())).collect();
+    let features: Vec<String> = features.into_iter().collect();
+    match features.as_slice() {
+        [feature] => format!("#[cfg({})]", feature),
+        features => format!("#[cfg(any({}))]", features.join(", ")),
+    }
+}
+
+impl BtcMethod {
+    /// Returns the name of the generated params type, e.g. `GetblockParams`
+    pub fn params_type_name(&self) -> String {
+        format!("{}Params", RustIdent::pascal_case(&self.name))
+    }
+
+    /// Generates a params struct for the method, with one field per argument
+    ///
+    /// `new` takes the required arguments and optional ones are set with
+    /// chained setters named after them. `to_named` returns the set
+    /// arguments by name, and `call_plan` lays them out through
+    /// `CallPlan::from_named`: in argument order, or by name when an unset
+    /// argument precedes a set one, so unset arguments are never sent as
+    /// `null`.
+    pub fn params_type(&self, mapper: &dyn TypeMapper) -> String {
+        let type_name = self.params_type_name();
+        let mut fields = String::new();
+        let mut params = Vec::new();
+        let mut initializers = Vec::new();
+        let mut setters = String::new();
+        let mut taken = BTreeSet::new();
+        for arg in &self.arguments {
+            let name = arg.names.first().map(String::as_str).unwrap_or_default();
+            let ident = RustIdent::snake_case(name).make_unique(&mut taken);
+            let rust = mapper.argument_type(&child_path(&self.name, name), arg);
+            fields.push_str(&doc_comment(&arg.description, "    "));
+            fields.push_str(&struct_field(&ident, name, &rust, !arg.required));
+
+            if arg.required {
+                params.push(format!("{}: {}", ident, rust));
+                initializers.push(ident.to_string());
+            } else {
+                initializers.push(format!("{}: None", ident));
+                writeln!(setters, "\n    /// Sets `{}`", name).unwrap();
+                writeln!(setters, "    pub fn {}(mut self, {}: {}) -> Self {{", ident, ident, rust)
+                    .unwrap();
+                writeln!(
+                    setters,
+                    "        self.{} = Some({});\n        self\n    }}",
+                    ident, ident
+                )
+                .unwrap();
+            }
+        }
+        let names: Vec<String> = self
+            .arguments
+            .iter()
+            .map(|arg| format!("{:?}", arg.names.first().map(String::as_str).unwrap_or_default()))
+            .collect();
+
+        // Params without required arguments are also `Default`
+        let derives = match params.is_empty() {
+            true => DERIVES.replacen("Clone", "Clone, Default", 1),
+            false => DERIVES.to_string(),
+        };
+        let mut source = format!(
+            "/// Parameters of `{}`\n{}\npub struct {} {{\n",
+            self.name, derives, type_name
+        );
+        source.push_str(&fields);
+        writeln!(source, "}}\n\nimpl {} {{", type_name).unwrap();
+        writeln!(source, "    /// Creates the params from the required arguments").unwrap();
+        writeln!(source, "    pub fn new({}) -> Self {{", params.join(", ")).unwrap();
+        match initializers.is_empty() {
+            true => source.push_str("        Self {}\n    }\n"),
+            false =>
+                writeln!(source, "        Self {{ {} }}\n    }}", initializers.join(", ")).unwrap(),
+        }
+        source.push_str(&setters);
+        source.push_str(
+            r#"
+    /// Returns the set arguments by name
+    pub fn to_named(&self) -> serde_json::Result<serde_json::Map<String, serde_json::Value>> {
+        match serde_json::to_value(self)? {
+            serde_json::Value::Object(named) => Ok(named),
+            value => Err(<serde_json::Error as serde::ser::Error>::custom(format_args!(
+                "params serialized to {} instead of an object",
+                value
+            ))),
+        }
+    }
+
+    /// Plans how to send the arguments: in order with trailing unset ones
+    /// dropped, or by name when an unset argument precedes a set one
+    pub fn call_plan(&self) -> serde_json::Result<bitcoin_rpc_types::CallPlan> {
+"#,
+        );
+        writeln!(source, "        const NAMES: &[&str] = &[{}];", names.join(", ")).unwrap();
+        source.push_str(
+            r#"        Ok(bitcoin_rpc_types::CallPlan::from_named(NAMES, self.to_named()?))
+    }
+}
+"#,
+        );
+        source
+    }
+}
+
+impl BtcMethod {
+    /// Generates the client trait method for this RPC, with its doc comment
+    ///
+    /// Required arguments become plain parameters and optional ones
+    /// `Option`s, in argument order; hidden arguments are omitted, as in the
+    /// generated docs. The result is the generated response type
+    /// (`response_type_name`) and the trait's `Self::Error`.
+    pub fn client_method(&self, mapper: &dyn TypeMapper) -> String {
+        let first_paragraph: String = self
+            .description
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .take_while(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut method = doc_comment(&first_paragraph, "    ");
+        if self.deprecated {
+            match &self.deprecation_note {
+                Some(note) => writeln!(method, "    #[deprecated(note = {:?})]", note).unwrap(),
+                None => method.push_str("    #[deprecated]\n"),
+            }
+        }
+
+        let mut params = vec!["&self".to_string()];
+        let mut taken = BTreeSet::new();
+        for arg in self.arguments.iter().filter(|arg| !arg.hidden) {
+            let name = arg.names.first().map(String::as_str).unwrap_or_default();
+            let ident = RustIdent::snake_case(name).make_unique(&mut taken);
+            let rust = mapper.argument_type(&child_path(&self.name, name), arg);
+            let rust = if arg.required { rust } else { format!("Option<{}>", rust) };
+            params.push(format!("{}: {}", ident, rust));
+        }
+        writeln!(
+            method,
+            "    fn {}({}) -> Result<{}, Self::Error>;",
+            RustIdent::snake_case(&self.name),
+            params.join(", "),
+            self.response_type_name()
+        )
+        .unwrap();
+        method
+    }
+}
+
+impl ApiDefinition {
+    /// Generates a client trait with one method per RPC, in name order
+    ///
+    /// The trait only fixes the shape of each call; implementors supply the
+    /// transport and the `Error` type. Response types are referenced by name
+    /// and are expected to be generated with `BtcMethod::result_types`.
+    pub fn client_trait(&self, trait_name: &str, mapper: &dyn TypeMapper) -> String {
+        let mut source = String::from("/// Bitcoin Core JSON-RPC client\n");
+        writeln!(source, "pub trait {} {{", trait_name).unwrap();
+        source.push_str("    /// Error returned by the transport or the node\n    type Error;\n");
+        for method in self.methods() {
+            source.push('\n');
+            source.push_str(&method.client_method(mapper));
+        }
+        source.push_str("}\n");
+        source
+    }
+}
+
+/// Collects generated items while walking a result tree
+struct Generator<'a> {
+    mapper: &'a dyn TypeMapper,
+    /// `#[cfg(..)]` attributes for fields, by node path
+    gates: BTreeMap<String, String>,
+    items: Vec<String>,
+}
+
+impl Generator<'_> {
+    /// Generates the types for a result tree whose root type is `type_name`
+    fn root(mut self, type_name: &str, path: &str, result: &BtcResult) -> String {
+        if self.is_struct(path, result) {
+            self.object(type_name, path, result);
+        } else {
+            let rust = self.rust_type(&format!("{}Item", type_name), path, result);
+            let mut alias = doc_comment(&result.description, "");
+            writeln!(alias, "pub type {} = {};", type_name, rust).unwrap();
+            self.items.insert(0, alias);
+        }
+        self.items.join("\n")
+    }
+
+    /// Whether a node is generated as a struct of its own
+    fn is_struct(&self, path: &str, result: &BtcResult) -> bool {
+        result.rpc_type() == RpcType::Object
+            && !result.is_map()
+            && fields(result).next().is_some()
+            && self.mapper.result_type(path, result) == "serde_json::Value"
+    }
+
+    /// Returns the Rust type of a node, generating structs named after
+    /// `type_name` for objects that the mapping leaves untyped
+    fn rust_type(&mut self, type_name: &str, path: &str, result: &BtcResult) -> String {
+        if self.is_struct(path, result) {
+            self.object(type_name, path, result);
+            return type_name.to_string();
+        }
+        let mapped = self.mapper.result_type(path, result);
+        if !mapped.contains("serde_json::Value") {
+            return mapped;
+        }
+
+        let items: Vec<&BtcResult> =
+            result.inner.iter().filter(|inner| !inner.is_elision()).collect();
+        if let Some(value) = result.map_value() {
+            return format!(
+                "std::collections::BTreeMap<String, {}>",
+                self.rust_type(type_name, path, value)
+            );
+        }
+        match (result.rpc_type(), items.as_slice()) {
+            (RpcType::Array, [item]) => format!("Vec<{}>", self.rust_type(type_name, path, item)),
+            (RpcType::ArrayFixed, items) => {
+                let types: Vec<String> = items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        self.rust_type(&format!("{}{}", type_name, index), path, item)
+                    })
+                    .collect();
+                format!("({},)", types.join(", "))
+            }
+            _ => mapped,
+        }
+    }
+
+    fn object(&mut self, type_name: &str, path: &str, result: &BtcResult) {
+        let index = self.items.len();
+        self.items.push(String::new());
+
+        let mut item = doc_comment(&result.description, "");
+        writeln!(item, "{}\npub struct {} {{", DERIVES, type_name).unwrap();
+        let mut taken = BTreeSet::new();
+        for field in fields(result) {
+            let ident = RustIdent::snake_case(&field.key_name).make_unique(&mut taken);
+            let nested = format!("{}{}", type_name, RustIdent::pascal_case(&field.key_name));
+            let field_path = child_path(path, &field.key_name);
+            let rust = self.rust_type(&nested, &field_path, field);
+
+            item.push_str(&doc_comment(&field.description, "    "));
+            if let Some(gate) = self.gates.get(&field_path) {
+                writeln!(item, "    {}", gate).unwrap();
+            }
+            item.push_str(&struct_field(&ident, &field.key_name, &rust, field.optional));
+        }
+        item.push_str("}\n");
+        self.items[index] = item;
+    }
+}
+
+/// Formats a struct field with its serde attributes; optional fields become
+/// `Option`s that are omitted when unset
+fn struct_field(ident: &RustIdent, name: &str, rust: &str, optional: bool) -> String {
+    let mut attributes = Vec::new();
+    if let Some(rename) = ident.serde_rename() {
+        attributes.push(format!("rename = {:?}", rename));
+    }
+    if optional {
+        attributes.push("default".to_string());
+        attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+    }
+    if let Some(with) = serde_with(name, rust, optional) {
+        attributes.push(format!("with = {:?}", with));
+    }
+
+    let mut field = String::new();
+    if !attributes.is_empty() {
+        writeln!(field, "    #[serde({})]", attributes.join(", ")).unwrap();
+    }
+    match optional {
+        true => writeln!(field, "    pub {}: Option<{}>,", ident, rust).unwrap(),
+        false => writeln!(field, "    pub {}: {},", ident, rust).unwrap(),
+    }
+    field
+}
+
+/// Amount types that Bitcoin Core writes in BTC
+const AMOUNT_TYPES: &[&str] = &["bitcoin::Amount", "bitcoin::SignedAmount"];
+
+/// Returns the serde `with` module a field of this type needs, if any
+///
+/// Amounts are written in BTC: plain amount fields use bitcoin's `as_btc`,
+/// and amounts nested in containers (`Vec<bitcoin::Amount>`, maps, tuples)
+/// use `bitcoin_rpc_types::amount::as_btc`, which handles `Option` itself.
+/// Fee rates are written in the unit their name implies (see
+/// `FeeRateUnit::for_argument`).
+fn serde_with(name: &str, rust: &str, optional: bool) -> Option<String> {
+    if rust == FEE_RATE_PARAM {
+        let module = match FeeRateUnit::for_argument(name)? {
+            FeeRateUnit::SatPerVb => "as_sat_per_vb",
+            FeeRateUnit::BtcPerKvb => "as_btc_per_kvb",
+        };
+        let opt = if optional { "::opt" } else { "" };
+        return Some(format!("bitcoin_rpc_types::fee_rate::{}{}", module, opt));
+    }
+    if AMOUNT_TYPES.contains(&rust) {
+        let with = if optional { "as_btc::opt" } else { "as_btc" };
+        return Some(format!("bitcoin::amount::serde::{}", with));
+    }
+    let mut tokens = rust.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'));
+    match tokens.any(|token| AMOUNT_TYPES.contains(&token)) {
+        true => Some("bitcoin_rpc_types::amount::as_btc".to_string()),
+        false => None,
+    }
+}
+
+/// Returns the fields of an object that get a struct field
+fn fields(result: &BtcResult) -> impl Iterator<Item = &BtcResult> {
+    result
+        .inner
+        .iter()
+        .filter(|field| !field.is_elision() && !field.dynamic_key && !field.key_name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_mapping::TypeMapping;
+    use crate::types::BtcArgument;
+
+    fn node(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            key_name: key_name.to_string(),
+            type_: type_.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    #[test]
+    fn test_result_types_struct() {
+        let result = BtcResult {
+            description: "Block information".to_string(),
+            ..node(
+                "",
+                "object",
+                vec![
+                    BtcResult {
+                        description: "the block hash".to_string(),
+                        ..node("hash", "hex", vec![])
+                    },
+                    BtcResult { optional: true, ..node("nextblockhash", "hex", vec![]) },
+                    node("bip125-replaceable", "string", vec![]),
+                    node("type", "string", vec![]),
+                    node("fee", "amount", vec![]),
+                    BtcResult { optional: true, ..node("feeRate", "amount", vec![]) },
+                    node(
+                        "tx",
+                        "array",
+                        vec![
+                            node("", "object", vec![node("txid", "hex", vec![])]),
+                            node("", "elision", vec![]),
+                        ],
+                    ),
+                ],
+            )
+        };
+
+        let source = result_types("GetblockResponse", &result, &TypeMapping::new());
+        assert_eq!(
+            source,
+            r#"/// Block information
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetblockResponse {
+    /// the block hash
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nextblockhash: Option<bitcoin::BlockHash>,
+    #[serde(rename = "bip125-replaceable")]
+    pub bip125_replaceable: String,
+    pub r#type: String,
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub fee: bitcoin::SignedAmount,
+    #[serde(rename = "feeRate", default, skip_serializing_if = "Option::is_none", with = "bitcoin_rpc_types::fee_rate::as_btc_per_kvb::opt")]
+    pub fee_rate: Option<bitcoin_rpc_types::FeeRateParam>,
+    pub tx: Vec<GetblockResponseTx>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetblockResponseTx {
+    pub txid: bitcoin::Txid,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_result_types_signed_and_fractional_numbers() {
+        let peer = node(
+            "",
+            "object",
+            vec![
+                node("id", "number", vec![]),
+                node("startingheight", "number", vec![]),
+                node("pingtime", "number", vec![]),
+            ],
+        );
+        assert_eq!(
+            result_types("GetpeerinfoResponse", &peer, &TypeMapping::new()),
+            r#"#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetpeerinfoResponse {
+    pub id: u64,
+    pub startingheight: i64,
+    pub pingtime: f64,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_result_types_aliases() {
+        let mapping = TypeMapping::new();
+        assert_eq!(
+            result_types("GetblockcountResponse", &node("", "number", vec![]), &mapping),
+            "pub type GetblockcountResponse = u64;\n"
+        );
+
+        let mempool = node(
+            "",
+            "object_dynamic",
+            vec![node("", "object", vec![node("vsize", "number", vec![])])],
+        );
+        assert_eq!(
+            result_types("GetrawmempoolResponse", &mempool, &mapping),
+            r#"pub type GetrawmempoolResponse = std::collections::BTreeMap<String, GetrawmempoolResponseItem>;
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetrawmempoolResponseItem {
+    pub vsize: u64,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_result_types_path_overrides() {
+        let method = BtcMethod {
+            name: "getblockchaininfo".to_string(),
+            arguments: vec![],
+            results: vec![node(
+                "",
+                "object",
+                vec![node("chain", "string", vec![]), node("warnings", "string", vec![])],
+            )],
+            ..BtcMethod::default()
+        };
+        let mapping = TypeMapping::new()
+            .with_result_path_override("getblockchaininfo.chain", "bitcoin::Network");
+        let source = method.result_types(&mapping);
+        assert!(source.contains("    pub chain: bitcoin::Network,\n"), "{}", source);
+        assert!(source.contains("    pub warnings: String,\n"), "{}", source);
+
+        // Free-standing result trees have paths relative to the root
+        let mapping = TypeMapping::new().with_result_path_override("chain", "bitcoin::Network");
+        let source = result_types("Info", &method.results[0], &mapping);
+        assert!(source.contains("    pub chain: bitcoin::Network,\n"), "{}", source);
+    }
+
+    #[test]
+    fn test_versioned_result_types() {
+        let peers = |fields: &[&str]| BtcMethod {
+            name: "getpeerinfo".to_string(),
+            results: vec![node(
+                "",
+                "array",
+                vec![node(
+                    "",
+                    "object",
+                    fields.iter().map(|key| node(key, "string", vec![])).collect(),
+                )],
+            )],
+            ..BtcMethod::default()
+        };
+        let api = |method: BtcMethod| {
+            let mut api_def = ApiDefinition::new();
+            api_def.insert_method(method).unwrap();
+            api_def
+        };
+        let set: ApiDefinitionSet = [
+            (CoreVersion::V27, api(peers(&["addr", "network"]))),
+            (CoreVersion::V28, api(peers(&["addr", "network", "transport_protocol_type"]))),
+            (CoreVersion::V29, api(peers(&["network", "transport_protocol_type"]))),
+        ]
+        .into_iter()
+        .collect();
+
+        let source = set.result_types("getpeerinfo", &TypeMapping::new()).unwrap();
+        assert_eq!(
+            source,
+            r#"pub type GetpeerinfoResponse = Vec<GetpeerinfoResponseItem>;
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetpeerinfoResponseItem {
+    #[cfg(any(feature = "v27", feature = "v28"))]
+    pub addr: String,
+    pub network: String,
+    #[cfg(any(feature = "v28", feature = "v29"))]
+    pub transport_protocol_type: String,
+}
+"#
+        );
+        assert!(set.result_types("getblock", &TypeMapping::new()).is_none());
+
+        // Alternatives are matched by name and gated like fields
+        let block = |levels: &[u8]| BtcMethod {
+            name: "getblock".to_string(),
+            results: levels
+                .iter()
+                .map(|level| BtcResult {
+                    condition: format!("for verbosity = {}", level),
+                    ..node("", "object", vec![node("hash", "hex", vec![])])
+                })
+                .collect(),
+            ..BtcMethod::default()
+        };
+        let set: ApiDefinitionSet =
+            [(CoreVersion::V28, api(block(&[0, 1]))), (CoreVersion::V29, api(block(&[0, 1, 3])))]
+                .into_iter()
+                .collect();
+        let source = set.result_types("getblock", &TypeMapping::new()).unwrap();
+        assert!(source.contains("    Verbosity1(GetblockResponseVerbosity1),\n"), "{}", source);
+        assert!(
+            source.contains(
+                "    #[cfg(feature = \"v29\")]\n    Verbosity3(GetblockResponseVerbosity3),\n"
+            ),
+            "{}",
+            source
+        );
+    }
+
+    #[test]
+    fn test_params_type() {
+        let arg = |name: &str, type_: &str, required: bool| BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            ..BtcArgument::default()
+        };
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            arguments: vec![
+                BtcArgument {
+                    description: "The block hash".to_string(),
+                    ..arg("blockhash", "hex", true)
+                },
+                arg("verbosity", "number", false),
+                arg("fee_rate", "amount", false),
+            ],
+            ..BtcMethod::default()
+        };
+        let source = method.params_type(&TypeMapping::new());
+        assert!(
+            source.starts_with(
+                r#"/// Parameters of `getblock`
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GetblockParams {
+    /// The block hash
+    pub blockhash: bitcoin::BlockHash,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "bitcoin_rpc_types::fee_rate::as_sat_per_vb::opt")]
+    pub fee_rate: Option<bitcoin_rpc_types::FeeRateParam>,
+}
+
+impl GetblockParams {
+    /// Creates the params from the required arguments
+    pub fn new(blockhash: bitcoin::BlockHash) -> Self {
+        Self { blockhash, verbosity: None, fee_rate: None }
+    }
+
+    /// Sets `verbosity`
+    pub fn verbosity(mut self, verbosity: u64) -> Self {
+        self.verbosity = Some(verbosity);
+        self
+    }
+"#
+            ),
+            "{}",
+            source
+        );
+        assert!(source.contains(
+            "        const NAMES: &[&str] = &[\"blockhash\", \"verbosity\", \"fee_rate\"];\n"
+        ));
+        assert!(source.contains("bitcoin_rpc_types::CallPlan::from_named(NAMES, self.to_named()?)"));
+        assert!(!source.contains("unreachable!"), "{}", source);
+
+        let getblockcount = BtcMethod { name: "getblockcount".to_string(), ..BtcMethod::default() };
+        let source = getblockcount.params_type(&TypeMapping::new());
+        assert!(source.contains("#[derive(Debug, Clone, Default, PartialEq,"), "{}", source);
+        assert!(source.contains("        Self {}\n"), "{}", source);
+    }
+
+    #[test]
+    fn test_nested_amount_fields() {
+        let method = BtcMethod {
+            name: "sendmany".to_string(),
+            arguments: vec![
+                BtcArgument {
+                    names: vec!["amounts".to_string()],
+                    type_: "object_user_keys".to_string(),
+                    required: true,
+                    inner: vec![BtcArgument {
+                        names: vec!["address".to_string()],
+                        type_: "amount".to_string(),
+                        ..BtcArgument::default()
+                    }],
+                    ..BtcArgument::default()
+                },
+                BtcArgument {
+                    names: vec!["subtractfeefrom".to_string()],
+                    type_: "array".to_string(),
+                    ..BtcArgument::default()
+                },
+            ],
+            ..BtcMethod::default()
+        };
+        let source = method.params_type(&TypeMapping::new());
+        assert!(
+            source.contains(
+                r#"    #[serde(with = "bitcoin_rpc_types::amount::as_btc")]
+    pub amounts: std::collections::BTreeMap<String, bitcoin::Amount>,
+"#
+            ),
+            "{}",
+            source
+        );
+        assert!(
+            source.contains(
+                r#"    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtractfeefrom: Option<Vec<serde_json::Value>>,
+"#
+            ),
+            "{}",
+            source
+        );
+
+        let balances = node(
+            "",
+            "object",
+            vec![BtcResult {
+                optional: true,
+                ..node("fees", "array", vec![node("", "amount", vec![])])
+            }],
+        );
+        let source = result_types("Balances", &balances, &TypeMapping::new());
+        assert!(
+            source.contains(
+                r#"    #[serde(default, skip_serializing_if = "Option::is_none", with = "bitcoin_rpc_types::amount::as_btc")]
+    pub fees: Option<Vec<bitcoin::SignedAmount>>,
+"#
+            ),
+            "{}",
+            source
+        );
+    }
+
+    #[test]
+    fn test_method_result_types_enum() {
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![
+                BtcResult { condition: "for verbosity = 0".to_string(), ..node("", "hex", vec![]) },
+                BtcResult {
+                    condition: "for verbosity = 1".to_string(),
+                    ..node("", "object", vec![node("hash", "hex", vec![])])
+                },
+                BtcResult {
+                    condition: "if the block is pruned".to_string(),
+                    ..node("", "none", vec![])
+                },
+            ],
+            ..BtcMethod::default()
+        };
+        let source = method.result_types(&TypeMapping::new());
+        assert!(source.starts_with(
+            r#"/// Response of `getblock`
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum GetblockResponse {
+    /// for verbosity = 0
+    Verbosity0(String),
+    /// for verbosity = 1
+    Verbosity1(GetblockResponseVerbosity1),
+    /// if the block is pruned
+    Variant2,
+}
+"#
+        ));
+        assert!(source.contains("pub struct GetblockResponseVerbosity1 {"));
+
+        let ping = BtcMethod { name: "ping".to_string(), ..BtcMethod::default() };
+        assert_eq!(
+            ping.result_types(&TypeMapping::new()),
+            "/// Response of `ping`\npub type PingResponse = ();\n"
+        );
+    }
+
+    #[test]
+    fn test_client_trait() {
+        let arg = |name: &str, type_: &str, required: bool| BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            ..BtcArgument::default()
+        };
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblock".to_string(),
+            BtcMethod {
+                name: "getblock".to_string(),
+                description: "\nIf verbosity is 0, returns a string.\nOtherwise an object.\n\nArguments:\n..."
+                    .to_string(),
+                arguments: vec![
+                    arg("blockhash", "hex", true),
+                    arg("verbosity", "number", false),
+                    BtcArgument { hidden: true, ..arg("debug", "boolean", false) },
+                ],
+                ..BtcMethod::default()
+            },
+        );
+        api_def.rpcs.insert(
+            "getinfo".to_string(),
+            BtcMethod {
+                name: "getinfo".to_string(),
+                deprecated: true,
+                deprecation_note: Some("use getnetworkinfo".to_string()),
+                ..BtcMethod::default()
+            },
+        );
+
+        assert_eq!(
+            api_def.client_trait("BitcoinRpc", &TypeMapping::new()),
+            r#"/// Bitcoin Core JSON-RPC client
+pub trait BitcoinRpc {
+    /// Error returned by the transport or the node
+    type Error;
+
+    /// If verbosity is 0, returns a string.
+    /// Otherwise an object.
+    fn getblock(&self, blockhash: bitcoin::BlockHash, verbosity: Option<u64>) -> Result<GetblockResponse, Self::Error>;
+
+    #[deprecated(note = "use getnetworkinfo")]
+    fn getinfo(&self) -> Result<GetinfoResponse, Self::Error>;
+}
+"#
+        );
+
+        // Arguments whose names sanitize to the same ident stay distinct
+        let method = BtcMethod {
+            name: "bumpfee".to_string(),
+            arguments: vec![arg("fee_rate", "number", false), arg("fee-rate", "number", false)],
+            ..BtcMethod::default()
+        };
+        let source = method.client_method(&TypeMapping::new());
+        assert!(
+            source.contains(
+                "fn bumpfee(&self, fee_rate: Option<bitcoin_rpc_types::FeeRateParam>, fee_rate_2: Option<u64>)"
+            ),
+            "{}",
+            source
+        );
+    }
+}
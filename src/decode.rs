@@ -0,0 +1,303 @@
+//! Schema-driven response decoding
+//!
+//! Generated types need the result shape at compile time. Explorers and
+//! other generic tools only learn the method at runtime; `DecodedValue` gives
+//! them a tree where the schema already picked out amounts, hashes and times,
+//! without any per-method code.
+
+use std::collections::BTreeMap;
+
+use bitcoin::hex::{DisplayHex, FromHex};
+use bitcoin::{BlockHash, SignedAmount, Txid, Wtxid};
+use serde_json::{Number, Value};
+
+use crate::rpc_type::RpcType;
+use crate::type_mapping::TypeMapping;
+use crate::types::{ApiDefinition, BtcMethod, BtcResult, Result, SchemaError};
+use crate::validate::{validate_response_with, ValidationOptions};
+
+/// A response value, typed where its schema node says what it holds
+///
+/// Values whose schema node is unknown, or that do not parse as the type
+/// the schema names, keep their plain JSON form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedValue {
+    /// `null`
+    Null,
+    /// Boolean
+    Bool(bool),
+    /// Number without a more specific type
+    Number(Number),
+    /// String without a more specific type
+    String(String),
+    /// Amount in BTC; negative for e.g. fees in wallet transaction lists
+    Amount(SignedAmount),
+    /// Transaction id
+    Txid(Txid),
+    /// Witness transaction id
+    Wtxid(Wtxid),
+    /// Block hash
+    BlockHash(BlockHash),
+    /// Hex-encoded data
+    Hex(Vec<u8>),
+    /// Unix timestamp in seconds
+    Timestamp(u64),
+    /// Array
+    Array(Vec<DecodedValue>),
+    /// Object, keyed by member name
+    Object(BTreeMap<String, DecodedValue>),
+}
+
+impl DecodedValue {
+    /// Returns the member of an object, if this is an object that has it
+    pub fn get(&self, key: &str) -> Option<&DecodedValue> {
+        match self {
+            Self::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the amount, if this is an amount
+    pub fn as_amount(&self) -> Option<SignedAmount> {
+        match self {
+            Self::Amount(amount) => Some(*amount),
+            _ => None,
+        }
+    }
+
+    /// Returns the timestamp, if this is a timestamp
+    pub fn as_timestamp(&self) -> Option<u64> {
+        match self {
+            Self::Timestamp(time) => Some(*time),
+            _ => None,
+        }
+    }
+
+    /// Converts the value back into JSON, as the node sent it
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Number(number) => Value::Number(number.clone()),
+            Self::String(string) => Value::String(string.clone()),
+            Self::Amount(amount) =>
+                Number::from_f64(amount.to_btc()).map_or(Value::Null, Value::Number),
+            Self::Txid(txid) => Value::String(txid.to_string()),
+            Self::Wtxid(wtxid) => Value::String(wtxid.to_string()),
+            Self::BlockHash(hash) => Value::String(hash.to_string()),
+            Self::Hex(bytes) => Value::String(bytes.to_lower_hex_string()),
+            Self::Timestamp(time) => Value::from(*time),
+            Self::Array(items) => Value::Array(items.iter().map(Self::to_json).collect()),
+            Self::Object(members) => Value::Object(
+                members.iter().map(|(key, value)| (key.clone(), value.to_json())).collect(),
+            ),
+        }
+    }
+}
+
+impl From<&Value> for DecodedValue {
+    /// Converts JSON without a schema; no value gets a specific type
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(*value),
+            Value::Number(number) => Self::Number(number.clone()),
+            Value::String(string) => Self::String(string.clone()),
+            Value::Array(items) => Self::Array(items.iter().map(Self::from).collect()),
+            Value::Object(members) => Self::Object(
+                members.iter().map(|(key, value)| (key.clone(), Self::from(value))).collect(),
+            ),
+        }
+    }
+}
+
+impl ApiDefinition {
+    /// Decodes a method's response using the method's result schema
+    ///
+    /// Fails with `SchemaError::UnknownMethod` if the method is not in the
+    /// definition.
+    pub fn decode_response(&self, method: &str, value: &Value) -> Result<DecodedValue> {
+        let btc_method = self
+            .get_method(method)
+            .ok_or_else(|| SchemaError::UnknownMethod(method.to_string()))?;
+        Ok(btc_method.decode_response(value))
+    }
+}
+
+impl BtcMethod {
+    /// Decodes a response using the first alternative result it validates
+    /// against, or without a schema if it matches none
+    ///
+    /// Alternatives are checked with `ValidationOptions::lenient`, so fields
+    /// added by newer nodes do not prevent decoding; they stay untyped.
+    pub fn decode_response(&self, value: &Value) -> DecodedValue {
+        match self.results.iter().find(|result| matches(result, value)) {
+            Some(result) => result.decode(value),
+            None => DecodedValue::from(value),
+        }
+    }
+}
+
+impl BtcResult {
+    /// Decodes a value described by this result node
+    ///
+    /// Leaves are typed as `TypeMapping` maps them: amounts, transaction ids
+    /// and block hashes by type and key name, other hex strings as bytes and
+    /// times as timestamps.
+    pub fn decode(&self, value: &Value) -> DecodedValue {
+        if self.skip_type_check {
+            return DecodedValue::from(value);
+        }
+        let typed = match (self.rpc_type(), value) {
+            (_, Value::Null) => None,
+            (RpcType::Time, value) => value.as_u64().map(DecodedValue::Timestamp),
+            (RpcType::Amount | RpcType::Hex | RpcType::String, value) => self.decode_leaf(value),
+            (RpcType::Object | RpcType::ObjectDynamic, Value::Object(members)) => {
+                let decoded = members.iter().map(|(key, member)| {
+                    let schema = self
+                        .map_value()
+                        .or_else(|| self.inner.iter().find(|field| &field.key_name == key));
+                    let member = match schema {
+                        Some(schema) => schema.decode(member),
+                        None => DecodedValue::from(member),
+                    };
+                    (key.clone(), member)
+                });
+                Some(DecodedValue::Object(decoded.collect()))
+            }
+            (RpcType::Array, Value::Array(items)) => {
+                let schemas: Vec<&BtcResult> =
+                    self.inner.iter().filter(|inner| !inner.is_elision()).collect();
+                let decoded = items.iter().map(|item| {
+                    match schemas.iter().find(|schema| matches(schema, item)) {
+                        Some(schema) => schema.decode(item),
+                        None => DecodedValue::from(item),
+                    }
+                });
+                Some(DecodedValue::Array(decoded.collect()))
+            }
+            (RpcType::ArrayFixed, Value::Array(items)) => {
+                let mut schemas = self.inner.iter().filter(|inner| !inner.is_elision());
+                let decoded = items.iter().map(|item| match schemas.next() {
+                    Some(schema) => schema.decode(item),
+                    None => DecodedValue::from(item),
+                });
+                Some(DecodedValue::Array(decoded.collect()))
+            }
+            _ => None,
+        };
+        typed.unwrap_or_else(|| DecodedValue::from(value))
+    }
+
+    fn decode_leaf(&self, value: &Value) -> Option<DecodedValue> {
+        let text = value.as_str();
+        match TypeMapping::new().result_type(self).as_str() {
+            "bitcoin::Amount" | "bitcoin::SignedAmount" =>
+                SignedAmount::from_btc(value.as_f64()?).ok().map(DecodedValue::Amount),
+            "bitcoin::Txid" => text?.parse().ok().map(DecodedValue::Txid),
+            "bitcoin::Wtxid" => text?.parse().ok().map(DecodedValue::Wtxid),
+            "bitcoin::BlockHash" => text?.parse().ok().map(DecodedValue::BlockHash),
+            _ if self.rpc_type() == RpcType::Hex =>
+                Vec::from_hex(text?).ok().map(DecodedValue::Hex),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a value fits a result node, tolerating unknown keys and `null`s
+fn matches(result: &BtcResult, value: &Value) -> bool {
+    validate_response_with(result, value, &ValidationOptions::lenient()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    fn field(key: &str, type_: &str) -> BtcResult {
+        BtcResult { type_: type_.to_string(), key_name: key.to_string(), ..BtcResult::default() }
+    }
+
+    fn getblock() -> BtcMethod {
+        BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![
+                field("", "hex"),
+                BtcResult {
+                    inner: vec![
+                        field("hash", "hex"),
+                        field("previousblockhash", "hex"),
+                        field("time", "time"),
+                        field("fee", "amount"),
+                        field("height", "number"),
+                        BtcResult {
+                            inner: vec![BtcResult {
+                                inner: vec![field("txid", "hex"), field("hex", "hex")],
+                                ..field("", "object")
+                            }],
+                            ..field("tx", "array")
+                        },
+                    ],
+                    ..field("", "object")
+                },
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_response() {
+        let method = getblock();
+        let value = json!({
+            "hash": "00ff",
+            "previousblockhash": HASH,
+            "time": 1231006505,
+            "fee": -0.0001,
+            "height": 0,
+            "tx": [{ "txid": TXID, "hex": "01" }],
+            "extra": "x",
+        });
+        let decoded = method.decode_response(&value);
+        assert_eq!(decoded.get("hash"), Some(&DecodedValue::Hex(vec![0x00, 0xff])));
+        assert_eq!(
+            decoded.get("previousblockhash"),
+            Some(&DecodedValue::BlockHash(HASH.parse().unwrap()))
+        );
+        assert_eq!(decoded.get("time").and_then(DecodedValue::as_timestamp), Some(1231006505));
+        assert_eq!(
+            decoded.get("fee").and_then(DecodedValue::as_amount),
+            Some(SignedAmount::from_sat(-10_000))
+        );
+        assert_eq!(decoded.get("height"), Some(&DecodedValue::Number(0.into())));
+        let DecodedValue::Array(txs) = decoded.get("tx").unwrap() else { panic!("expected array") };
+        assert_eq!(txs[0].get("txid"), Some(&DecodedValue::Txid(TXID.parse().unwrap())));
+        assert_eq!(txs[0].get("hex"), Some(&DecodedValue::Hex(vec![0x01])));
+        assert_eq!(decoded.get("extra"), Some(&DecodedValue::String("x".to_string())));
+        assert_eq!(decoded.to_json(), value);
+
+        let decoded = method.decode_response(&json!("00ff"));
+        assert_eq!(decoded, DecodedValue::Hex(vec![0x00, 0xff]));
+    }
+
+    #[test]
+    fn test_decode_fallback() {
+        let method = getblock();
+        assert_eq!(method.decode_response(&json!(5)), DecodedValue::Number(5.into()));
+        assert_eq!(
+            field("hash", "hex").decode(&json!("not hex")),
+            DecodedValue::String("not hex".to_string())
+        );
+
+        let mut api_def = ApiDefinition::new();
+        api_def.insert_method(method).unwrap();
+        assert!(api_def.decode_response("getblock", &json!("00")).is_ok());
+        assert!(matches!(
+            api_def.decode_response("getblok", &Value::Null),
+            Err(SchemaError::UnknownMethod(method)) if method == "getblok"
+        ));
+    }
+}
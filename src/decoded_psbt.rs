@@ -0,0 +1,340 @@
+//! `decodepsbt` result types
+//!
+//! This module provides `DecodedPsbt`, returned by `decodepsbt`, with its
+//! per-input and per-output maps. Keys, signatures and preimages are kept as
+//! the hex strings Bitcoin Core returns; transactions and output scripts
+//! reuse the `decoded_tx` types.
+
+use std::collections::BTreeMap;
+
+use bitcoin::bip32::{DerivationPath, Fingerprint};
+use bitcoin::{Amount, ScriptBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::decoded_tx::{DecodedScriptPubKey, DecodedScriptSig, DecodedTransaction};
+
+/// Result of `decodepsbt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedPsbt {
+    /// The unsigned transaction
+    pub tx: DecodedTransaction,
+    /// Extended public keys with their origin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub global_xpubs: Vec<PsbtGlobalXpub>,
+    /// PSBT version
+    pub psbt_version: u32,
+    /// Proprietary global records
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proprietary: Vec<PsbtProprietary>,
+    /// Unknown global records, hex key to hex value
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unknown: BTreeMap<String, String>,
+    /// Per-input data, in input order
+    pub inputs: Vec<DecodedPsbtInput>,
+    /// Per-output data, in output order
+    pub outputs: Vec<DecodedPsbtOutput>,
+    /// Fee, if the values of all inputs are known
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<Amount>,
+}
+
+impl DecodedPsbt {
+    /// Returns whether every input has its final script fields set
+    pub fn is_finalized(&self) -> bool { self.inputs.iter().all(DecodedPsbtInput::is_finalized) }
+}
+
+/// Extended public key of a PSBT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtGlobalXpub {
+    /// The extended public key
+    pub xpub: String,
+    /// Fingerprint of the master key
+    pub master_fingerprint: Fingerprint,
+    /// Derivation path from the master key
+    pub path: DerivationPath,
+}
+
+/// Proprietary PSBT record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtProprietary {
+    /// Identifier prefix, as hex
+    pub identifier: String,
+    /// Subtype
+    pub subtype: u64,
+    /// Key, as hex
+    pub key: String,
+    /// Value, as hex
+    pub value: String,
+}
+
+/// Decoded script without an address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedScript {
+    /// Disassembly
+    pub asm: String,
+    /// The script
+    pub hex: ScriptBuf,
+    /// Script type
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Witness UTXO of a PSBT input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtWitnessUtxo {
+    /// Value of the output
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: Amount,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: DecodedScriptPubKey,
+}
+
+/// BIP32 origin of a public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtBip32Deriv {
+    /// Public key, as hex
+    pub pubkey: String,
+    /// Fingerprint of the master key
+    pub master_fingerprint: Fingerprint,
+    /// Derivation path from the master key
+    pub path: DerivationPath,
+}
+
+/// BIP32 origin of a taproot x-only public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtTaprootBip32Deriv {
+    /// X-only public key, as hex
+    pub pubkey: String,
+    /// Fingerprint of the master key
+    pub master_fingerprint: Fingerprint,
+    /// Derivation path from the master key
+    pub path: DerivationPath,
+    /// Hashes of the leaves the key is used in, as hex
+    pub leaf_hashes: Vec<String>,
+}
+
+/// Taproot script path signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtTaprootScriptPathSig {
+    /// X-only public key, as hex
+    pub pubkey: String,
+    /// Hash of the leaf, as hex
+    pub leaf_hash: String,
+    /// Signature, as hex
+    pub sig: String,
+}
+
+/// Taproot leaf script with its control blocks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtTaprootScript {
+    /// The leaf script
+    pub script: ScriptBuf,
+    /// Leaf version
+    pub leaf_ver: u8,
+    /// Control blocks for the script, as hex
+    pub control_blocks: Vec<String>,
+}
+
+/// Leaf of a taproot output tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsbtTaprootLeaf {
+    /// Depth of the leaf in the tree
+    pub depth: u8,
+    /// Leaf version
+    pub leaf_ver: u8,
+    /// The leaf script
+    pub script: ScriptBuf,
+}
+
+/// Per-input data of a decoded PSBT
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedPsbtInput {
+    /// Full transaction of the spent output, for non-witness inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_witness_utxo: Option<DecodedTransaction>,
+    /// The spent output, for witness inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_utxo: Option<PsbtWitnessUtxo>,
+    /// Partial signatures, hex public key to hex signature
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub partial_signatures: BTreeMap<String, String>,
+    /// Sighash type to sign with, e.g. `ALL|ANYONECANPAY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sighash: Option<String>,
+    /// Redeem script, for P2SH inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redeem_script: Option<DecodedScript>,
+    /// Witness script, for P2WSH inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_script: Option<DecodedScript>,
+    /// BIP32 origins of the public keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bip32_derivs: Vec<PsbtBip32Deriv>,
+    /// Final signature script
+    #[serde(rename = "final_scriptSig", default, skip_serializing_if = "Option::is_none")]
+    pub final_script_sig: Option<DecodedScriptSig>,
+    /// Final witness stack items, as hex
+    #[serde(rename = "final_scriptwitness", default, skip_serializing_if = "Option::is_none")]
+    pub final_script_witness: Option<Vec<String>>,
+    /// RIPEMD160 preimages, hex hash to hex preimage
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ripemd160_preimages: BTreeMap<String, String>,
+    /// SHA256 preimages, hex hash to hex preimage
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sha256_preimages: BTreeMap<String, String>,
+    /// HASH160 preimages, hex hash to hex preimage
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hash160_preimages: BTreeMap<String, String>,
+    /// HASH256 preimages, hex hash to hex preimage
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hash256_preimages: BTreeMap<String, String>,
+    /// Taproot key path signature, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taproot_key_path_sig: Option<String>,
+    /// Taproot script path signatures
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taproot_script_path_sigs: Vec<PsbtTaprootScriptPathSig>,
+    /// Taproot leaf scripts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taproot_scripts: Vec<PsbtTaprootScript>,
+    /// BIP32 origins of the taproot keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taproot_bip32_derivs: Vec<PsbtTaprootBip32Deriv>,
+    /// Taproot internal key, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taproot_internal_key: Option<String>,
+    /// Taproot merkle root, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taproot_merkle_root: Option<String>,
+    /// Unknown records, hex key to hex value
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unknown: BTreeMap<String, String>,
+    /// Proprietary records
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proprietary: Vec<PsbtProprietary>,
+}
+
+impl DecodedPsbtInput {
+    /// Returns whether the final script fields are set
+    pub fn is_finalized(&self) -> bool {
+        self.final_script_sig.is_some() || self.final_script_witness.is_some()
+    }
+}
+
+/// Per-output data of a decoded PSBT
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedPsbtOutput {
+    /// Redeem script, for P2SH outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redeem_script: Option<DecodedScript>,
+    /// Witness script, for P2WSH outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_script: Option<DecodedScript>,
+    /// BIP32 origins of the public keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bip32_derivs: Vec<PsbtBip32Deriv>,
+    /// Taproot internal key, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taproot_internal_key: Option<String>,
+    /// Leaves of the taproot tree, in depth-first order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taproot_tree: Vec<PsbtTaprootLeaf>,
+    /// BIP32 origins of the taproot keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taproot_bip32_derivs: Vec<PsbtTaprootBip32Deriv>,
+    /// Unknown records, hex key to hex value
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unknown: BTreeMap<String, String>,
+    /// Proprietary records
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proprietary: Vec<PsbtProprietary>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    const PUBKEY: &str = "03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd";
+
+    #[test]
+    fn test_decoded_psbt() {
+        let value = json!({
+            "tx": {
+                "txid": TXID,
+                "hash": TXID,
+                "size": 82,
+                "vsize": 82,
+                "weight": 328,
+                "version": 2,
+                "locktime": 0,
+                "vin": [{
+                    "txid": TXID,
+                    "vout": 0,
+                    "scriptSig": { "asm": "", "hex": "" },
+                    "sequence": 4294967293u32,
+                }],
+                "vout": [{
+                    "value": 0.4999,
+                    "n": 0,
+                    "scriptPubKey": {
+                        "asm": "0 e8df018c7e326cc253faac7e46cdc51e68542c42",
+                        "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)#00000000",
+                        "hex": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                        "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                        "type": "witness_v0_keyhash",
+                    },
+                }],
+            },
+            "psbt_version": 0,
+            "unknown": { "0f00": "01" },
+            "inputs": [{
+                "witness_utxo": {
+                    "amount": 0.5,
+                    "scriptPubKey": {
+                        "asm": "0 e8df018c7e326cc253faac7e46cdc51e68542c42",
+                        "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)#00000000",
+                        "hex": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                        "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                        "type": "witness_v0_keyhash",
+                    },
+                },
+                "partial_signatures": { PUBKEY: "3044" },
+                "sighash": "ALL",
+                "bip32_derivs": [{
+                    "pubkey": PUBKEY,
+                    "master_fingerprint": "d34db33f",
+                    "path": "84'/0'/0'/0/1",
+                }],
+            }],
+            "outputs": [{
+                "taproot_tree": [{ "depth": 1, "leaf_ver": 192, "script": "51" }],
+            }],
+            "fee": 0.0001,
+        });
+        let psbt: DecodedPsbt = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(psbt.fee, Some(Amount::from_sat(10_000)));
+        assert!(!psbt.is_finalized());
+        let input = &psbt.inputs[0];
+        assert_eq!(input.witness_utxo.as_ref().unwrap().amount, Amount::from_sat(50_000_000));
+        assert_eq!(input.partial_signatures[PUBKEY], "3044");
+        assert_eq!(input.bip32_derivs[0].path, "m/84'/0'/0'/0/1".parse().unwrap());
+        assert_eq!(psbt.outputs[0].taproot_tree[0].leaf_ver, 0xc0);
+        assert_eq!(serde_json::to_value(&psbt).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decoded_psbt_input_finalized() {
+        let input: DecodedPsbtInput =
+            serde_json::from_value(json!({ "final_scriptwitness": ["3044", PUBKEY] })).unwrap();
+        assert!(input.is_finalized());
+        assert!(!DecodedPsbtInput::default().is_finalized());
+    }
+}
@@ -0,0 +1,216 @@
+//! Decoded transaction result types
+//!
+//! This module provides `DecodedTransaction`, returned by
+//! `decoderawtransaction` and the verbose form of `getrawtransaction`, and
+//! its inputs, outputs and scripts, which other results such as
+//! `gettransaction` and `getblock` embed.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::{Address, Amount, BlockHash, ScriptBuf, Txid, Wtxid};
+use serde::{Deserialize, Serialize};
+
+use crate::raw_tx::RawTxHex;
+
+/// Decoded transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedTransaction {
+    /// Transaction id
+    pub txid: Txid,
+    /// Witness transaction id
+    pub hash: Wtxid,
+    /// Serialized size
+    pub size: u64,
+    /// Virtual size
+    pub vsize: u64,
+    /// Weight
+    pub weight: u64,
+    /// Version
+    pub version: i32,
+    /// Lock time
+    pub locktime: u32,
+    /// Inputs
+    pub vin: Vec<DecodedVin>,
+    /// Outputs
+    pub vout: Vec<DecodedVout>,
+    /// Fee, from `getrawtransaction` with verbosity 2 when undo data is
+    /// available
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<Amount>,
+    /// Whether the block is in the active chain, from `getrawtransaction`
+    /// with a block hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_active_chain: Option<bool>,
+    /// The transaction, from `getrawtransaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<RawTxHex>,
+    /// Hash of the containing block, from `getrawtransaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Number of confirmations, from `getrawtransaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u32>,
+    /// Same as `blocktime`, from `getrawtransaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<u64>,
+    /// Timestamp of the containing block, from `getrawtransaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocktime: Option<u64>,
+}
+
+impl DecodedTransaction {
+    /// Returns whether the transaction is a coinbase
+    pub fn is_coinbase(&self) -> bool { self.vin.first().is_some_and(|vin| vin.coinbase.is_some()) }
+
+    /// Returns the sum of the output values, or `None` on overflow
+    pub fn total_output_value(&self) -> Option<Amount> {
+        self.vout.iter().try_fold(Amount::ZERO, |total, vout| total.checked_add(vout.value))
+    }
+}
+
+/// Input of a decoded transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedVin {
+    /// Coinbase data, as hex, for coinbase inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<String>,
+    /// Transaction id of the spent output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txid: Option<Txid>,
+    /// Index of the spent output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vout: Option<u32>,
+    /// Signature script
+    #[serde(rename = "scriptSig", default, skip_serializing_if = "Option::is_none")]
+    pub script_sig: Option<DecodedScriptSig>,
+    /// Witness stack items, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txinwitness: Option<Vec<String>>,
+    /// Spent output, from `getrawtransaction` with verbosity 2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prevout: Option<DecodedPrevout>,
+    /// Sequence number
+    pub sequence: u32,
+}
+
+/// Signature script of a decoded input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedScriptSig {
+    /// Disassembly
+    pub asm: String,
+    /// The script
+    pub hex: ScriptBuf,
+}
+
+/// Output spent by a decoded input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedPrevout {
+    /// Whether the output is from a coinbase
+    pub generated: bool,
+    /// Height of the block containing the output
+    pub height: u32,
+    /// Value of the output
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub value: Amount,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: DecodedScriptPubKey,
+}
+
+/// Output of a decoded transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedVout {
+    /// Value
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub value: Amount,
+    /// Output index
+    pub n: u32,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: DecodedScriptPubKey,
+}
+
+/// Decoded output script
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedScriptPubKey {
+    /// Disassembly
+    pub asm: String,
+    /// Inferred descriptor
+    pub desc: String,
+    /// The script
+    pub hex: ScriptBuf,
+    /// Address, for scripts that have one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address<NetworkUnchecked>>,
+    /// Script type, e.g. `witness_v0_keyhash` or `nulldata`
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_decoded_transaction() {
+        let value = json!({
+            "txid": TXID,
+            "hash": TXID,
+            "size": 204,
+            "vsize": 204,
+            "weight": 816,
+            "version": 1,
+            "locktime": 0,
+            "vin": [{ "coinbase": "04ffff001d0104", "sequence": 4294967295u32 }],
+            "vout": [{
+                "value": 50.0,
+                "n": 0,
+                "scriptPubKey": {
+                    "asm": "04678afdb0 OP_CHECKSIG",
+                    "desc": "pk(04678afdb0)#00000000",
+                    "hex": "4104678afdb0ac",
+                    "type": "pubkey",
+                },
+            }],
+        });
+        let tx: DecodedTransaction = serde_json::from_value(value.clone()).unwrap();
+        assert!(tx.is_coinbase());
+        assert_eq!(tx.total_output_value(), Some(Amount::from_sat(5_000_000_000)));
+        assert_eq!(tx.vout[0].script_pubkey.address, None);
+        assert_eq!(serde_json::to_value(&tx).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decoded_vin_with_prevout() {
+        let value = json!({
+            "txid": TXID,
+            "vout": 1,
+            "scriptSig": { "asm": "", "hex": "" },
+            "txinwitness": ["3044", "02ab"],
+            "prevout": {
+                "generated": false,
+                "height": 839_000,
+                "value": 0.5,
+                "scriptPubKey": {
+                    "asm": "0 e8df018c7e326cc253faac7e46cdc51e68542c42",
+                    "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)#00000000",
+                    "hex": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                    "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                    "type": "witness_v0_keyhash",
+                },
+            },
+            "sequence": 4294967293u32,
+        });
+        let vin: DecodedVin = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(vin.prevout.as_ref().unwrap().value, Amount::from_sat(50_000_000));
+        assert!(vin.script_sig.as_ref().unwrap().hex.is_empty());
+        assert_eq!(serde_json::to_value(&vin).unwrap(), value);
+    }
+}
@@ -0,0 +1,168 @@
+//! Multi-version API definition container
+//!
+//! This module provides `ApiDefinitionSet`, which holds one `ApiDefinition`
+//! per Bitcoin Core version and answers version-aware queries across them.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ApiDefinition, BtcMethod};
+use crate::version::CoreVersion;
+
+/// API definitions keyed by Bitcoin Core version
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiDefinitionSet {
+    versions: BTreeMap<CoreVersion, ApiDefinition>,
+}
+
+impl ApiDefinitionSet {
+    /// Creates a new empty set
+    pub fn new() -> Self { Self { versions: BTreeMap::new() } }
+
+    /// Adds the definition for a version, returning the one it replaced
+    pub fn insert(
+        &mut self,
+        version: CoreVersion,
+        api_def: ApiDefinition,
+    ) -> Option<ApiDefinition> {
+        self.versions.insert(version, api_def)
+    }
+
+    /// Removes the definition for a version
+    pub fn remove(&mut self, version: CoreVersion) -> Option<ApiDefinition> {
+        self.versions.remove(&version)
+    }
+
+    /// Gets the definition for an exact version
+    pub fn get(&self, version: CoreVersion) -> Option<&ApiDefinition> {
+        self.versions.get(&version)
+    }
+
+    /// Returns the contained versions in ascending order
+    pub fn versions(&self) -> impl Iterator<Item = CoreVersion> + '_ {
+        self.versions.keys().copied()
+    }
+
+    /// Returns the `(version, definition)` pairs in ascending version order
+    pub fn iter(&self) -> impl Iterator<Item = (CoreVersion, &ApiDefinition)> {
+        self.versions.iter().map(|(version, api_def)| (*version, api_def))
+    }
+
+    /// Returns the number of versions
+    pub fn len(&self) -> usize { self.versions.len() }
+
+    /// Returns whether the set contains no versions
+    pub fn is_empty(&self) -> bool { self.versions.is_empty() }
+
+    /// Returns the newest version and its definition
+    pub fn latest(&self) -> Option<(CoreVersion, &ApiDefinition)> {
+        self.versions.iter().next_back().map(|(version, api_def)| (*version, api_def))
+    }
+
+    /// Returns the definition that best describes `version`
+    ///
+    /// This is the newest definition not newer than `version`; if `version`
+    /// predates every definition, the oldest definition is returned instead.
+    pub fn nearest(&self, version: CoreVersion) -> Option<(CoreVersion, &ApiDefinition)> {
+        self.versions
+            .range(..=version)
+            .next_back()
+            .or_else(|| self.versions.iter().next())
+            .map(|(version, api_def)| (*version, api_def))
+    }
+
+    /// Returns the versions whose definition contains the method, in ascending order
+    pub fn versions_supporting(&self, method: &str) -> Vec<CoreVersion> {
+        self.versions
+            .iter()
+            .filter(|(_, api_def)| api_def.contains_method(method))
+            .map(|(version, _)| *version)
+            .collect()
+    }
+
+    /// Returns each version's definition of the method, in ascending version order
+    pub fn method_history<'a>(
+        &'a self,
+        method: &'a str,
+    ) -> impl Iterator<Item = (CoreVersion, &'a BtcMethod)> + 'a {
+        self.versions
+            .iter()
+            .filter_map(move |(version, api_def)| Some((*version, api_def.get_method(method)?)))
+    }
+}
+
+impl FromIterator<(CoreVersion, ApiDefinition)> for ApiDefinitionSet {
+    fn from_iter<I: IntoIterator<Item = (CoreVersion, ApiDefinition)>>(iter: I) -> Self {
+        Self { versions: iter.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(methods: &[&str]) -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        for name in methods {
+            api_def
+                .insert_method(BtcMethod { name: name.to_string(), ..BtcMethod::default() })
+                .unwrap();
+        }
+        api_def
+    }
+
+    fn set() -> ApiDefinitionSet {
+        [
+            (CoreVersion::V25, api(&["getblock", "getblockcount"])),
+            (CoreVersion::V27, api(&["getblock", "getblockcount", "getprioritisedtransactions"])),
+            (CoreVersion::V29, api(&["getblock", "getdescriptoractivity"])),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_versions_supporting() {
+        let set = set();
+        assert_eq!(
+            set.versions_supporting("getblock"),
+            vec![CoreVersion::V25, CoreVersion::V27, CoreVersion::V29]
+        );
+        assert_eq!(
+            set.versions_supporting("getblockcount"),
+            vec![CoreVersion::V25, CoreVersion::V27]
+        );
+        assert!(set.versions_supporting("getinfo").is_empty());
+        assert_eq!(set.method_history("getdescriptoractivity").count(), 1);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let set = set();
+        assert_eq!(set.nearest(CoreVersion::V27).unwrap().0, CoreVersion::V27);
+        assert_eq!(set.nearest(CoreVersion::V28).unwrap().0, CoreVersion::V27);
+        assert_eq!(set.nearest(CoreVersion::new(30, 0)).unwrap().0, CoreVersion::V29);
+        assert_eq!(set.nearest(CoreVersion::new(24, 0)).unwrap().0, CoreVersion::V25);
+        assert!(ApiDefinitionSet::new().nearest(CoreVersion::V28).is_none());
+        assert_eq!(set.latest().unwrap().0, CoreVersion::V29);
+    }
+
+    #[test]
+    fn test_insert_remove_and_serde() {
+        let mut set = set();
+        assert_eq!(set.len(), 3);
+        assert!(set.insert(CoreVersion::V26, api(&["getblock"])).is_none());
+        assert!(set.remove(CoreVersion::V25).is_some());
+        assert_eq!(
+            set.versions().collect::<Vec<_>>(),
+            vec![CoreVersion::V26, CoreVersion::V27, CoreVersion::V29]
+        );
+
+        let json = serde_json::to_value(&set).unwrap();
+        assert!(json.get("26.0").is_some());
+        let round_trip: ApiDefinitionSet = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, set);
+    }
+}
@@ -0,0 +1,182 @@
+//! Soft fork deployment types
+//!
+//! This module provides `GetDeploymentInfoResult`, returned by
+//! `getdeploymentinfo`, and `Deployment`, the per-deployment object also
+//! used by the `softforks` field of `getblockchaininfo` before Bitcoin Core 23.
+
+use std::collections::BTreeMap;
+
+use bitcoin::BlockHash;
+use serde::{Deserialize, Serialize};
+
+/// Kind of a soft fork deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentType {
+    /// Activated at a height fixed in the code
+    Buried,
+    /// Activated by BIP9 version bits signalling
+    Bip9,
+}
+
+/// State of a BIP9 deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bip9Status {
+    /// Before the start time
+    Defined,
+    /// Signalling period in progress
+    Started,
+    /// Threshold reached; active after the next period
+    LockedIn,
+    /// Rules enforced
+    Active,
+    /// Timed out without locking in
+    Failed,
+}
+
+/// Soft fork deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployment {
+    /// Kind of deployment
+    #[serde(rename = "type")]
+    pub type_: DeploymentType,
+    /// Activation height, for buried and active BIP9 deployments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Whether the rules are enforced for the next block
+    pub active: bool,
+    /// BIP9 state, for BIP9 deployments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bip9: Option<Bip9Info>,
+}
+
+/// BIP9 state of a deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bip9Info {
+    /// Version bit used for signalling, while started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bit: Option<u8>,
+    /// Median time past at which signalling starts; -1 means always active
+    pub start_time: i64,
+    /// Median time past at which the deployment fails if not locked in
+    pub timeout: i64,
+    /// Minimum height of activation
+    pub min_activation_height: u32,
+    /// State for the current block
+    pub status: Bip9Status,
+    /// Height of the first block the state applies to
+    pub since: u32,
+    /// State for the next block
+    pub status_next: Bip9Status,
+    /// Signalling statistics, while started or locked in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<Bip9Statistics>,
+    /// Signalling of the blocks in the current period, `#` for signalling
+    /// and `-` otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signalling: Option<String>,
+}
+
+/// Signalling statistics of the current BIP9 period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bip9Statistics {
+    /// Length of a signalling period in blocks
+    pub period: u32,
+    /// Signalling blocks needed to lock in, while started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    /// Blocks elapsed in the period
+    pub elapsed: u32,
+    /// Signalling blocks in the period
+    pub count: u32,
+    /// Whether the threshold can still be reached, while started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub possible: Option<bool>,
+}
+
+/// Result of `getdeploymentinfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetDeploymentInfoResult {
+    /// Hash of the block the state is reported for
+    pub hash: BlockHash,
+    /// Height of that block
+    pub height: u32,
+    /// Script verification flags enforced for the block (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub script_flags: Vec<String>,
+    /// Deployments by name
+    pub deployments: BTreeMap<String, Deployment>,
+}
+
+impl GetDeploymentInfoResult {
+    /// Returns whether a deployment is active for the next block
+    pub fn is_active(&self, name: &str) -> bool {
+        self.deployments.get(name).is_some_and(|deployment| deployment.active)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    #[test]
+    fn test_get_deployment_info() {
+        let value = json!({
+            "hash": HASH,
+            "height": 150,
+            "deployments": {
+                "segwit": { "type": "buried", "active": true, "height": 0 },
+                "testdummy": {
+                    "type": "bip9",
+                    "active": false,
+                    "bip9": {
+                        "bit": 28,
+                        "start_time": 0,
+                        "timeout": 9223372036854775807i64,
+                        "min_activation_height": 0,
+                        "status": "started",
+                        "since": 144,
+                        "status_next": "started",
+                        "statistics": {
+                            "period": 144,
+                            "threshold": 108,
+                            "elapsed": 7,
+                            "count": 7,
+                            "possible": true,
+                        },
+                        "signalling": "#######",
+                    },
+                },
+                "taproot": {
+                    "type": "bip9",
+                    "height": 0,
+                    "active": true,
+                    "bip9": {
+                        "start_time": -1,
+                        "timeout": 9223372036854775807i64,
+                        "min_activation_height": 0,
+                        "status": "active",
+                        "since": 0,
+                        "status_next": "active",
+                    },
+                },
+            },
+        });
+        let result: GetDeploymentInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(result.is_active("taproot"));
+        assert!(!result.is_active("testdummy"));
+        assert!(!result.is_active("unknown"));
+        let bip9 = result.deployments["testdummy"].bip9.as_ref().unwrap();
+        assert_eq!(bip9.status, Bip9Status::Started);
+        assert_eq!(bip9.statistics.unwrap().threshold, Some(108));
+        assert_eq!(result.deployments["segwit"].type_, DeploymentType::Buried);
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+        assert_eq!(serde_json::to_value(Bip9Status::LockedIn).unwrap(), json!("locked_in"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Output descriptor result types and checksums
+//!
+//! This module provides `GetDescriptorInfoResult`, returned by
+//! `getdescriptorinfo`, and the descriptor checksum of BIP380, so a
+//! descriptor can be checked or given a checksum without a node.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Characters a descriptor may contain, grouped so that the index of a
+/// character within its group of 32 fits in 5 bits
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Characters of a checksum, as in bech32
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Length of a checksum
+const CHECKSUM_LEN: usize = 8;
+
+/// Result of `getdescriptorinfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetDescriptorInfoResult {
+    /// Canonical form of the descriptor, with its checksum and without
+    /// private keys
+    pub descriptor: String,
+    /// Checksum of the descriptor as given
+    pub checksum: String,
+    /// Whether the descriptor is ranged
+    pub isrange: bool,
+    /// Whether the descriptor is solvable
+    pub issolvable: bool,
+    /// Whether the given descriptor has private keys
+    pub hasprivatekeys: bool,
+}
+
+impl GetDescriptorInfoResult {
+    /// Checks that `checksum` is that of `input`, the descriptor as given to
+    /// `getdescriptorinfo`
+    pub fn check_checksum(&self, input: &str) -> std::result::Result<(), DescriptorChecksumError> {
+        let (desc, _) = split_checksum(input);
+        let expected = descriptor_checksum(desc)?;
+        match expected == self.checksum {
+            true => Ok(()),
+            false =>
+                Err(DescriptorChecksumError::Mismatch { expected, found: self.checksum.clone() }),
+        }
+    }
+}
+
+/// Error returned when computing or verifying a descriptor checksum
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorChecksumError {
+    /// A character outside the descriptor character set
+    #[error("Invalid character in descriptor: {0:?}")]
+    InvalidCharacter(char),
+    /// No `#` followed by a checksum
+    #[error("Missing checksum")]
+    Missing,
+    /// A checksum that is not 8 characters long
+    #[error("Expected 8 character checksum, not {0} characters")]
+    InvalidLength(usize),
+    /// A checksum that does not match the descriptor
+    #[error("Provided checksum '{found}' does not match computed checksum '{expected}'")]
+    Mismatch {
+        /// Checksum of the descriptor
+        expected: String,
+        /// Checksum given
+        found: String,
+    },
+}
+
+/// Returns the checksum of a descriptor given without one
+pub fn descriptor_checksum(desc: &str) -> std::result::Result<String, DescriptorChecksumError> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+    for ch in desc.chars() {
+        let position =
+            INPUT_CHARSET.find(ch).ok_or(DescriptorChecksumError::InvalidCharacter(ch))? as u64;
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..CHECKSUM_LEN {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    Ok((0..CHECKSUM_LEN)
+        .map(|i| CHECKSUM_CHARSET[((c >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Returns the descriptor with its checksum appended, replacing any checksum
+/// it has
+pub fn add_descriptor_checksum(desc: &str) -> std::result::Result<String, DescriptorChecksumError> {
+    let (desc, _) = split_checksum(desc);
+    Ok(format!("{}#{}", desc, descriptor_checksum(desc)?))
+}
+
+/// Checks the checksum of a descriptor given as `desc#checksum`
+pub fn verify_descriptor_checksum(desc: &str) -> std::result::Result<(), DescriptorChecksumError> {
+    let (desc, found) = split_checksum(desc);
+    let found = found.ok_or(DescriptorChecksumError::Missing)?;
+    if found.chars().count() != CHECKSUM_LEN {
+        return Err(DescriptorChecksumError::InvalidLength(found.chars().count()));
+    }
+    let expected = descriptor_checksum(desc)?;
+    match expected == found {
+        true => Ok(()),
+        false => Err(DescriptorChecksumError::Mismatch { expected, found: found.to_string() }),
+    }
+}
+
+/// Splits a descriptor at the last `#` into the descriptor and its checksum
+fn split_checksum(desc: &str) -> (&str, Option<&str>) {
+    match desc.rsplit_once('#') {
+        Some((desc, checksum)) => (desc, Some(checksum)),
+        None => (desc, None),
+    }
+}
+
+/// Steps the BCH code over a GF(32) value
+fn polymod(c: u64, value: u64) -> u64 {
+    const GENERATORS: [u64; 5] =
+        [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+    let top = c >> 35;
+    let mut c = ((c & 0x7_ffff_ffff) << 5) ^ value;
+    for (i, generator) in GENERATORS.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // From the BIP380 test vectors
+    const DESC: &str = "raw(deadbeef)";
+    const CHECKSUM: &str = "89f8spxm";
+
+    #[test]
+    fn test_descriptor_checksum() {
+        assert_eq!(descriptor_checksum(DESC).unwrap(), CHECKSUM);
+        let with_checksum = format!("{}#{}", DESC, CHECKSUM);
+        assert_eq!(add_descriptor_checksum(DESC).unwrap(), with_checksum);
+        assert_eq!(add_descriptor_checksum(&with_checksum).unwrap(), with_checksum);
+        assert_eq!(verify_descriptor_checksum(&with_checksum), Ok(()));
+
+        assert_eq!(verify_descriptor_checksum(DESC), Err(DescriptorChecksumError::Missing));
+        assert_eq!(
+            verify_descriptor_checksum("raw(deadbeef)#89f8spx"),
+            Err(DescriptorChecksumError::InvalidLength(7))
+        );
+        assert_eq!(
+            verify_descriptor_checksum("raw(deedbeef)#89f8spxm"),
+            Err(DescriptorChecksumError::Mismatch {
+                expected: descriptor_checksum("raw(deedbeef)").unwrap(),
+                found: CHECKSUM.to_string(),
+            })
+        );
+        assert_eq!(
+            descriptor_checksum("raw(dé)"),
+            Err(DescriptorChecksumError::InvalidCharacter('é'))
+        );
+    }
+
+    #[test]
+    fn test_get_descriptor_info() {
+        let value = json!({
+            "descriptor": format!("{}#{}", DESC, CHECKSUM),
+            "checksum": CHECKSUM,
+            "isrange": false,
+            "issolvable": false,
+            "hasprivatekeys": false,
+        });
+        let info: GetDescriptorInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(verify_descriptor_checksum(&info.descriptor), Ok(()));
+        assert_eq!(info.check_checksum(DESC), Ok(()));
+        assert!(info.check_checksum("raw(00)").is_err());
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+    }
+}
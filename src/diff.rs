@@ -0,0 +1,391 @@
+//! Cross-version schema diffing
+//!
+//! This module compares two `ApiDefinition`s and reports which methods were
+//! added, removed, or modified, together with per-method argument and result
+//! changes. It is intended for tooling that needs to know exactly what changed
+//! between two Bitcoin Core releases.
+
+use std::collections::BTreeMap;
+
+use crate::types::{ApiDefinition, BtcArgument, BtcMethod, BtcResult};
+
+/// Differences between two API definitions
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ApiDiff {
+    /// Names of methods present only in the newer definition
+    pub added: Vec<String>,
+    /// Names of methods present only in the older definition
+    pub removed: Vec<String>,
+    /// Methods present in both definitions whose shape changed, keyed by name
+    pub modified: BTreeMap<String, MethodDiff>,
+}
+
+impl ApiDiff {
+    /// Returns true if the two definitions are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Differences between two versions of the same method
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MethodDiff {
+    /// Whether the method description changed
+    pub description_changed: bool,
+    /// Whether the positional order of the arguments changed
+    pub argument_order_changed: bool,
+    /// Argument changes, in the order of the newer definition
+    pub arguments: Vec<ArgumentChange>,
+    /// Result changes, ordered by result path
+    pub results: Vec<ResultChange>,
+}
+
+impl MethodDiff {
+    /// Returns true if the method is unchanged
+    pub fn is_empty(&self) -> bool {
+        !self.description_changed
+            && !self.argument_order_changed
+            && self.arguments.is_empty()
+            && self.results.is_empty()
+    }
+}
+
+/// A change to a single method argument
+///
+/// Arguments are matched by their primary (first) name.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)] // diff reports are small and short-lived
+pub enum ArgumentChange {
+    /// Argument only present in the newer definition
+    Added(BtcArgument),
+    /// Argument only present in the older definition
+    Removed(BtcArgument),
+    /// Argument present in both definitions with a different type or requirement
+    Modified {
+        /// Argument definition in the older schema
+        old: BtcArgument,
+        /// Argument definition in the newer schema
+        new: BtcArgument,
+    },
+}
+
+/// A change to a single node of a method's result tree
+///
+/// Result nodes are addressed by a `/`-separated path. Top-level results are
+/// identified by their index, nested results by their key name (or by their
+/// index for unnamed array elements).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultChange {
+    /// Result node only present in the newer definition
+    Added {
+        /// Path to the result node
+        path: String,
+        /// Result node in the newer schema
+        result: BtcResult,
+    },
+    /// Result node only present in the older definition
+    Removed {
+        /// Path to the result node
+        path: String,
+        /// Result node in the older schema
+        result: BtcResult,
+    },
+    /// Result node present in both definitions with a different type, optionality or condition
+    Modified {
+        /// Path to the result node
+        path: String,
+        /// Result node in the older schema
+        old: BtcResult,
+        /// Result node in the newer schema
+        new: BtcResult,
+    },
+}
+
+impl ApiDefinition {
+    /// Compares this definition (the older one) with `other` (the newer one)
+    pub fn diff(&self, other: &ApiDefinition) -> ApiDiff {
+        let mut diff = ApiDiff::default();
+
+        for (name, old) in &self.rpcs {
+            match other.rpcs.get(name) {
+                Some(new) => {
+                    let method_diff = old.diff(new);
+                    if !method_diff.is_empty() {
+                        diff.modified.insert(name.clone(), method_diff);
+                    }
+                }
+                None => diff.removed.push(name.clone()),
+            }
+        }
+        diff.added =
+            other.rpcs.keys().filter(|name| !self.rpcs.contains_key(*name)).cloned().collect();
+
+        diff
+    }
+}
+
+impl BtcMethod {
+    /// Compares this method (the older one) with `other` (the newer one)
+    pub fn diff(&self, other: &BtcMethod) -> MethodDiff {
+        let old_args: BTreeMap<&str, &BtcArgument> =
+            self.arguments.iter().map(|arg| (primary_name(arg), arg)).collect();
+        let new_args: BTreeMap<&str, &BtcArgument> =
+            other.arguments.iter().map(|arg| (primary_name(arg), arg)).collect();
+
+        let mut arguments = Vec::new();
+        for arg in &other.arguments {
+            match old_args.get(primary_name(arg)) {
+                Some(old) if !same_argument_shape(old, arg) => arguments
+                    .push(ArgumentChange::Modified { old: (*old).clone(), new: arg.clone() }),
+                Some(_) => {}
+                None => arguments.push(ArgumentChange::Added(arg.clone())),
+            }
+        }
+        for arg in &self.arguments {
+            if !new_args.contains_key(primary_name(arg)) {
+                arguments.push(ArgumentChange::Removed(arg.clone()));
+            }
+        }
+
+        let old_order: Vec<&str> = self
+            .arguments
+            .iter()
+            .map(primary_name)
+            .filter(|name| new_args.contains_key(name))
+            .collect();
+        let new_order: Vec<&str> = other
+            .arguments
+            .iter()
+            .map(primary_name)
+            .filter(|name| old_args.contains_key(name))
+            .collect();
+
+        let old_results = flatten_results(&self.results);
+        let new_results = flatten_results(&other.results);
+        let mut results = Vec::new();
+        for (path, new) in &new_results {
+            match old_results.get(path) {
+                Some(old) if !same_result_shape(old, new) => results.push(ResultChange::Modified {
+                    path: path.clone(),
+                    old: (*old).clone(),
+                    new: (*new).clone(),
+                }),
+                Some(_) => {}
+                None =>
+                    results.push(ResultChange::Added { path: path.clone(), result: (*new).clone() }),
+            }
+        }
+        for (path, old) in &old_results {
+            if !new_results.contains_key(path) {
+                results.push(ResultChange::Removed { path: path.clone(), result: (*old).clone() });
+            }
+        }
+        results.sort_by(|a, b| a.path().cmp(b.path()));
+
+        MethodDiff {
+            description_changed: self.description != other.description,
+            argument_order_changed: old_order != new_order,
+            arguments,
+            results,
+        }
+    }
+}
+
+impl ResultChange {
+    /// Returns the path of the changed result node
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Modified { path, .. } =>
+                path,
+        }
+    }
+}
+
+fn primary_name(arg: &BtcArgument) -> &str { arg.names.first().map(String::as_str).unwrap_or("") }
+
+fn same_argument_shape(old: &BtcArgument, new: &BtcArgument) -> bool {
+    old.names == new.names
+        && old.type_ == new.type_
+        && old.type_str == new.type_str
+        && old.required == new.required
+        && old.also_positional == new.also_positional
+        && old.hidden == new.hidden
+        && old.default == new.default
+        && old.minimum == new.minimum
+        && old.maximum == new.maximum
+        && old.allowed_values == new.allowed_values
+        && old.inner == new.inner
+}
+
+fn same_result_shape(old: &BtcResult, new: &BtcResult) -> bool {
+    old.type_ == new.type_
+        && old.optional == new.optional
+        && old.skip_type_check == new.skip_type_check
+        && old.condition == new.condition
+        && old.dynamic_key == new.dynamic_key
+}
+
+fn flatten_results(results: &[BtcResult]) -> BTreeMap<String, &BtcResult> {
+    fn walk<'a>(prefix: &str, inner: &'a [BtcResult], out: &mut BTreeMap<String, &'a BtcResult>) {
+        for (index, result) in inner.iter().enumerate() {
+            let segment = if result.key_name.is_empty() {
+                index.to_string()
+            } else {
+                result.key_name.clone()
+            };
+            let path = format!("{prefix}/{segment}");
+            walk(&path, &result.inner, out);
+            out.insert(path, result);
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    for (index, result) in results.iter().enumerate() {
+        let path = index.to_string();
+        walk(&path, &result.inner, &mut out);
+        out.insert(path, result);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(name: &str, type_: &str, required: bool) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            required,
+            type_: type_.to_string(),
+            ..BtcArgument::default()
+        }
+    }
+
+    fn result(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            type_: type_.to_string(),
+            key_name: key_name.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    fn method(name: &str, arguments: Vec<BtcArgument>, results: Vec<BtcResult>) -> BtcMethod {
+        BtcMethod {
+            name: name.to_string(),
+            argument_names: arguments.iter().map(|arg| arg.names[0].clone()).collect(),
+            arguments,
+            results,
+            ..BtcMethod::default()
+        }
+    }
+
+    fn api(methods: Vec<BtcMethod>) -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        for method in methods {
+            api_def.rpcs.insert(method.name.clone(), method);
+        }
+        api_def
+    }
+
+    #[test]
+    fn test_diff_identical_definitions_is_empty() {
+        let old = api(vec![method("getblockcount", vec![], vec![result("", "number", vec![])])]);
+        assert!(old.diff(&old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_added_and_removed_methods() {
+        let old =
+            api(vec![method("getblockcount", vec![], vec![]), method("getinfo", vec![], vec![])]);
+        let new = api(vec![
+            method("getblockcount", vec![], vec![]),
+            method("getdeploymentinfo", vec![], vec![]),
+        ]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["getdeploymentinfo".to_string()]);
+        assert_eq!(diff.removed, vec!["getinfo".to_string()]);
+        assert!(diff.modified.is_empty());
+    }
+
+    #[test]
+    fn test_diff_argument_changes() {
+        let old = api(vec![method(
+            "getblock",
+            vec![argument("blockhash", "string", true), argument("verbose", "boolean", false)],
+            vec![],
+        )]);
+        let new = api(vec![method(
+            "getblock",
+            vec![argument("blockhash", "hex", true), argument("verbosity", "number", false)],
+            vec![],
+        )]);
+
+        let diff = old.diff(&new);
+        let method_diff = &diff.modified["getblock"];
+        assert_eq!(method_diff.arguments.len(), 3);
+        assert!(matches!(
+            &method_diff.arguments[0],
+            ArgumentChange::Modified { old, new } if old.type_ == "string" && new.type_ == "hex"
+        ));
+        assert!(
+            matches!(&method_diff.arguments[1], ArgumentChange::Added(arg) if arg.names[0] == "verbosity")
+        );
+        assert!(
+            matches!(&method_diff.arguments[2], ArgumentChange::Removed(arg) if arg.names[0] == "verbose")
+        );
+        assert!(!method_diff.argument_order_changed);
+    }
+
+    #[test]
+    fn test_diff_argument_order_changed() {
+        let old = api(vec![method(
+            "send",
+            vec![argument("a", "string", true), argument("b", "string", true)],
+            vec![],
+        )]);
+        let new = api(vec![method(
+            "send",
+            vec![argument("b", "string", true), argument("a", "string", true)],
+            vec![],
+        )]);
+
+        let method_diff = &old.diff(&new).modified["send"];
+        assert!(method_diff.argument_order_changed);
+        assert!(method_diff.arguments.is_empty());
+    }
+
+    #[test]
+    fn test_diff_nested_result_changes() {
+        let old = api(vec![method(
+            "getblockchaininfo",
+            vec![],
+            vec![result(
+                "",
+                "object",
+                vec![result("chain", "string", vec![]), result("softforks", "object", vec![])],
+            )],
+        )]);
+        let new = api(vec![method(
+            "getblockchaininfo",
+            vec![],
+            vec![result(
+                "",
+                "object",
+                vec![result("chain", "string", vec![]), result("warnings", "array", vec![])],
+            )],
+        )]);
+
+        let method_diff = &old.diff(&new).modified["getblockchaininfo"];
+        assert_eq!(method_diff.results.len(), 2);
+        assert!(matches!(
+            &method_diff.results[0],
+            ResultChange::Removed { path, .. } if path == "0/softforks"
+        ));
+        assert!(matches!(
+            &method_diff.results[1],
+            ResultChange::Added { path, .. } if path == "0/warnings"
+        ));
+    }
+}
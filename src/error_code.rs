@@ -0,0 +1,225 @@
+//! Bitcoin Core RPC error codes
+//!
+//! `RpcErrorCode` names the error codes of Bitcoin Core's `protocol.h`, so
+//! clients can match on `RpcErrorCode::InvalidParameter` rather than `-8`.
+//! Codes not in the list are kept in `RpcErrorCode::Unknown`.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::jsonrpc::RpcError;
+
+/// Which side an RPC error is attributed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorCategory {
+    /// The request was malformed, invalid or rejected
+    Client,
+    /// The node could not serve the request, or failed while serving it
+    Server,
+    /// A wallet could not be used or the wallet operation failed
+    Wallet,
+}
+
+macro_rules! error_codes {
+    ($($category:ident => { $($variant:ident = $code:literal => $name:literal,)* })*) => {
+        /// Error code of a Bitcoin Core RPC error
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum RpcErrorCode {
+            $($(
+                #[doc = concat!("`", $name, "` (", $code, ")")]
+                $variant,
+            )*)*
+            /// A code not known to this crate
+            Unknown(i32),
+        }
+
+        impl RpcErrorCode {
+            /// All known error codes
+            pub const ALL: &'static [RpcErrorCode] = &[$($(RpcErrorCode::$variant,)*)*];
+
+            /// Returns the error code for a numeric code
+            pub fn from_i32(code: i32) -> Self {
+                match code {
+                    $($($code => Self::$variant,)*)*
+                    code => Self::Unknown(code),
+                }
+            }
+
+            /// Returns the numeric code
+            pub fn code(&self) -> i32 {
+                match self {
+                    $($(Self::$variant => $code,)*)*
+                    Self::Unknown(code) => *code,
+                }
+            }
+
+            /// Returns the constant's name in Bitcoin Core, e.g. `RPC_INVALID_PARAMETER`
+            pub fn name(&self) -> Option<&'static str> {
+                match self {
+                    $($(Self::$variant => Some($name),)*)*
+                    Self::Unknown(_) => None,
+                }
+            }
+
+            /// Returns which side the error is attributed to, if known
+            pub fn category(&self) -> Option<ErrorCategory> {
+                match self {
+                    $($(Self::$variant => Some(ErrorCategory::$category),)*)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    Client => {
+        InvalidRequest = -32600 => "RPC_INVALID_REQUEST",
+        MethodNotFound = -32601 => "RPC_METHOD_NOT_FOUND",
+        InvalidParams = -32602 => "RPC_INVALID_PARAMS",
+        ParseError = -32700 => "RPC_PARSE_ERROR",
+        TypeError = -3 => "RPC_TYPE_ERROR",
+        InvalidAddressOrKey = -5 => "RPC_INVALID_ADDRESS_OR_KEY",
+        InvalidParameter = -8 => "RPC_INVALID_PARAMETER",
+        DeserializationError = -22 => "RPC_DESERIALIZATION_ERROR",
+        VerifyError = -25 => "RPC_VERIFY_ERROR",
+        VerifyRejected = -26 => "RPC_VERIFY_REJECTED",
+        VerifyAlreadyInChain = -27 => "RPC_VERIFY_ALREADY_IN_CHAIN",
+        MethodDeprecated = -32 => "RPC_METHOD_DEPRECATED",
+        ClientNodeAlreadyAdded = -23 => "RPC_CLIENT_NODE_ALREADY_ADDED",
+        ClientNodeNotAdded = -24 => "RPC_CLIENT_NODE_NOT_ADDED",
+        ClientNodeNotConnected = -29 => "RPC_CLIENT_NODE_NOT_CONNECTED",
+        ClientInvalidIpOrSubnet = -30 => "RPC_CLIENT_INVALID_IP_OR_SUBNET",
+    }
+    Server => {
+        InternalError = -32603 => "RPC_INTERNAL_ERROR",
+        MiscError = -1 => "RPC_MISC_ERROR",
+        OutOfMemory = -7 => "RPC_OUT_OF_MEMORY",
+        DatabaseError = -20 => "RPC_DATABASE_ERROR",
+        InWarmup = -28 => "RPC_IN_WARMUP",
+        ClientNotConnected = -9 => "RPC_CLIENT_NOT_CONNECTED",
+        ClientInInitialDownload = -10 => "RPC_CLIENT_IN_INITIAL_DOWNLOAD",
+        ClientP2pDisabled = -31 => "RPC_CLIENT_P2P_DISABLED",
+        ClientMempoolDisabled = -33 => "RPC_CLIENT_MEMPOOL_DISABLED",
+        ClientNodeCapacityReached = -34 => "RPC_CLIENT_NODE_CAPACITY_REACHED",
+    }
+    Wallet => {
+        WalletError = -4 => "RPC_WALLET_ERROR",
+        WalletInsufficientFunds = -6 => "RPC_WALLET_INSUFFICIENT_FUNDS",
+        WalletInvalidLabelName = -11 => "RPC_WALLET_INVALID_LABEL_NAME",
+        WalletKeypoolRanOut = -12 => "RPC_WALLET_KEYPOOL_RAN_OUT",
+        WalletUnlockNeeded = -13 => "RPC_WALLET_UNLOCK_NEEDED",
+        WalletPassphraseIncorrect = -14 => "RPC_WALLET_PASSPHRASE_INCORRECT",
+        WalletWrongEncState = -15 => "RPC_WALLET_WRONG_ENC_STATE",
+        WalletEncryptionFailed = -16 => "RPC_WALLET_ENCRYPTION_FAILED",
+        WalletAlreadyUnlocked = -17 => "RPC_WALLET_ALREADY_UNLOCKED",
+        WalletNotFound = -18 => "RPC_WALLET_NOT_FOUND",
+        WalletNotSpecified = -19 => "RPC_WALLET_NOT_SPECIFIED",
+        WalletAlreadyLoaded = -35 => "RPC_WALLET_ALREADY_LOADED",
+        WalletAlreadyExists = -36 => "RPC_WALLET_ALREADY_EXISTS",
+    }
+}
+
+impl RpcErrorCode {
+    /// Returns whether the code is one of the known codes
+    pub fn is_known(&self) -> bool { !matches!(self, Self::Unknown(_)) }
+
+    /// Returns whether repeating the same request later may succeed
+    ///
+    /// This holds while the node is starting up, syncing or without peers;
+    /// the request itself is not at fault.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::InWarmup
+                | Self::ClientInInitialDownload
+                | Self::ClientNotConnected
+                | Self::ClientNodeCapacityReached
+        )
+    }
+}
+
+impl fmt::Display for RpcErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{} ({})", name, self.code()),
+            None => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+impl From<i32> for RpcErrorCode {
+    fn from(code: i32) -> Self { Self::from_i32(code) }
+}
+
+impl From<RpcErrorCode> for i32 {
+    fn from(code: RpcErrorCode) -> Self { code.code() }
+}
+
+impl Serialize for RpcErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Self::from_i32(i32::deserialize(deserializer)?))
+    }
+}
+
+impl RpcError {
+    /// Returns the typed error code
+    pub fn error_code(&self) -> RpcErrorCode { RpcErrorCode::from_i32(self.code) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_error_code_round_trip() {
+        assert_eq!(RpcErrorCode::from_i32(-8), RpcErrorCode::InvalidParameter);
+        assert_eq!(RpcErrorCode::InvalidParameter.code(), -8);
+        assert_eq!(RpcErrorCode::InWarmup.name(), Some("RPC_IN_WARMUP"));
+        assert_eq!(RpcErrorCode::MethodNotFound.to_string(), "RPC_METHOD_NOT_FOUND (-32601)");
+        assert_eq!(RpcErrorCode::WalletNotFound.category(), Some(ErrorCategory::Wallet));
+        assert_eq!(RpcErrorCode::InvalidParams.category(), Some(ErrorCategory::Client));
+        assert_eq!(RpcErrorCode::DatabaseError.category(), Some(ErrorCategory::Server));
+
+        let unknown = RpcErrorCode::from(-99);
+        assert_eq!(unknown, RpcErrorCode::Unknown(-99));
+        assert!(!unknown.is_known());
+        assert_eq!(unknown.category(), None);
+        assert_eq!(unknown.to_string(), "-99");
+        assert_eq!(i32::from(unknown), -99);
+
+        for code in RpcErrorCode::ALL {
+            assert!(code.is_known());
+            assert_eq!(&RpcErrorCode::from_i32(code.code()), code);
+        }
+        let distinct: BTreeSet<i32> = RpcErrorCode::ALL.iter().map(RpcErrorCode::code).collect();
+        assert_eq!(distinct.len(), RpcErrorCode::ALL.len());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(RpcErrorCode::InWarmup.is_retryable());
+        assert!(RpcErrorCode::ClientInInitialDownload.is_retryable());
+        assert!(!RpcErrorCode::InvalidParameter.is_retryable());
+        assert!(!RpcErrorCode::WalletUnlockNeeded.is_retryable());
+        assert!(!RpcErrorCode::Unknown(-99).is_retryable());
+    }
+
+    #[test]
+    fn test_rpc_error_code() {
+        let error = RpcError::new(-28, "Loading block index...");
+        assert_eq!(error.error_code(), RpcErrorCode::InWarmup);
+        assert_eq!(serde_json::to_string(&RpcErrorCode::WalletError).unwrap(), "-4");
+        let code: RpcErrorCode = serde_json::from_str("-18").unwrap();
+        assert_eq!(code, RpcErrorCode::WalletNotFound);
+    }
+}
@@ -0,0 +1,207 @@
+//! Structured view of method examples
+//!
+//! `BtcMethod::examples` holds the example invocations printed by `help`,
+//! one per line and prefixed with `> `. This module parses the `bitcoin-cli`
+//! and `curl` invocations so they can be replayed by tests and docs.
+
+use serde_json::Value;
+
+use crate::types::BtcMethod;
+
+/// How an example invokes the method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleKind {
+    /// `bitcoin-cli` command line
+    Cli,
+    /// `curl` JSON-RPC request
+    Curl,
+}
+
+/// A single parsed example invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// How the method is invoked
+    pub kind: ExampleKind,
+    /// Name of the invoked method
+    pub method: String,
+    /// Parameters as they would be passed to `bitcoin-cli`
+    ///
+    /// Shell quoting is removed. Named parameters are `name=value` pairs;
+    /// parameters of `curl` examples are rendered as JSON, except strings,
+    /// which are passed unquoted.
+    pub params: Vec<String>,
+    /// Whether parameters are passed by name (`-named`, or a JSON object)
+    pub named: bool,
+    /// The example line, without the `> ` prompt
+    pub line: String,
+}
+
+impl BtcMethod {
+    /// Parses the `bitcoin-cli` and `curl` invocations in `examples`
+    ///
+    /// Descriptive text and lines that cannot be parsed are skipped.
+    pub fn parsed_examples(&self) -> Vec<Example> {
+        self.examples
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("> "))
+            .filter_map(parse_example)
+            .collect()
+    }
+}
+
+fn parse_example(line: &str) -> Option<Example> {
+    let line = line.trim();
+    let words = shell_words(line)?;
+    match words.first()?.as_str() {
+        "bitcoin-cli" => parse_cli(line, &words[1..]),
+        "curl" => parse_curl(line, &words[1..]),
+        _ => None,
+    }
+}
+
+fn parse_cli(line: &str, words: &[String]) -> Option<Example> {
+    let options = words.iter().take_while(|word| word.starts_with('-')).count();
+    let named = words[..options].iter().any(|option| option == "-named");
+    let (method, params) = words[options..].split_first()?;
+    Some(Example {
+        kind: ExampleKind::Cli,
+        method: method.clone(),
+        params: params.to_vec(),
+        named,
+        line: line.to_string(),
+    })
+}
+
+fn parse_curl(line: &str, words: &[String]) -> Option<Example> {
+    let position = words.iter().position(|word| word == "--data-binary" || word == "-d")?;
+    let body: Value = serde_json::from_str(words.get(position + 1)?).ok()?;
+    let method = body.get("method")?.as_str()?.to_string();
+
+    let render = |value: &Value| match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    let (params, named) = match body.get("params") {
+        None | Some(Value::Null) => (Vec::new(), false),
+        Some(Value::Array(values)) => (values.iter().map(render).collect(), false),
+        Some(Value::Object(map)) =>
+            (map.iter().map(|(name, value)| format!("{}={}", name, render(value))).collect(), true),
+        Some(_) => return None,
+    };
+
+    Some(Example { kind: ExampleKind::Curl, method, params, named, line: line.to_string() })
+}
+
+/// Splits a command line into words the way a POSIX shell would, for the
+/// quoting used in examples: single quotes, double quotes with `\` escapes,
+/// and `\` outside quotes
+///
+/// Returns `None` on an unterminated quote.
+fn shell_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() =>
+                if let Some(word) = word.take() {
+                    words.push(word);
+                },
+            '\'' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => current.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => current.push(c),
+                            c => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).push(chars.next()?),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GETBLOCK_EXAMPLES: &str = r#"
+> bitcoin-cli getblock "00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"
+> curl --user myusername --data-binary '{"jsonrpc": "2.0", "id": "curltest", "method": "getblock", "params": ["00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09", 2]}' -H 'content-type: application/json' http://127.0.0.1:8332/
+"#;
+
+    #[test]
+    fn test_parsed_examples() {
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            examples: GETBLOCK_EXAMPLES.to_string(),
+            ..BtcMethod::default()
+        };
+        let examples = method.parsed_examples();
+        assert_eq!(examples.len(), 2);
+
+        assert_eq!(examples[0].kind, ExampleKind::Cli);
+        assert_eq!(examples[0].method, "getblock");
+        assert_eq!(
+            examples[0].params,
+            vec!["00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"]
+        );
+        assert!(!examples[0].named);
+        assert!(examples[0].line.starts_with("bitcoin-cli getblock"));
+
+        assert_eq!(examples[1].kind, ExampleKind::Curl);
+        assert_eq!(examples[1].method, "getblock");
+        assert_eq!(examples[1].params[1], "2");
+    }
+
+    #[test]
+    fn test_parsed_examples_named_and_descriptions() {
+        let method = BtcMethod {
+            examples: r#"Send 0.1 BTC with a fee rate of 1.1 sat/vB
+> bitcoin-cli -named send outputs='{"bc1q09vm5lfy0j5reeulh4x5752q25uqqvz34hufdl": 0.1}' fee_rate=1.1
+> curl --user myusername --data-binary '{"jsonrpc": "2.0", "id": "curltest", "method": "send", "params": {"fee_rate": 1.1, "comment": "rent"}}' http://127.0.0.1:8332/
+"#
+            .to_string(),
+            ..BtcMethod::default()
+        };
+        let examples = method.parsed_examples();
+        assert_eq!(examples.len(), 2);
+        assert!(examples[0].named);
+        assert_eq!(
+            examples[0].params,
+            vec![r#"outputs={"bc1q09vm5lfy0j5reeulh4x5752q25uqqvz34hufdl": 0.1}"#, "fee_rate=1.1"]
+        );
+        assert!(examples[1].named);
+        assert_eq!(examples[1].params, vec!["comment=rent", "fee_rate=1.1"]);
+    }
+
+    #[test]
+    fn test_shell_words() {
+        assert_eq!(
+            shell_words(r#"a "b \"c\"" 'd e'f\ g"#).unwrap(),
+            vec!["a", r#"b "c""#, "d ef g"]
+        );
+        assert!(shell_words("'unterminated").is_none());
+        assert!(shell_words("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+//! Fee estimation result types
+//!
+//! This module provides `EstimateSmartFeeResult`, returned by
+//! `estimatesmartfee`. Its `estimate_mode` argument is `EstimateMode`.
+
+use bitcoin::{Amount, FeeRate};
+use serde::{Deserialize, Serialize};
+
+use crate::fee_rate::FeeRateParam;
+
+/// Result of `estimatesmartfee`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EstimateSmartFeeResult {
+    /// Estimated fee rate per kvB, absent if no estimate is available
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub feerate: Option<Amount>,
+    /// Errors encountered while estimating
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// Confirmation target the estimate is for, which may differ from the
+    /// requested target
+    pub blocks: u32,
+}
+
+impl EstimateSmartFeeResult {
+    /// Returns the estimated fee rate, rounded down to a whole sat/kwu
+    pub fn fee_rate(&self) -> Option<FeeRate> {
+        self.feerate.map(|feerate| FeeRateParam::from_btc_per_kvb(feerate).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_smart_fee() {
+        let value = json!({ "feerate": 0.00012, "blocks": 2 });
+        let result: EstimateSmartFeeResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(result.feerate, Some(Amount::from_sat(12_000)));
+        assert_eq!(result.fee_rate(), Some(FeeRate::from_sat_per_vb_unchecked(12)));
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+        let value = json!({ "errors": ["Insufficient data or no feerate found"], "blocks": 0 });
+        let result: EstimateSmartFeeResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(result.fee_rate(), None);
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+    }
+}
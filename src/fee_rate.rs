@@ -0,0 +1,245 @@
+//! Fee rate argument type
+//!
+//! Bitcoin Core takes fee rates in two units: `fee_rate` arguments and
+//! options are in sat/vB, while `feeRate` and `maxfeerate` are in BTC/kvB.
+//! Sending a rate in the wrong unit over- or underpays by a factor of 10^5.
+//! `FeeRateParam` holds the rate independent of the unit and is written out
+//! through `FeeRateUnit`, or the `as_sat_per_vb` and `as_btc_per_kvb` serde
+//! modules for struct fields.
+
+use std::fmt;
+
+use bitcoin::{Amount, FeeRate};
+use serde_json::{Number, Value};
+
+/// Unit of a fee rate in the JSON-RPC interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeRateUnit {
+    /// Satoshis per virtual byte, as a number with up to 3 decimals
+    SatPerVb,
+    /// BTC per 1000 virtual bytes, as a BTC amount
+    BtcPerKvb,
+}
+
+impl FeeRateUnit {
+    /// Returns the unit of a fee rate argument or option, by name
+    pub fn for_argument(name: &str) -> Option<Self> {
+        match name {
+            "fee_rate" => Some(Self::SatPerVb),
+            "feeRate" | "maxfeerate" => Some(Self::BtcPerKvb),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FeeRateUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SatPerVb => "sat/vB",
+            Self::BtcPerKvb => "BTC/kvB",
+        })
+    }
+}
+
+/// Fee rate argument, stored in sat/kvB
+///
+/// sat/kvB is Bitcoin Core's internal unit, so rates in either RPC unit
+/// convert without loss: 1 sat/kvB is 0.001 sat/vB or 1 satoshi in BTC/kvB.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeeRateParam(u64);
+
+impl FeeRateParam {
+    /// Zero fee rate
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a fee rate from sat/kvB
+    pub fn from_sat_per_kvb(sat_per_kvb: u64) -> Self { Self(sat_per_kvb) }
+
+    /// Creates a fee rate from sat/vB, rounded to 0.001 sat/vB
+    ///
+    /// Returns `None` for negative, non-finite or overflowing rates.
+    pub fn from_sat_per_vb(sat_per_vb: f64) -> Option<Self> {
+        let sat_per_kvb = (sat_per_vb * 1000.0).round();
+        match sat_per_kvb.is_finite() && (0.0..u64::MAX as f64).contains(&sat_per_kvb) {
+            true => Some(Self(sat_per_kvb as u64)),
+            false => None,
+        }
+    }
+
+    /// Creates a fee rate from an amount per 1000 virtual bytes
+    pub fn from_btc_per_kvb(amount: Amount) -> Self { Self(amount.to_sat()) }
+
+    /// Returns the rate in sat/kvB
+    pub fn to_sat_per_kvb(self) -> u64 { self.0 }
+
+    /// Returns the rate in sat/vB
+    pub fn to_sat_per_vb(self) -> f64 { self.0 as f64 / 1000.0 }
+
+    /// Returns the amount per 1000 virtual bytes
+    pub fn to_btc_per_kvb(self) -> Amount { Amount::from_sat(self.0) }
+
+    /// Returns the JSON value of the rate in a unit
+    pub fn to_json_value(self, unit: FeeRateUnit) -> Value {
+        let number = match unit {
+            FeeRateUnit::SatPerVb => self.to_sat_per_vb(),
+            FeeRateUnit::BtcPerKvb => self.to_btc_per_kvb().to_btc(),
+        };
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+
+    /// Parses a JSON number or numeric string in a unit
+    pub fn from_json_value(value: &Value, unit: FeeRateUnit) -> Option<Self> {
+        let number = match value {
+            Value::Number(number) => number.as_f64()?,
+            Value::String(s) => s.parse().ok()?,
+            _ => return None,
+        };
+        match unit {
+            FeeRateUnit::SatPerVb => Self::from_sat_per_vb(number),
+            FeeRateUnit::BtcPerKvb => Amount::from_btc(number).ok().map(Self::from_btc_per_kvb),
+        }
+    }
+}
+
+impl fmt::Display for FeeRateParam {
+    /// Formats the rate in sat/vB
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_sat_per_vb(), FeeRateUnit::SatPerVb)
+    }
+}
+
+impl From<FeeRate> for FeeRateParam {
+    /// Converts exactly; 1 sat/kwu is 4 sat/kvB
+    fn from(fee_rate: FeeRate) -> Self { Self(fee_rate.to_sat_per_kwu().saturating_mul(4)) }
+}
+
+impl From<FeeRateParam> for FeeRate {
+    /// Converts to sat/kwu, rounding down to a multiple of 4 sat/kvB
+    fn from(fee_rate: FeeRateParam) -> Self { FeeRate::from_sat_per_kwu(fee_rate.0 / 4) }
+}
+
+macro_rules! fee_rate_serde {
+    ($name:ident, $unit:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Use with `#[serde(with = "...")]`; the `opt` submodule handles
+        /// `Option<FeeRateParam>` fields.
+        pub mod $name {
+            use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+            use serde_json::Value;
+
+            use super::{FeeRateParam, FeeRateUnit};
+
+            /// Serializes a fee rate
+            pub fn serialize<S: Serializer>(
+                fee_rate: &FeeRateParam,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                fee_rate.to_json_value($unit).serialize(serializer)
+            }
+
+            /// Deserializes a fee rate
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<FeeRateParam, D::Error> {
+                let value = Value::deserialize(deserializer)?;
+                FeeRateParam::from_json_value(&value, $unit).ok_or_else(|| {
+                    de::Error::custom(format!("invalid fee rate in {}: {}", $unit, value))
+                })
+            }
+
+            /// Serde functions for `Option<FeeRateParam>`
+            pub mod opt {
+                use serde::{Deserialize, Deserializer, Serializer};
+
+                use super::super::FeeRateParam;
+
+                /// Serializes an optional fee rate
+                pub fn serialize<S: Serializer>(
+                    fee_rate: &Option<FeeRateParam>,
+                    serializer: S,
+                ) -> std::result::Result<S::Ok, S::Error> {
+                    match fee_rate {
+                        Some(fee_rate) => super::serialize(fee_rate, serializer),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                /// Deserializes an optional fee rate
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> std::result::Result<Option<FeeRateParam>, D::Error> {
+                    #[derive(Deserialize)]
+                    struct Wrapper(#[serde(with = "super")] FeeRateParam);
+
+                    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(rate)| rate))
+                }
+            }
+        }
+    };
+}
+
+fee_rate_serde!(as_sat_per_vb, FeeRateUnit::SatPerVb, "Serde functions for fee rates in sat/vB");
+fee_rate_serde!(as_btc_per_kvb, FeeRateUnit::BtcPerKvb, "Serde functions for fee rates in BTC/kvB");
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_fee_rate_units() {
+        let rate = FeeRateParam::from_sat_per_vb(2.5).unwrap();
+        assert_eq!(rate.to_sat_per_kvb(), 2500);
+        assert_eq!(rate.to_btc_per_kvb(), Amount::from_sat(2500));
+        assert_eq!(rate, FeeRateParam::from_btc_per_kvb(Amount::from_sat(2500)));
+        assert_eq!(rate.to_json_value(FeeRateUnit::SatPerVb), json!(2.5));
+        assert_eq!(rate.to_json_value(FeeRateUnit::BtcPerKvb), json!(0.000025));
+        assert_eq!(rate.to_string(), "2.5 sat/vB");
+
+        assert_eq!(FeeRateParam::from_sat_per_vb(0.0014), FeeRateParam::from_sat_per_vb(0.001));
+        assert_eq!(FeeRateParam::from_sat_per_vb(-1.0), None);
+        assert_eq!(FeeRateParam::from_sat_per_vb(f64::NAN), None);
+        assert_eq!(
+            FeeRateParam::from_json_value(&json!("0.0001"), FeeRateUnit::BtcPerKvb),
+            Some(FeeRateParam::from_sat_per_kvb(10_000))
+        );
+        assert_eq!(FeeRateParam::from_json_value(&json!(true), FeeRateUnit::SatPerVb), None);
+
+        assert_eq!(FeeRateUnit::for_argument("fee_rate"), Some(FeeRateUnit::SatPerVb));
+        assert_eq!(FeeRateUnit::for_argument("maxfeerate"), Some(FeeRateUnit::BtcPerKvb));
+        assert_eq!(FeeRateUnit::for_argument("conf_target"), None);
+    }
+
+    #[test]
+    fn test_fee_rate_bitcoin_conversion() {
+        let rate = FeeRateParam::from(FeeRate::from_sat_per_vb_unchecked(3));
+        assert_eq!(rate.to_sat_per_vb(), 3.0);
+        assert_eq!(FeeRate::from(rate), FeeRate::from_sat_per_vb_unchecked(3));
+        assert_eq!(FeeRate::from(FeeRateParam::from_sat_per_kvb(7)).to_sat_per_kwu(), 1);
+    }
+
+    #[test]
+    fn test_fee_rate_serde() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Options {
+            #[serde(with = "as_sat_per_vb")]
+            fee_rate: FeeRateParam,
+            #[serde(rename = "feeRate", default, with = "as_btc_per_kvb::opt")]
+            fee_rate_btc_kvb: Option<FeeRateParam>,
+        }
+
+        let rate = FeeRateParam::from_sat_per_kvb(1500);
+        let options = Options { fee_rate: rate, fee_rate_btc_kvb: Some(rate) };
+        let value = serde_json::to_value(&options).unwrap();
+        assert_eq!(value, json!({ "fee_rate": 1.5, "feeRate": 0.000015 }));
+        assert_eq!(serde_json::from_value::<Options>(value).unwrap(), options);
+        assert_eq!(
+            serde_json::from_value::<Options>(json!({ "fee_rate": "1.5" })).unwrap(),
+            Options { fee_rate: rate, fee_rate_btc_kvb: None }
+        );
+        assert!(serde_json::from_value::<Options>(json!({ "fee_rate": -1 })).is_err());
+    }
+}
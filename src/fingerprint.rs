@@ -0,0 +1,144 @@
+//! Stable schema fingerprinting
+//!
+//! Content hashes over the canonical JSON form of a schema, so build systems
+//! can detect whether regenerating client code is necessary. Per-method
+//! fingerprints allow incremental regeneration.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::hex::{DisplayHex, FromHex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::types::{canonical_value, ApiDefinition, BtcMethod, Result};
+
+/// SHA-256 digest of the canonical JSON serialization of a schema item
+///
+/// Displayed and serialized as lowercase hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fingerprint([u8; 32]);
+
+impl Fingerprint {
+    /// Returns the digest bytes
+    pub fn as_bytes(&self) -> &[u8; 32] { &self.0 }
+
+    /// Hashes the compact JSON of the value with every object's keys sorted
+    fn of<T: Serialize>(value: &T) -> Result<Self> {
+        let json = serde_json::to_vec(&canonical_value(value)?)?;
+        Ok(Self(sha256::Hash::hash(&json).to_byte_array()))
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.0.as_hex()) }
+}
+
+/// Error returned when parsing a `Fingerprint` from hex fails
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid fingerprint: {0:?}")]
+pub struct ParseFingerprintError(String);
+
+impl FromStr for Fingerprint {
+    type Err = ParseFingerprintError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        <[u8; 32]>::from_hex(s).map(Self).map_err(|_| ParseFingerprintError(s.to_string()))
+    }
+}
+
+impl Serialize for Fingerprint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fingerprint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl ApiDefinition {
+    /// Computes the fingerprint of the whole definition
+    ///
+    /// Equal definitions always produce equal fingerprints, independent of
+    /// insertion order or the formatting of the file they were loaded from.
+    pub fn fingerprint(&self) -> Result<Fingerprint> { Fingerprint::of(self) }
+
+    /// Computes the fingerprint of every method, keyed by method name
+    pub fn method_fingerprints(&self) -> Result<BTreeMap<String, Fingerprint>> {
+        self.rpcs.iter().map(|(name, method)| Ok((name.clone(), method.fingerprint()?))).collect()
+    }
+}
+
+impl BtcMethod {
+    /// Computes the fingerprint of this method definition
+    pub fn fingerprint(&self) -> Result<Fingerprint> { Fingerprint::of(self) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(descriptions: &[(&str, &str)]) -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        for (name, description) in descriptions {
+            api_def
+                .insert_method(BtcMethod {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    ..BtcMethod::default()
+                })
+                .unwrap();
+        }
+        api_def
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let first = api(&[("getblock", "a"), ("uptime", "b")]);
+        let second = api(&[("uptime", "b"), ("getblock", "a")]);
+        assert_eq!(first.fingerprint().unwrap(), second.fingerprint().unwrap());
+
+        let reloaded: ApiDefinition = serde_json::to_string(&first).unwrap().parse().unwrap();
+        assert_eq!(first.fingerprint().unwrap(), reloaded.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn test_fingerprint_known_value() {
+        // Pinned, so the hash does not change with serde_json's map backend
+        let uptime = api(&[("uptime", "Returns the server uptime.")]);
+        assert_eq!(
+            uptime.fingerprint().unwrap().to_string(),
+            "28ae21e39951e516ff7dd1322804021ee6efb2c7e8cc17cb1af1c1dbbe8c4738"
+        );
+    }
+
+    #[test]
+    fn test_method_fingerprints_track_changes() {
+        let old = api(&[("getblock", "a"), ("uptime", "b")]);
+        let new = api(&[("getblock", "a"), ("uptime", "changed")]);
+        assert_ne!(old.fingerprint().unwrap(), new.fingerprint().unwrap());
+
+        let old_methods = old.method_fingerprints().unwrap();
+        let new_methods = new.method_fingerprints().unwrap();
+        assert_eq!(old_methods["getblock"], new_methods["getblock"]);
+        assert_ne!(old_methods["uptime"], new_methods["uptime"]);
+    }
+
+    #[test]
+    fn test_fingerprint_hex_round_trip() {
+        let fingerprint = api(&[("getblock", "a")]).fingerprint().unwrap();
+        let hex = fingerprint.to_string();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex.parse::<Fingerprint>().unwrap(), fingerprint);
+        assert!("abcd".parse::<Fingerprint>().is_err());
+
+        let json = serde_json::to_string(&fingerprint).unwrap();
+        assert_eq!(serde_json::from_str::<Fingerprint>(&json).unwrap(), fingerprint);
+    }
+}
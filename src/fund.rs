@@ -0,0 +1,296 @@
+//! `fundrawtransaction` and `walletcreatefundedpsbt` types
+//!
+//! Bitcoin Core's funding options mix camelCase (`changeAddress`,
+//! `subtractFeeFromOutputs`) with snake_case (`add_inputs`, `fee_rate`).
+//! `FundRawTransactionOptions` serializes each field under its documented
+//! name and also accepts the snake_case aliases Core takes.
+//! `walletcreatefundedpsbt` takes the same options, with its inputs given as
+//! `FundingInput`s and its outputs as `TxOutput`s.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::{Address, Amount, Txid};
+use serde::{Deserialize, Serialize};
+
+use crate::address_type::AddressType;
+use crate::fee_rate::FeeRateParam;
+use crate::psbt::PsbtString;
+use crate::raw_tx::RawTxHex;
+
+/// Fee estimate mode for `conf_target`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EstimateMode {
+    /// Let the node choose
+    #[default]
+    Unset,
+    /// Lower fees, more sensitive to short-term drops in the fee market
+    Economical,
+    /// Higher fees, safer over longer confirmation targets
+    Conservative,
+}
+
+/// Options of `fundrawtransaction`
+///
+/// `None` and empty fields are omitted, leaving Bitcoin Core's default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FundRawTransactionOptions {
+    /// Add inputs beyond the ones given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_inputs: Option<bool>,
+    /// Include unconfirmed inputs from outside the wallet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_unsafe: Option<bool>,
+    /// Minimum confirmations of added inputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minconf: Option<u32>,
+    /// Maximum confirmations of added inputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxconf: Option<u32>,
+    /// Address to send the change to
+    #[serde(
+        rename = "changeAddress",
+        alias = "change_address",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub change_address: Option<Address<NetworkUnchecked>>,
+    /// Output index of the change output
+    #[serde(
+        rename = "changePosition",
+        alias = "change_position",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub change_position: Option<u32>,
+    /// Address type of the change output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_type: Option<AddressType>,
+    /// Also select watch-only inputs
+    #[serde(
+        rename = "includeWatching",
+        alias = "include_watching",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub include_watching: Option<bool>,
+    /// Lock the selected inputs
+    #[serde(
+        rename = "lockUnspents",
+        alias = "lock_unspents",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub lock_unspents: Option<bool>,
+    /// Fee rate, sent in sat/vB
+    #[serde(
+        default,
+        with = "crate::fee_rate::as_sat_per_vb::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate: Option<FeeRateParam>,
+    /// Fee rate, sent in BTC/kvB; Bitcoin Core rejects it alongside `fee_rate`
+    #[serde(
+        rename = "feeRate",
+        default,
+        with = "crate::fee_rate::as_btc_per_kvb::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate_btc_kvb: Option<FeeRateParam>,
+    /// Indexes of the outputs the fee is deducted from
+    #[serde(
+        rename = "subtractFeeFromOutputs",
+        alias = "subtract_fee_from_outputs",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub subtract_fee_from_outputs: Vec<u32>,
+    /// Weights of inputs the wallet cannot estimate
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_weights: Vec<InputWeight>,
+    /// Maximum weight of the funded transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tx_weight: Option<u64>,
+    /// Signal BIP125 replaceability
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaceable: Option<bool>,
+    /// Confirmation target in blocks for fee estimation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conf_target: Option<u32>,
+    /// Fee estimate mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate_mode: Option<EstimateMode>,
+    /// Keys, scripts and descriptors for solving external inputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solving_data: Option<SolvingData>,
+}
+
+/// Weight of an external input
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InputWeight {
+    /// Transaction id of the input
+    pub txid: Txid,
+    /// Output index of the input
+    pub vout: u32,
+    /// Weight of the input, including the outpoint and sequence
+    pub weight: u64,
+}
+
+/// Data for solving inputs the wallet does not know
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolvingData {
+    /// Public keys, as hex
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pubkeys: Vec<String>,
+    /// Scripts, as hex
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<String>,
+    /// Output descriptors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub descriptors: Vec<String>,
+}
+
+/// Options of `walletcreatefundedpsbt`
+///
+/// The options are shared with `fundrawtransaction`, except that
+/// `input_weights` is not accepted; weights go in `FundingInput::weight`.
+pub type WalletCreateFundedPsbtOptions = FundRawTransactionOptions;
+
+/// Input of `walletcreatefundedpsbt`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FundingInput {
+    /// Transaction id of the output to spend
+    pub txid: Txid,
+    /// Output index of the output to spend
+    pub vout: u32,
+    /// Sequence number; defaults from the locktime and replaceability
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    /// Weight of the input, for inputs the wallet cannot estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u64>,
+}
+
+impl FundingInput {
+    /// Creates an input spending an outpoint
+    pub fn new(txid: Txid, vout: u32) -> Self { Self { txid, vout, sequence: None, weight: None } }
+}
+
+/// Result of `walletcreatefundedpsbt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletCreateFundedPsbtResult {
+    /// The funded PSBT
+    pub psbt: PsbtString,
+    /// Fee of the funded transaction
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub fee: Amount,
+    /// Index of the change output, or -1 without change
+    pub changepos: i32,
+}
+
+impl WalletCreateFundedPsbtResult {
+    /// Returns the index of the change output, if there is one
+    pub fn change_position(&self) -> Option<u32> { u32::try_from(self.changepos).ok() }
+}
+
+/// Result of `fundrawtransaction`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundRawTransactionResult {
+    /// The funded transaction
+    pub hex: RawTxHex,
+    /// Fee of the funded transaction
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub fee: Amount,
+    /// Index of the change output, or -1 without change
+    pub changepos: i32,
+}
+
+impl FundRawTransactionResult {
+    /// Returns the index of the change output, if there is one
+    pub fn change_position(&self) -> Option<u32> { u32::try_from(self.changepos).ok() }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_fund_options_serialize() {
+        let options = FundRawTransactionOptions {
+            add_inputs: Some(true),
+            change_address: Some("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".parse().unwrap()),
+            change_position: Some(1),
+            change_type: Some(AddressType::Bech32m),
+            lock_unspents: Some(true),
+            fee_rate: FeeRateParam::from_sat_per_vb(2.5),
+            fee_rate_btc_kvb: Some(FeeRateParam::from_sat_per_kvb(1000)),
+            subtract_fee_from_outputs: vec![0],
+            input_weights: vec![InputWeight { txid: TXID.parse().unwrap(), vout: 1, weight: 272 }],
+            estimate_mode: Some(EstimateMode::Economical),
+            ..FundRawTransactionOptions::default()
+        };
+        let value = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "add_inputs": true,
+                "changeAddress": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                "changePosition": 1,
+                "change_type": "bech32m",
+                "lockUnspents": true,
+                "fee_rate": 2.5,
+                "feeRate": 0.00001,
+                "subtractFeeFromOutputs": [0],
+                "input_weights": [{ "txid": TXID, "vout": 1, "weight": 272 }],
+                "estimate_mode": "economical",
+            })
+        );
+        assert_eq!(serde_json::from_value::<FundRawTransactionOptions>(value).unwrap(), options);
+        assert_eq!(serde_json::to_value(FundRawTransactionOptions::default()).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn test_fund_options_aliases() {
+        let options: FundRawTransactionOptions = serde_json::from_value(json!({
+            "change_position": 0,
+            "include_watching": true,
+            "subtract_fee_from_outputs": [1, 2],
+        }))
+        .unwrap();
+        assert_eq!(options.change_position, Some(0));
+        assert_eq!(options.include_watching, Some(true));
+        assert_eq!(options.subtract_fee_from_outputs, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_fund_result() {
+        let result: FundRawTransactionResult =
+            serde_json::from_value(json!({ "hex": "0200", "fee": 0.0000141, "changepos": -1 }))
+                .unwrap();
+        assert_eq!(result.fee, Amount::from_sat(1410));
+        assert_eq!(result.change_position(), None);
+    }
+
+    #[test]
+    fn test_wallet_create_funded_psbt() {
+        let inputs = vec![
+            FundingInput::new(TXID.parse().unwrap(), 0),
+            FundingInput {
+                sequence: Some(0xfffffffd),
+                ..FundingInput::new(TXID.parse().unwrap(), 1)
+            },
+        ];
+        let value = serde_json::to_value(&inputs).unwrap();
+        assert_eq!(
+            value,
+            json!([{ "txid": TXID, "vout": 0 }, { "txid": TXID, "vout": 1, "sequence": 4294967293u32 }])
+        );
+        assert_eq!(serde_json::from_value::<Vec<FundingInput>>(value).unwrap(), inputs);
+
+        let result: WalletCreateFundedPsbtResult = serde_json::from_value(
+            json!({ "psbt": "cHNidP8BAAoCAAAAAAAAAAAAAAAA", "fee": 0.00002, "changepos": 1 }),
+        )
+        .unwrap();
+        assert_eq!(result.fee, Amount::from_sat(2000));
+        assert_eq!(result.change_position(), Some(1));
+    }
+}
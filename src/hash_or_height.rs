@@ -2,18 +2,26 @@
 //!
 //! This module provides the `HashOrHeight` enum which represents either a block hash
 //! (bitcoin::BlockHash) or block height (integer) for Bitcoin RPC APIs where methods
-//! can accept either identifier to specify a particular block.
+//! can accept either identifier to specify a particular block. Core rejects
+//! negative heights, so blocks counted back from the tip are a separate
+//! `TipOffset`, resolved against a known tip height before they are sent.
+
+use std::fmt;
+use std::str::FromStr;
 
 use bitcoin::BlockHash;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
 
 /// Represents either a block hash or a block height
 ///
 /// This type is used in some Bitcoin RPC APIs where methods can accept
 /// either a block hash (bitcoin::BlockHash) or a block height (u32) to
 /// identify a specific block.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// Serializes as the hash string or the height.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum HashOrHeight {
     /// Block hash
     Hash(BlockHash),
@@ -45,6 +53,163 @@ impl HashOrHeight {
             None
         }
     }
+
+    /// Returns the JSON value sent for this in RPC parameters
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            Self::Hash(hash) => Value::String(hash.to_string()),
+            Self::Height(height) => Value::from(*height),
+        }
+    }
+}
+
+impl Serialize for HashOrHeight {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Hash(hash) => hash.serialize(serializer),
+            Self::Height(height) => serializer.serialize_u32(*height),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HashOrHeight {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Hash(BlockHash),
+            Height(i64),
+        }
+
+        match Repr::deserialize(deserializer) {
+            Ok(Repr::Hash(hash)) => Ok(Self::Hash(hash)),
+            Ok(Repr::Height(height)) => Self::try_from(height).map_err(de::Error::custom),
+            Err(_) => Err(de::Error::custom("expected a block hash or a block height")),
+        }
+    }
+}
+
+impl fmt::Display for HashOrHeight {
+    /// Formats a hash as 64 hex characters and a height in decimal
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hash(hash) => write!(f, "{}", hash),
+            Self::Height(height) => write!(f, "{}", height),
+        }
+    }
+}
+
+/// A block counted back from the chain tip, `0` being the tip itself
+///
+/// No RPC takes tip-relative heights (Core's `ParseHashOrHeight` rejects
+/// negative heights), so an offset is resolved into a `HashOrHeight` against
+/// a known tip height, e.g. from `getblockcount`, before it is sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TipOffset(u32);
+
+impl TipOffset {
+    /// The chain tip
+    pub const TIP: Self = Self(0);
+
+    /// Creates an offset of `blocks` below the chain tip
+    pub fn new(blocks: u32) -> Self { Self(blocks) }
+
+    /// Returns the number of blocks below the chain tip
+    pub fn blocks(self) -> u32 { self.0 }
+
+    /// Returns the height of the block, or None if the offset is deeper
+    /// than the chain
+    pub fn to_height(self, tip_height: u32) -> Option<u32> { tip_height.checked_sub(self.0) }
+
+    /// Resolves the offset into a height against the given tip height
+    pub fn resolve(self, tip_height: u32) -> Option<HashOrHeight> {
+        self.to_height(tip_height).map(HashOrHeight::Height)
+    }
+}
+
+/// Error returned when parsing a `HashOrHeight` fails
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseHashOrHeightError {
+    /// A string that should be, but is not, a 64-character hex block hash
+    #[error("Invalid block hash: {0:?}")]
+    InvalidHash(String),
+    /// A decimal integer too large for a block height
+    #[error("Block height out of range: {0:?}")]
+    HeightOutOfRange(String),
+    /// A negative block height, which Bitcoin Core rejects
+    #[error("Block height is negative: {0:?}")]
+    NegativeHeight(String),
+    /// Neither a block hash nor a decimal integer
+    #[error("Expected a 64-character hex block hash or a block height, got {0:?}")]
+    Invalid(String),
+}
+
+impl FromStr for HashOrHeight {
+    type Err = ParseHashOrHeightError;
+
+    /// Parses 64 hex characters as a hash and a decimal integer as a height
+    ///
+    /// Surrounding whitespace is ignored.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.len() == 64 {
+            return trimmed
+                .parse()
+                .map(Self::Hash)
+                .map_err(|_| ParseHashOrHeightError::InvalidHash(s.to_string()));
+        }
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, trimmed),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ParseHashOrHeightError::Invalid(s.to_string()));
+        }
+        let height: u32 =
+            digits.parse().map_err(|_| ParseHashOrHeightError::HeightOutOfRange(s.to_string()))?;
+        match negative {
+            true => Err(ParseHashOrHeightError::NegativeHeight(s.to_string())),
+            false => Ok(Self::Height(height)),
+        }
+    }
+}
+
+impl TryFrom<i64> for HashOrHeight {
+    type Error = ParseHashOrHeightError;
+
+    /// Converts a height, rejecting negative ones
+    fn try_from(height: i64) -> std::result::Result<Self, Self::Error> {
+        if height < 0 {
+            return Err(ParseHashOrHeightError::NegativeHeight(height.to_string()));
+        }
+        u32::try_from(height)
+            .map(Self::Height)
+            .map_err(|_| ParseHashOrHeightError::HeightOutOfRange(height.to_string()))
+    }
+}
+
+impl TryFrom<Value> for HashOrHeight {
+    type Error = ParseHashOrHeightError;
+
+    /// Converts a JSON string holding a block hash or a JSON integer height,
+    /// as `TryFrom<i64>`
+    ///
+    /// Strings must be hashes; `"100"` is rejected as it would be by the node.
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
+        match value {
+            Value::String(hash) => match hash.parse() {
+                Ok(hash) => Ok(Self::Hash(hash)),
+                Err(_) => Err(ParseHashOrHeightError::InvalidHash(hash)),
+            },
+            Value::Number(number) => match number.as_i64() {
+                Some(height) => Self::try_from(height),
+                None if number.is_u64() =>
+                    Err(ParseHashOrHeightError::HeightOutOfRange(number.to_string())),
+                None => Err(ParseHashOrHeightError::Invalid(number.to_string())),
+            },
+            value => Err(ParseHashOrHeightError::Invalid(value.to_string())),
+        }
+    }
 }
 
 impl From<BlockHash> for HashOrHeight {
@@ -135,6 +300,105 @@ mod tests {
         assert_eq!(hash_or_height.as_hash().unwrap(), &hash);
     }
 
+    #[test]
+    fn test_hash_or_height_from_str() {
+        let hash = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+        let parsed: HashOrHeight = hash.parse().unwrap();
+        assert_eq!(parsed, HashOrHeight::Hash(hash.parse().unwrap()));
+        assert_eq!(parsed.to_string(), hash);
+
+        let parsed: HashOrHeight = " 840000\n".parse().unwrap();
+        assert_eq!(parsed, HashOrHeight::Height(840000));
+        assert_eq!(parsed.to_string(), "840000");
+
+        assert_eq!(
+            "z".repeat(64).parse::<HashOrHeight>(),
+            Err(ParseHashOrHeightError::InvalidHash("z".repeat(64)))
+        );
+        assert_eq!(
+            "4294967296".parse::<HashOrHeight>(),
+            Err(ParseHashOrHeightError::HeightOutOfRange("4294967296".to_string()))
+        );
+        for invalid in ["", "-", "00ff", "12a"] {
+            assert_eq!(
+                invalid.parse::<HashOrHeight>(),
+                Err(ParseHashOrHeightError::Invalid(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_tip_offset() {
+        let offset = TipOffset::new(6);
+        assert_eq!(offset.blocks(), 6);
+        assert_eq!(offset.to_height(840000), Some(839994));
+        assert_eq!(offset.resolve(840000), Some(HashOrHeight::Height(839994)));
+        assert_eq!(TipOffset::TIP.resolve(840000), Some(HashOrHeight::Height(840000)));
+        assert_eq!(offset.resolve(5), None);
+    }
+
+    #[test]
+    fn test_hash_or_height_serde() {
+        let hash = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+        let hash_or_height = HashOrHeight::Hash(hash.parse().unwrap());
+        assert_eq!(serde_json::to_value(&hash_or_height).unwrap(), serde_json::json!(hash));
+        assert_eq!(serde_json::to_string(&HashOrHeight::Height(100)).unwrap(), "100");
+        assert_eq!(
+            serde_json::from_value::<HashOrHeight>(serde_json::json!(hash)).unwrap(),
+            hash_or_height
+        );
+        assert_eq!(serde_json::from_str::<HashOrHeight>("100").unwrap(), HashOrHeight::Height(100));
+
+        assert!(serde_json::from_str::<HashOrHeight>("4294967296").is_err());
+        assert!(serde_json::from_str::<HashOrHeight>("\"00ff\"").is_err());
+        assert!(serde_json::from_str::<HashOrHeight>("1.5").is_err());
+    }
+
+    #[test]
+    fn test_hash_or_height_try_from() {
+        let hash = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+        let hash_or_height = HashOrHeight::try_from(serde_json::json!(hash)).unwrap();
+        assert_eq!(hash_or_height, HashOrHeight::Hash(hash.parse().unwrap()));
+        assert_eq!(hash_or_height.to_json_value(), serde_json::json!(hash));
+
+        assert_eq!(HashOrHeight::try_from(840000i64), Ok(HashOrHeight::Height(840000)));
+        assert_eq!(
+            HashOrHeight::try_from(-1i64),
+            Err(ParseHashOrHeightError::NegativeHeight("-1".to_string()))
+        );
+        assert_eq!(
+            HashOrHeight::try_from(serde_json::json!(-1)),
+            Err(ParseHashOrHeightError::NegativeHeight("-1".to_string()))
+        );
+        assert_eq!(
+            "-6".parse::<HashOrHeight>(),
+            Err(ParseHashOrHeightError::NegativeHeight("-6".to_string()))
+        );
+        assert!(serde_json::from_str::<HashOrHeight>("-6").is_err());
+        assert_eq!(HashOrHeight::Height(7).to_json_value(), serde_json::json!(7));
+
+        assert_eq!(
+            HashOrHeight::try_from(i64::from(u32::MAX) + 1),
+            Err(ParseHashOrHeightError::HeightOutOfRange("4294967296".to_string()))
+        );
+        assert_eq!(
+            HashOrHeight::try_from(serde_json::json!("100")),
+            Err(ParseHashOrHeightError::InvalidHash("100".to_string()))
+        );
+        assert_eq!(
+            HashOrHeight::try_from(serde_json::json!(u64::MAX)),
+            Err(ParseHashOrHeightError::HeightOutOfRange(u64::MAX.to_string()))
+        );
+        assert!(matches!(
+            HashOrHeight::try_from(serde_json::json!(1.5)),
+            Err(ParseHashOrHeightError::Invalid(_))
+        ));
+        assert!(matches!(
+            HashOrHeight::try_from(Value::Null),
+            Err(ParseHashOrHeightError::Invalid(_))
+        ));
+    }
+
     #[test]
     fn test_from_u32() {
         let height = 12345u32;
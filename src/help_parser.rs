@@ -0,0 +1,591 @@
+//! Parser for `bitcoin-cli help` output
+//!
+//! This module builds `BtcMethod`, `BtcArgument` and `BtcResult` structures
+//! from the human-readable text returned by the `help` and `help <method>`
+//! RPCs, so a schema can be bootstrapped from any running node.
+
+use crate::types::{ApiDefinition, BtcArgument, BtcMethod, BtcResult, Result, SchemaError};
+
+/// A single method entry of the `help` overview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpEntry {
+    /// Category heading the method is listed under (e.g. `Blockchain`)
+    pub category: String,
+    /// Name of the method
+    pub name: String,
+    /// Full one-line synopsis, including the argument summary
+    pub synopsis: String,
+}
+
+/// Parses the output of `help` (without arguments) into its method entries
+pub fn parse_help_overview(text: &str) -> Vec<HelpEntry> {
+    let mut category = String::new();
+    let mut entries = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("==").and_then(|rest| rest.strip_suffix("==")) {
+            category = heading.trim().to_string();
+            continue;
+        }
+        if let Some(name) = line.split_whitespace().next() {
+            entries.push(HelpEntry {
+                category: category.clone(),
+                name: name.to_string(),
+                synopsis: line.to_string(),
+            });
+        }
+    }
+
+    entries
+}
+
+/// Parses the output of `help <method>` into a method definition
+pub fn parse_method_help(text: &str) -> Result<BtcMethod> {
+    let mut lines = text.lines();
+    let name = lines
+        .by_ref()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .and_then(|line| line.split_whitespace().next())
+        .ok_or_else(|| SchemaError::HelpParse("empty help text".to_string()))?
+        .to_string();
+
+    let mut section = Section::Description;
+    let mut description = Vec::new();
+    let mut argument_lines = Vec::new();
+    let mut result_sections: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut examples = Vec::new();
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed == "Arguments:" {
+            section = Section::Arguments;
+            continue;
+        }
+        if trimmed == "Examples:" {
+            section = Section::Examples;
+            continue;
+        }
+        if let Some(condition) = result_heading(trimmed) {
+            section = Section::Result;
+            result_sections.push((condition, Vec::new()));
+            continue;
+        }
+
+        match section {
+            Section::Description => description.push(trimmed),
+            Section::Arguments => argument_lines.push(line),
+            Section::Result =>
+                if let Some((_, lines)) = result_sections.last_mut() {
+                    lines.push(line);
+                },
+            Section::Examples => examples.push(trimmed),
+        }
+    }
+
+    let arguments = parse_arguments(&argument_lines)?;
+    let mut results = Vec::new();
+    for (condition, lines) in &result_sections {
+        for mut result in parse_results(lines)? {
+            result.condition = condition.clone();
+            results.push(result);
+        }
+    }
+
+    Ok(BtcMethod {
+        argument_names: arguments.iter().filter_map(|arg| arg.names.first().cloned()).collect(),
+        name,
+        description: description.join("\n").trim().to_string(),
+        examples: examples.join("\n").trim().to_string(),
+        arguments,
+        results,
+        ..BtcMethod::default()
+    })
+}
+
+impl ApiDefinition {
+    /// Builds an API definition from `help` output
+    ///
+    /// `overview` is the output of `help`; `method_help` is called with each
+    /// method name and must return the output of `help <method>`.
+    pub fn from_help<F>(overview: &str, mut method_help: F) -> Result<Self>
+    where
+        F: FnMut(&str) -> Result<String>,
+    {
+        let mut api_def = ApiDefinition::new();
+        for entry in parse_help_overview(overview) {
+            let mut method = parse_method_help(&method_help(&entry.name)?)?;
+            method.category = entry.category.parse().ok();
+            api_def.rpcs.insert(entry.name, method);
+        }
+        Ok(api_def)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Description,
+    Arguments,
+    Result,
+    Examples,
+}
+
+/// Returns the condition of a `Result:` / `Result (...):` heading line
+fn result_heading(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("Result")?.strip_suffix(':')?.trim();
+    if rest.is_empty() {
+        return Some(String::new());
+    }
+    rest.strip_prefix('(')?.strip_suffix(')').map(|condition| condition.trim().to_string())
+}
+
+/// Splits a line into the text before a type annotation, the annotation
+/// contents, and the trailing description
+fn split_annotation(line: &str) -> Option<(&str, &str, &str)> {
+    let mut search_from = 0;
+    while let Some(offset) = line[search_from..].find('(') {
+        let open = search_from + offset;
+        let preceded_by_space = line[..open].ends_with(char::is_whitespace) || open == 0;
+        if preceded_by_space && starts_with_type(&line[open + 1..]) {
+            let mut depth = 0;
+            for (i, c) in line[open..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            let close = open + i;
+                            return Some((
+                                line[..open].trim(),
+                                &line[open + 1..close],
+                                line[close + 1..].trim(),
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            return None;
+        }
+        search_from = open + 1;
+    }
+    None
+}
+
+fn starts_with_type(s: &str) -> bool {
+    ["string", "numeric", "boolean", "json ", "any"].iter().any(|prefix| s.starts_with(prefix))
+}
+
+/// Maps a help type description (e.g. `json object`) to a schema type name
+fn schema_type(help_type: &str) -> String {
+    match help_type.trim() {
+        "string" => "string",
+        "numeric" => "number",
+        "boolean" => "boolean",
+        "json object" => "object",
+        "json array" => "array",
+        "json null" => "none",
+        other => other,
+    }
+    .to_string()
+}
+
+fn parse_arguments(lines: &[&str]) -> Result<Vec<BtcArgument>> {
+    let mut arguments: Vec<BtcArgument> = Vec::new();
+    // Option objects and arrays whose body is still open, outermost first
+    let mut stack: Vec<BtcArgument> = Vec::new();
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if stack.is_empty() {
+            if let Some(argument) = parse_argument_line(trimmed)? {
+                arguments.push(argument);
+                continue;
+            }
+        }
+
+        let (head, annotation, description) = match split_annotation(trimmed) {
+            Some((head, annotation, description)) => (head, Some(annotation), description),
+            None => (trimmed, None, ""),
+        };
+        let head = head.trim_end_matches(',').trim();
+
+        if head.starts_with(['}', ']']) {
+            let node = stack.pop().ok_or_else(|| {
+                SchemaError::HelpParse(format!("unbalanced argument line: {line}"))
+            })?;
+            match stack.last_mut() {
+                Some(parent) => parent.inner.push(node),
+                None => arguments.push(node),
+            }
+            continue;
+        }
+
+        if head.starts_with("...") || head.starts_with(",...") {
+            continue;
+        }
+
+        let Some(annotation) = annotation else {
+            if head == "{" || head == "[" {
+                // A bare opener starts the body of the argument declared just before it
+                let container = match stack.last_mut() {
+                    Some(parent) => parent.inner.pop(),
+                    None => arguments.pop(),
+                };
+                let container = container.ok_or_else(|| {
+                    SchemaError::HelpParse(format!("unexpected argument body: {line}"))
+                })?;
+                stack.push(container);
+                continue;
+            }
+
+            // Wrapped description text belongs to the most recent argument
+            let previous = match stack.last_mut() {
+                Some(parent) if !parent.inner.is_empty() => parent.inner.last_mut(),
+                Some(parent) => Some(parent),
+                None => arguments.last_mut(),
+            };
+            if let Some(previous) = previous {
+                previous.description.push('\n');
+                previous.description.push_str(trimmed);
+            }
+            continue;
+        };
+
+        if stack.is_empty() {
+            continue;
+        }
+        let node = parse_inner_argument(head, annotation, description);
+        if head.ends_with('{') || head.ends_with('[') {
+            stack.push(node);
+        } else if let Some(parent) = stack.last_mut() {
+            parent.inner.push(node);
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(SchemaError::HelpParse("unterminated argument object or array".to_string()));
+    }
+
+    Ok(arguments)
+}
+
+/// Parses a nested argument line such as `"add_inputs": bool, (boolean, optional) ...`
+///
+/// Object fields are named by their key; array elements by their placeholder
+/// (e.g. `vout_index`), or left unnamed when they open an object or array.
+fn parse_inner_argument(head: &str, annotation: &str, description: &str) -> BtcArgument {
+    let (annotation, default) = split_default(annotation);
+    let mut parts = annotation.split(',').map(str::trim);
+    let type_ = schema_type(parts.next().unwrap_or_default());
+    // Array elements are listed without an optionality marker and are always present
+    let required = !parts.any(|part| part == "optional");
+
+    let key = head
+        .strip_prefix('"')
+        .and_then(|rest| rest.split_once('"'))
+        .filter(|(_, after)| after.trim_start().starts_with(':'))
+        .map(|(key, _)| key);
+    let name = match key {
+        Some(key) => key,
+        None => head.trim_end_matches(['{', '[']).trim().trim_matches('"'),
+    };
+
+    BtcArgument {
+        names: if name.is_empty() { Vec::new() } else { vec![name.to_string()] },
+        description: description.to_string(),
+        required,
+        type_,
+        default,
+        ..BtcArgument::default()
+    }
+}
+
+/// Separates a trailing `default=...` from a type annotation
+///
+/// The default comes last and may itself contain commas (`default=["a","b"]`).
+fn split_default(annotation: &str) -> (&str, Option<serde_json::Value>) {
+    match annotation.split_once("default=") {
+        Some((head, default)) => (head, BtcArgument::parse_default(default)),
+        None => (annotation, None),
+    }
+}
+
+/// Parses a numbered argument line such as `1. blockhash (string, required) The block hash`
+fn parse_argument_line(line: &str) -> Result<Option<BtcArgument>> {
+    let Some((number, rest)) = line.split_once(". ") else { return Ok(None) };
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let (name, annotation, description) = split_annotation(rest).ok_or_else(|| {
+        SchemaError::HelpParse(format!("missing type annotation in argument line: {line}"))
+    })?;
+    let (annotation, default) = split_default(annotation);
+    let mut parts = annotation.split(',').map(str::trim);
+    let type_ = schema_type(parts.next().unwrap_or_default());
+    let required = parts.any(|part| part == "required");
+
+    Ok(Some(BtcArgument {
+        names: name.split('|').map(str::to_string).collect(),
+        description: description.to_string(),
+        oneline_description: String::new(),
+        also_positional: false,
+        type_str: None,
+        required,
+        hidden: false,
+        type_,
+        default,
+        ..BtcArgument::default()
+    }))
+}
+
+fn parse_results(lines: &[&str]) -> Result<Vec<BtcResult>> {
+    let mut top_level: Vec<BtcResult> = Vec::new();
+    let mut stack: Vec<BtcResult> = Vec::new();
+
+    fn attach(node: BtcResult, stack: &mut [BtcResult], top_level: &mut Vec<BtcResult>) {
+        match stack.last_mut() {
+            Some(parent) => parent.inner.push(node),
+            None => top_level.push(node),
+        }
+    }
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let (head, annotation, description) = match split_annotation(trimmed) {
+            Some((head, annotation, description)) => (head, Some(annotation), description),
+            None => (trimmed, None, ""),
+        };
+        let head = head.trim_end_matches(',').trim();
+
+        if head.starts_with(['}', ']']) {
+            let node = stack
+                .pop()
+                .ok_or_else(|| SchemaError::HelpParse(format!("unbalanced result line: {line}")))?;
+            attach(node, &mut stack, &mut top_level);
+            continue;
+        }
+
+        if head.starts_with("...") {
+            let node = BtcResult {
+                type_: "elision".to_string(),
+                description: if description.is_empty() {
+                    head.trim_start_matches('.').trim().to_string()
+                } else {
+                    description.to_string()
+                },
+                ..BtcResult::default()
+            };
+            attach(node, &mut stack, &mut top_level);
+            continue;
+        }
+
+        let Some(annotation) = annotation else {
+            // Wrapped description text belongs to the most recent node
+            let previous = match stack.last_mut() {
+                Some(parent) if !parent.inner.is_empty() => parent.inner.last_mut(),
+                Some(parent) => Some(parent),
+                None => top_level.last_mut(),
+            };
+            if let Some(previous) = previous {
+                previous.description.push('\n');
+                previous.description.push_str(trimmed);
+            }
+            continue;
+        };
+
+        let mut parts = annotation.split(',').map(str::trim);
+        let type_ = schema_type(parts.next().unwrap_or_default());
+        let optional = parts.any(|part| part == "optional");
+        let key_name = head
+            .strip_prefix('"')
+            .and_then(|rest| rest.split_once('"'))
+            .filter(|(_, after)| after.trim_start().starts_with(':'))
+            .map(|(key, _)| key.to_string())
+            .unwrap_or_default();
+
+        let node = BtcResult {
+            type_,
+            optional,
+            description: description.to_string(),
+            key_name,
+            ..BtcResult::default()
+        };
+
+        let opens_container = head.ends_with('{') || head.ends_with('[');
+        if opens_container {
+            stack.push(node);
+        } else {
+            attach(node, &mut stack, &mut top_level);
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(SchemaError::HelpParse("unterminated result object or array".to_string()));
+    }
+
+    Ok(top_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::category::Category;
+
+    const GETBLOCK_HELP: &str = r#"getblock "blockhash" ( verbosity )
+
+If verbosity is 0, returns a string that is serialized, hex-encoded data for block 'hash'.
+If verbosity is 1, returns an Object with information about block <hash>.
+
+Arguments:
+1. blockhash    (string, required) The block hash
+2. verbosity    (numeric, optional, default=1) 0 for hex-encoded data, 1 for a JSON object
+                and 2 for JSON object with transaction data
+
+Result (for verbosity = 0):
+"hex"    (string) A string that is serialized, hex-encoded data for block 'hash'
+
+Result (for verbosity = 1):
+{                                 (json object)
+  "hash" : "hex",                 (string) the block hash (same as provided)
+  "confirmations" : n,            (numeric) The number of confirmations
+  "tx" : [                        (json array) The transaction ids
+    "hex",                        (string) The transaction id
+    ...
+  ],
+  "nextblockhash" : "hex"         (string, optional) The hash of the next block
+}
+
+Examples:
+> bitcoin-cli getblock "00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"
+"#;
+
+    #[test]
+    fn test_parse_help_overview() {
+        let overview = "== Blockchain ==\ngetbestblockhash\ngetblock \"blockhash\" ( verbosity )\n\n== Control ==\nhelp ( \"command\" )\n";
+        let entries = parse_help_overview(overview);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].category, "Blockchain");
+        assert_eq!(entries[1].name, "getblock");
+        assert_eq!(entries[1].synopsis, "getblock \"blockhash\" ( verbosity )");
+        assert_eq!(entries[2].category, "Control");
+        assert_eq!(entries[2].name, "help");
+    }
+
+    #[test]
+    fn test_parse_method_help_arguments() {
+        let method = parse_method_help(GETBLOCK_HELP).unwrap();
+        assert_eq!(method.name, "getblock");
+        assert!(method.description.starts_with("If verbosity is 0"));
+        assert_eq!(method.argument_names, vec!["blockhash", "verbosity"]);
+
+        assert_eq!(method.arguments[0].type_, "string");
+        assert!(method.arguments[0].required);
+        assert_eq!(method.arguments[1].type_, "number");
+        assert!(!method.arguments[1].required);
+        assert_eq!(method.arguments[0].default, None);
+        assert_eq!(method.arguments[1].default, Some(serde_json::json!(1)));
+        assert!(method.arguments[1].description.ends_with("transaction data"));
+        assert!(method.examples.starts_with("> bitcoin-cli getblock"));
+    }
+
+    #[test]
+    fn test_parse_method_help_nested_arguments() {
+        let help = r#"fundrawtransaction "hexstring" ( options )
+
+Arguments:
+1. hexstring                          (string, required) The hex string of the raw transaction
+2. options                            (json object, optional) Options
+     {
+       "add_inputs": bool,            (boolean, optional, default=true) Add inputs
+       "changeAddress": "str",        (string, optional, default=automatic) The change address
+       "subtractFeeFromOutputs": [    (json array, optional, default=[]) The integers.
+         vout_index,                  (numeric) The zero-based output index
+         ...
+       ],
+     }
+3. inputs                             (json array, required) The inputs
+     [
+       {                              (json object)
+         "txid": "hex",               (string, required) The transaction id
+         "vout": n,                   (numeric, required) The output number
+       },
+       ...
+     ]
+"#;
+        let method = parse_method_help(help).unwrap();
+        assert_eq!(method.argument_names, vec!["hexstring", "options", "inputs"]);
+
+        let options = &method.arguments[1];
+        let keys: Vec<&str> = options.inner.iter().map(|arg| arg.names[0].as_str()).collect();
+        assert_eq!(keys, vec!["add_inputs", "changeAddress", "subtractFeeFromOutputs"]);
+        assert_eq!(options.inner[0].default, Some(serde_json::json!(true)));
+        assert!(!options.inner[0].required);
+        assert_eq!(options.inner[1].default, None);
+        assert_eq!(options.inner[2].default, Some(serde_json::json!([])));
+        assert_eq!(options.inner[2].inner[0].names, vec!["vout_index"]);
+        assert!(options.inner[2].inner[0].required);
+
+        let inputs = &method.arguments[2];
+        assert_eq!(inputs.inner.len(), 1);
+        assert!(inputs.inner[0].names.is_empty());
+        assert_eq!(inputs.inner[0].type_, "object");
+        assert_eq!(inputs.inner[0].inner[1].names, vec!["vout"]);
+        assert_eq!(inputs.inner[0].inner[1].type_, "number");
+    }
+
+    #[test]
+    fn test_parse_method_help_results() {
+        let method = parse_method_help(GETBLOCK_HELP).unwrap();
+        assert_eq!(method.results.len(), 2);
+
+        let hex = &method.results[0];
+        assert_eq!(hex.type_, "string");
+        assert_eq!(hex.condition, "for verbosity = 0");
+
+        let object = &method.results[1];
+        assert_eq!(object.type_, "object");
+        assert_eq!(object.condition, "for verbosity = 1");
+        let keys: Vec<&str> = object.inner.iter().map(|r| r.key_name.as_str()).collect();
+        assert_eq!(keys, vec!["hash", "confirmations", "tx", "nextblockhash"]);
+        assert_eq!(object.inner[0].description, "the block hash (same as provided)");
+        assert_eq!(object.inner[2].type_, "array");
+        assert_eq!(object.inner[2].inner.len(), 2);
+        assert_eq!(object.inner[2].inner[1].type_, "elision");
+        assert!(object.inner[3].optional);
+    }
+
+    #[test]
+    fn test_parse_method_help_errors() {
+        assert!(parse_method_help("").is_err());
+
+        let unbalanced = "getfoo\n\nResult:\n{     (json object)\n  \"a\" : n,   (numeric) A\n";
+        assert!(matches!(parse_method_help(unbalanced), Err(SchemaError::HelpParse(_))));
+    }
+
+    #[test]
+    fn test_api_definition_from_help() {
+        let overview = "== Blockchain ==\ngetblock \"blockhash\" ( verbosity )\n";
+        let api_def = ApiDefinition::from_help(overview, |name| {
+            assert_eq!(name, "getblock");
+            Ok(GETBLOCK_HELP.to_string())
+        })
+        .unwrap();
+        assert_eq!(api_def.rpcs.len(), 1);
+        let method = api_def.get_method("getblock").unwrap();
+        assert_eq!(method.arguments.len(), 2);
+        assert_eq!(method.category, Some(Category::Blockchain));
+    }
+}
@@ -0,0 +1,170 @@
+//! Rust identifiers for schema names
+//!
+//! JSON keys and method names are not always valid or idiomatic Rust
+//! identifiers (`type`, `bip125-replaceable`, `scriptPubKey`, `24h`).
+//! `RustIdent` converts them and keeps the original name, so generated code
+//! can restore it with `#[serde(rename = "...")]`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Rust keywords, strict and reserved
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Returns whether a word is a Rust keyword
+pub fn is_keyword(word: &str) -> bool { KEYWORDS.contains(&word) }
+
+/// A Rust identifier derived from a schema name
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RustIdent {
+    ident: String,
+    original: String,
+}
+
+impl RustIdent {
+    /// Converts a name into a snake_case identifier, for fields, arguments and functions
+    ///
+    /// Words are split at non-alphanumeric characters and lowercase-to-uppercase
+    /// boundaries. Keywords become raw identifiers (`r#type`), except those
+    /// that cannot be raw (`self_`), and a leading digit gets a `_` prefix.
+    pub fn snake_case(name: &str) -> Self {
+        let ident = words(name).join("_");
+        let ident = match ident.chars().next() {
+            None => "_empty".to_string(),
+            Some(c) if c.is_ascii_digit() => format!("_{}", ident),
+            _ if matches!(ident.as_str(), "self" | "super" | "crate") => format!("{}_", ident),
+            _ if is_keyword(&ident) => format!("r#{}", ident),
+            _ => ident,
+        };
+        Self { ident, original: name.to_string() }
+    }
+
+    /// Converts a name into a PascalCase identifier, for types and enum variants
+    pub fn pascal_case(name: &str) -> Self {
+        let ident: String = words(name)
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect();
+        let ident = match ident.chars().next() {
+            None => "Empty".to_string(),
+            Some(c) if c.is_ascii_digit() => format!("_{}", ident),
+            _ if ident == "Self" => "Self_".to_string(),
+            _ => ident,
+        };
+        Self { ident, original: name.to_string() }
+    }
+
+    /// Returns the identifier as written in source, including any `r#` prefix
+    pub fn as_str(&self) -> &str { &self.ident }
+
+    /// Returns the name the identifier was derived from
+    pub fn original(&self) -> &str { &self.original }
+
+    /// Returns the name for `#[serde(rename = "...")]`, when serde would not
+    /// otherwise (de)serialize the identifier under its original name
+    pub fn serde_rename(&self) -> Option<&str> {
+        let unraw = self.ident.strip_prefix("r#").unwrap_or(&self.ident);
+        (unraw != self.original).then_some(self.original.as_str())
+    }
+
+    /// Appends `_2`, `_3`, ... until the identifier is not in `taken`, then
+    /// records it there
+    ///
+    /// Distinct names can sanitize to the same identifier (`fee-rate` and
+    /// `fee_rate`); this keeps the fields of one struct apart.
+    pub fn make_unique(mut self, taken: &mut BTreeSet<String>) -> Self {
+        if taken.contains(&self.ident) {
+            let base = self.ident.clone();
+            let suffix = (2..).find(|n| !taken.contains(&format!("{}_{}", base, n))).unwrap();
+            self.ident = format!("{}_{}", base, suffix);
+        }
+        taken.insert(self.ident.clone());
+        self
+    }
+}
+
+impl fmt::Display for RustIdent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.ident) }
+}
+
+/// Splits a name into lowercase words at non-alphanumeric characters and
+/// lowercase-to-uppercase boundaries
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        word.push(c.to_ascii_lowercase());
+    }
+    words.extend((!word.is_empty()).then_some(word));
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case() {
+        let cases = [
+            ("txid", "txid", None),
+            ("scriptPubKey", "script_pub_key", Some("scriptPubKey")),
+            ("bip125-replaceable", "bip125_replaceable", Some("bip125-replaceable")),
+            ("type", "r#type", None),
+            ("loop", "r#loop", None),
+            ("self", "self_", Some("self")),
+            ("24h", "_24h", Some("24h")),
+            ("", "_empty", Some("")),
+        ];
+        for (name, ident, rename) in cases {
+            let rust = RustIdent::snake_case(name);
+            assert_eq!(rust.as_str(), ident, "{}", name);
+            assert_eq!(rust.serde_rename(), rename, "{}", name);
+            assert_eq!(rust.original(), name);
+        }
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(RustIdent::pascal_case("getblock").as_str(), "Getblock");
+        assert_eq!(RustIdent::pascal_case("script_pub_key").as_str(), "ScriptPubKey");
+        assert_eq!(RustIdent::pascal_case("bip125-replaceable").to_string(), "Bip125Replaceable");
+        assert_eq!(RustIdent::pascal_case("self").as_str(), "Self_");
+        assert!(is_keyword("async"));
+        assert!(!is_keyword("txid"));
+    }
+
+    #[test]
+    fn test_make_unique() {
+        let mut taken = BTreeSet::new();
+        let first = RustIdent::snake_case("fee_rate").make_unique(&mut taken);
+        let second = RustIdent::snake_case("fee-rate").make_unique(&mut taken);
+        let third = RustIdent::snake_case("feeRate").make_unique(&mut taken);
+        assert_eq!(first.as_str(), "fee_rate");
+        assert_eq!(second.as_str(), "fee_rate_2");
+        assert_eq!(third.as_str(), "fee_rate_3");
+        assert_eq!(second.serde_rename(), Some("fee-rate"));
+        assert_eq!(first.serde_rename(), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Index status result types
+//!
+//! This module provides `IndexInfo`, returned by `getindexinfo`, which maps
+//! each enabled index to its sync status. Indexes not known to this crate,
+//! such as those added by newer nodes, are kept as `IndexName::Other`.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Name of an optional index, as `getindexinfo` reports it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IndexName {
+    /// Transaction index, enabled by `-txindex`
+    Txindex,
+    /// UTXO set statistics index, enabled by `-coinstatsindex`
+    Coinstatsindex,
+    /// Basic compact block filter index, enabled by `-blockfilterindex`
+    BasicBlockFilterIndex,
+    /// An index not known to this crate
+    Other(String),
+}
+
+impl IndexName {
+    /// Returns the name Bitcoin Core uses for the index
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Txindex => "txindex",
+            Self::Coinstatsindex => "coinstatsindex",
+            Self::BasicBlockFilterIndex => "basic block filter index",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for IndexName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<&str> for IndexName {
+    fn from(name: &str) -> Self {
+        match name {
+            "txindex" => Self::Txindex,
+            "coinstatsindex" => Self::Coinstatsindex,
+            "basic block filter index" => Self::BasicBlockFilterIndex,
+            name => Self::Other(name.to_string()),
+        }
+    }
+}
+
+impl FromStr for IndexName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Ok(Self::from(s)) }
+}
+
+impl Serialize for IndexName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for IndexName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from(name.as_str()))
+    }
+}
+
+/// Sync status of an index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexStatus {
+    /// Whether the index has caught up with the active chain
+    pub synced: bool,
+    /// Height up to which the index is built
+    pub best_block_height: u32,
+}
+
+/// Result of `getindexinfo`, from index name to status
+///
+/// Only enabled indexes are listed; with an index name argument, only that
+/// index is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct IndexInfo(pub BTreeMap<IndexName, IndexStatus>);
+
+impl IndexInfo {
+    /// Returns the status of an index, or `None` if it is not enabled
+    pub fn get(&self, name: &IndexName) -> Option<&IndexStatus> { self.0.get(name) }
+
+    /// Returns whether an index is enabled
+    pub fn is_enabled(&self, name: &IndexName) -> bool { self.0.contains_key(name) }
+
+    /// Returns whether an index is enabled and synced
+    pub fn is_synced(&self, name: &IndexName) -> bool {
+        self.get(name).is_some_and(|status| status.synced)
+    }
+
+    /// Returns the indexes and their statuses, by name
+    pub fn iter(&self) -> impl Iterator<Item = (&IndexName, &IndexStatus)> { self.0.iter() }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_index_info() {
+        let value = json!({
+            "txindex": { "synced": true, "best_block_height": 840_000 },
+            "basic block filter index": { "synced": false, "best_block_height": 700_000 },
+            "txospenderindex": { "synced": true, "best_block_height": 840_000 },
+        });
+        let info: IndexInfo = serde_json::from_value(value.clone()).unwrap();
+        assert!(info.is_synced(&IndexName::Txindex));
+        assert!(info.is_enabled(&IndexName::BasicBlockFilterIndex));
+        assert!(!info.is_synced(&IndexName::BasicBlockFilterIndex));
+        assert!(!info.is_enabled(&IndexName::Coinstatsindex));
+        assert!(info.is_synced(&"txospenderindex".parse().unwrap()));
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+
+        let empty: IndexInfo = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(empty.iter().count(), 0);
+    }
+}
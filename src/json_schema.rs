@@ -0,0 +1,415 @@
+//! JSON Schema generation
+//!
+//! Translates `BtcArgument` and `BtcResult` trees into draft 2020-12 JSON
+//! Schema documents, so node responses and request parameters can be checked
+//! with off-the-shelf JSON Schema validators.
+
+use serde_json::{json, Map, Value};
+
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcMethod, BtcResult};
+
+/// Dialect URI of the generated JSON Schema documents
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl BtcMethod {
+    /// Returns a JSON Schema document describing this method's result
+    ///
+    /// Conditional results are combined with `anyOf`.
+    pub fn result_json_schema(&self) -> Value { with_dialect(results_schema(&self.results)) }
+
+    /// Returns a JSON Schema document describing this method's parameters
+    ///
+    /// The schema accepts either a positional array or a named-parameter object.
+    pub fn params_json_schema(&self) -> Value {
+        let min_items = self.arguments.iter().rposition(|arg| arg.required).map_or(0, |i| i + 1);
+        let positional = json!({
+            "type": "array",
+            "prefixItems": self.arguments.iter().map(argument_schema).collect::<Vec<_>>(),
+            "items": false,
+            "minItems": min_items,
+        });
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for arg in &self.arguments {
+            let Some(name) = arg.names.first() else { continue };
+            properties.insert(name.clone(), argument_schema(arg));
+            if arg.required {
+                required.push(Value::String(name.clone()));
+            }
+        }
+        let named = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        });
+
+        with_dialect(json!({ "anyOf": [positional, named] }))
+    }
+}
+
+impl BtcResult {
+    /// Returns a JSON Schema document describing this result node
+    pub fn json_schema(&self) -> Value { with_dialect(result_schema(self)) }
+}
+
+fn with_dialect(mut schema: Value) -> Value {
+    if let Value::Object(map) = &mut schema {
+        map.insert("$schema".to_string(), Value::String(JSON_SCHEMA_DIALECT.to_string()));
+    }
+    schema
+}
+
+/// Returns the JSON Schema for a single argument
+pub(crate) fn argument_schema(arg: &BtcArgument) -> Value {
+    let mut schema = argument_type_schema(&arg.rpc_type(), arg);
+    add_description(&mut schema, &arg.description);
+    if let Some(default) = &arg.default {
+        schema["default"] = default.clone();
+    }
+    if let Some(minimum) = arg.minimum {
+        schema["minimum"] = json!(minimum);
+    }
+    if let Some(maximum) = arg.maximum {
+        schema["maximum"] = json!(maximum);
+    }
+    if !arg.allowed_values.is_empty() {
+        schema["enum"] = Value::Array(arg.allowed_values.clone());
+    }
+    schema
+}
+
+fn argument_type_schema(rpc_type: &RpcType, arg: &BtcArgument) -> Value {
+    match rpc_type {
+        RpcType::String => json!({ "type": "string" }),
+        RpcType::Hex => json!({ "type": "string", "pattern": "^[0-9a-fA-F]*$" }),
+        RpcType::Number => json!({ "type": "number" }),
+        RpcType::Amount => json!({ "type": ["number", "string"] }),
+        RpcType::Boolean => json!({ "type": "boolean" }),
+        RpcType::Object | RpcType::ObjectNamedParams => argument_object_schema(&arg.inner),
+        RpcType::ObjectUserKeys => match arg.inner.first() {
+            Some(value) =>
+                json!({ "type": "object", "additionalProperties": argument_schema(value) }),
+            None => json!({ "type": "object" }),
+        },
+        RpcType::Array => match arg.inner.as_slice() {
+            [] => json!({ "type": "array" }),
+            [item] => json!({ "type": "array", "items": argument_schema(item) }),
+            items => json!({
+                "type": "array",
+                "items": { "anyOf": items.iter().map(argument_schema).collect::<Vec<_>>() },
+            }),
+        },
+        RpcType::Range => json!({
+            "oneOf": [
+                { "type": "integer", "minimum": 0 },
+                {
+                    "type": "array",
+                    "prefixItems": [{ "type": "integer" }, { "type": "integer" }],
+                    "items": false
+                }
+            ]
+        }),
+        RpcType::OneOf(types) => json!({
+            "anyOf": types
+                .iter()
+                .map(|rpc_type| argument_type_schema(rpc_type, arg))
+                .collect::<Vec<_>>(),
+        }),
+        _ => json!({}),
+    }
+}
+
+fn argument_object_schema(inner: &[BtcArgument]) -> Value {
+    if inner.is_empty() {
+        return json!({ "type": "object" });
+    }
+
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in inner {
+        let Some(name) = field.names.first() else { continue };
+        properties.insert(name.clone(), argument_schema(field));
+        if field.required {
+            required.push(Value::String(name.clone()));
+        }
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Returns the JSON Schema for a list of alternative results
+///
+/// A single result maps directly to its schema; several results (one per
+/// condition) are combined with `anyOf`. No results maps to `null`.
+pub(crate) fn results_schema(results: &[BtcResult]) -> Value {
+    match results {
+        [] => json!({ "type": "null" }),
+        [result] => result_schema(result),
+        results => json!({ "anyOf": results.iter().map(result_schema).collect::<Vec<_>>() }),
+    }
+}
+
+/// Returns the JSON Schema for a single result node and its children
+pub(crate) fn result_schema(result: &BtcResult) -> Value {
+    if result.skip_type_check {
+        let mut schema = json!({});
+        add_description(&mut schema, &result.description);
+        return schema;
+    }
+
+    let mut schema = match result.rpc_type() {
+        RpcType::String => json!({ "type": "string" }),
+        RpcType::Hex => json!({ "type": "string", "pattern": "^[0-9a-fA-F]*$" }),
+        RpcType::Number | RpcType::Amount | RpcType::Time => json!({ "type": "number" }),
+        RpcType::Boolean => json!({ "type": "boolean" }),
+        RpcType::None => json!({ "type": "null" }),
+        RpcType::Object | RpcType::ObjectDynamic if result.is_map() => json!({
+            "type": "object",
+            "additionalProperties": item_schema(&result.inner),
+        }),
+        RpcType::Object => object_schema(&result.inner),
+        RpcType::Array => json!({ "type": "array", "items": item_schema(&result.inner) }),
+        RpcType::ArrayFixed => json!({
+            "type": "array",
+            "prefixItems": result
+                .inner
+                .iter()
+                .filter(|inner| !inner.is_elision())
+                .map(result_schema)
+                .collect::<Vec<_>>(),
+        }),
+        _ => json!({}),
+    };
+    add_description(&mut schema, &result.description);
+    schema
+}
+
+fn object_schema(inner: &[BtcResult]) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut open = false;
+
+    for field in inner {
+        if field.is_elision() || field.dynamic_key || field.key_name.is_empty() {
+            open = true;
+            continue;
+        }
+        properties.insert(field.key_name.clone(), result_schema(field));
+        if !field.optional {
+            required.push(Value::String(field.key_name.clone()));
+        }
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    if !open {
+        schema["additionalProperties"] = Value::Bool(false);
+    }
+    schema
+}
+
+fn item_schema(inner: &[BtcResult]) -> Value {
+    let items: Vec<Value> =
+        inner.iter().filter(|item| !item.is_elision()).map(result_schema).collect();
+    match items.len() {
+        0 => json!({}),
+        1 => items.into_iter().next().unwrap_or_default(),
+        _ => json!({ "anyOf": items }),
+    }
+}
+
+fn add_description(schema: &mut Value, description: &str) {
+    if !description.is_empty() {
+        schema["description"] = Value::String(description.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(name: &str, type_: &str, required: bool) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            required,
+            type_: type_.to_string(),
+            ..BtcArgument::default()
+        }
+    }
+
+    fn leaf(key_name: &str, type_: &str, optional: bool) -> BtcResult {
+        BtcResult {
+            type_: type_.to_string(),
+            optional,
+            key_name: key_name.to_string(),
+            ..BtcResult::default()
+        }
+    }
+
+    #[test]
+    fn test_result_schema_object() {
+        let result = BtcResult {
+            type_: "object".to_string(),
+            inner: vec![leaf("hash", "hex", false), leaf("nextblockhash", "hex", true)],
+            ..BtcResult::default()
+        };
+        let schema = result_schema(&result);
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["hash"]["type"], "string");
+        assert_eq!(schema["required"], json!(["hash"]));
+        assert_eq!(schema["additionalProperties"], false);
+    }
+
+    #[test]
+    fn test_result_schema_array_and_elision() {
+        let result = BtcResult {
+            type_: "array".to_string(),
+            inner: vec![leaf("", "string", false), leaf("", "elision", false)],
+            ..BtcResult::default()
+        };
+        assert_eq!(
+            result_schema(&result),
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+    }
+
+    #[test]
+    fn test_result_schema_dynamic_keys() {
+        let result = BtcResult {
+            type_: "object".to_string(),
+            inner: vec![BtcResult { dynamic_key: true, ..leaf("txid", "number", false) }],
+            ..BtcResult::default()
+        };
+        assert_eq!(
+            result_schema(&result),
+            json!({ "type": "object", "additionalProperties": { "type": "number" } })
+        );
+    }
+
+    #[test]
+    fn test_results_schema_alternatives() {
+        assert_eq!(results_schema(&[]), json!({ "type": "null" }));
+        let schema = results_schema(&[leaf("", "hex", false), leaf("", "object", false)]);
+        assert_eq!(schema["anyOf"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_argument_schema() {
+        let arg = BtcArgument {
+            names: vec!["verbosity".to_string()],
+            description: "Verbosity level".to_string(),
+            required: false,
+            type_: "number".to_string(),
+            default: Some(json!(1)),
+            minimum: Some(0.0),
+            maximum: Some(3.0),
+            ..BtcArgument::default()
+        };
+        assert_eq!(
+            argument_schema(&arg),
+            json!({
+                "type": "number",
+                "description": "Verbosity level",
+                "default": 1,
+                "minimum": 0.0,
+                "maximum": 3.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_argument_schema_union() {
+        let arg = BtcArgument {
+            names: vec!["hash_or_height".to_string()],
+            type_: "hex or number".to_string(),
+            ..BtcArgument::default()
+        };
+        assert_eq!(
+            argument_schema(&arg),
+            json!({
+                "anyOf": [
+                    { "type": "string", "pattern": "^[0-9a-fA-F]*$" },
+                    { "type": "number" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_argument_schema_nested() {
+        let field = |name: &str, type_: &str, required: bool| BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            ..BtcArgument::default()
+        };
+        let inputs = BtcArgument {
+            names: vec!["inputs".to_string()],
+            type_: "array".to_string(),
+            inner: vec![BtcArgument {
+                type_: "object".to_string(),
+                inner: vec![field("txid", "hex", true), field("sequence", "number", false)],
+                ..BtcArgument::default()
+            }],
+            ..BtcArgument::default()
+        };
+        assert_eq!(
+            argument_schema(&inputs),
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "txid": { "type": "string", "pattern": "^[0-9a-fA-F]*$" },
+                        "sequence": { "type": "number" }
+                    },
+                    "required": ["txid"]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_params_json_schema() {
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["blockhash".to_string(), "verbosity".to_string()],
+            arguments: vec![
+                argument("blockhash", "hex", true),
+                argument("verbosity", "number", false),
+            ],
+            ..BtcMethod::default()
+        };
+
+        let schema = method.params_json_schema();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        let positional = &schema["anyOf"][0];
+        assert_eq!(positional["minItems"], 1);
+        assert_eq!(positional["prefixItems"][1]["type"], "number");
+        let named = &schema["anyOf"][1];
+        assert_eq!(named["required"], json!(["blockhash"]));
+        assert_eq!(named["properties"]["blockhash"]["pattern"], "^[0-9a-fA-F]*$");
+    }
+
+    #[test]
+    fn test_result_json_schema() {
+        let method = BtcMethod {
+            name: "getblockcount".to_string(),
+            results: vec![leaf("", "number", false)],
+            ..BtcMethod::default()
+        };
+        assert_eq!(
+            method.result_json_schema(),
+            json!({ "$schema": JSON_SCHEMA_DIALECT, "type": "number" })
+        );
+    }
+}
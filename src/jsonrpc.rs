@@ -0,0 +1,427 @@
+//! JSON-RPC envelope types
+//!
+//! Bitcoin Core answers every request with an object holding `result`,
+//! `error` and the request's `id`. `JsonRpcResponse` models that envelope for
+//! any result type, and `RpcError` the error object, so clients do not each
+//! reimplement the edge cases: a null result is a valid answer for methods
+//! without a return value, and an error takes precedence over any result.
+//! `RequestId` is the `id` both carry, which the node echoes unchanged.
+//!
+//! Bitcoin Core 28 added strict JSON-RPC 2.0 handling next to the legacy
+//! 1.0-style protocol; `JsonRpcVersion` selects which one an envelope follows.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
+
+/// JSON-RPC protocol version of a request or response
+///
+/// The version changes how envelopes are serialized:
+///
+/// - `V1` envelopes carry no `jsonrpc` member, and responses hold both
+///   `result` and `error`, one of them null.
+/// - `V2` envelopes carry `"jsonrpc": "2.0"`, responses hold either
+///   `result` or `error`, and requests without an id are notifications,
+///   which get no response.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JsonRpcVersion {
+    /// Legacy 1.0-style protocol, understood by every Bitcoin Core version
+    #[default]
+    V1,
+    /// JSON-RPC 2.0, as handled by Bitcoin Core 28 and later
+    V2,
+}
+
+impl JsonRpcVersion {
+    /// Returns the value of the `jsonrpc` member, if the version has one
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::V1 => None,
+            Self::V2 => Some("2.0"),
+        }
+    }
+}
+
+impl Serialize for JsonRpcVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str().unwrap_or("1.0"))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonRpcVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            None | Some("1.0") | Some("1.1") => Ok(Self::V1),
+            Some("2.0") => Ok(Self::V2),
+            Some(other) =>
+                Err(serde::de::Error::custom(format!("unsupported JSON-RPC version {:?}", other))),
+        }
+    }
+}
+
+/// Id of a JSON-RPC request, echoed in its response
+///
+/// Bitcoin Core accepts any JSON value as id; numbers, strings and null
+/// cover the ids clients send in practice. Requests without an id have a
+/// null id.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    /// Numeric id
+    Number(u64),
+    /// String id
+    String(String),
+    /// No id
+    #[default]
+    Null,
+}
+
+impl RequestId {
+    /// Returns whether the id is null
+    pub fn is_null(&self) -> bool { matches!(self, Self::Null) }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(id) => write!(f, "{}", id),
+            Self::String(id) => f.write_str(id),
+            Self::Null => f.write_str("null"),
+        }
+    }
+}
+
+impl From<u64> for RequestId {
+    fn from(id: u64) -> Self { Self::Number(id) }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self { Self::String(id) }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self { Self::String(id.to_string()) }
+}
+
+/// Generates increasing numeric request ids
+///
+/// Ids are unique per generator, including across threads, so responses of
+/// a batch can be matched to their requests.
+#[derive(Debug, Default)]
+pub struct RequestIdGenerator {
+    next: AtomicU64,
+}
+
+impl RequestIdGenerator {
+    /// Creates a generator whose first id is 0
+    pub fn new() -> Self { Self::default() }
+
+    /// Creates a generator whose first id is `first`
+    pub fn starting_at(first: u64) -> Self { Self { next: AtomicU64::new(first) } }
+
+    /// Returns the next id
+    pub fn next_id(&self) -> RequestId {
+        RequestId::Number(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Error object of a JSON-RPC response
+#[derive(Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[error("RPC error {code}: {message}")]
+pub struct RpcError {
+    /// Error code, e.g. `-8` for an invalid parameter
+    pub code: i32,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error information, if the server sent any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Creates an error without additional data
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), data: None }
+    }
+}
+
+/// Request of a JSON-RPC call
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JsonRpcRequest {
+    /// Protocol version, read from the `jsonrpc` member
+    #[serde(default, rename = "jsonrpc")]
+    pub version: JsonRpcVersion,
+    /// Name of the called method
+    pub method: String,
+    /// Positional (array) or named (object) parameters
+    #[serde(default)]
+    pub params: Value,
+    /// Id of the request, echoed in the response
+    #[serde(default)]
+    pub id: RequestId,
+}
+
+impl JsonRpcRequest {
+    /// Creates a 1.0-style request with a null id
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self { version: JsonRpcVersion::V1, method: method.into(), params, id: RequestId::Null }
+    }
+
+    /// Sets the id
+    pub fn with_id(mut self, id: impl Into<RequestId>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Sets the protocol version
+    pub fn with_version(mut self, version: JsonRpcVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Returns whether the request is a JSON-RPC 2.0 notification, which
+    /// gets no response
+    pub fn is_notification(&self) -> bool {
+        self.version == JsonRpcVersion::V2 && self.id.is_null()
+    }
+}
+
+impl Serialize for JsonRpcRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(version) = self.version.as_str() {
+            map.serialize_entry("jsonrpc", version)?;
+        }
+        if !self.is_notification() {
+            map.serialize_entry("id", &self.id)?;
+        }
+        map.serialize_entry("method", &self.method)?;
+        if !self.params.is_null() {
+            map.serialize_entry("params", &self.params)?;
+        }
+        map.end()
+    }
+}
+
+/// Response to a JSON-RPC request
+///
+/// `result` is `None` both when the server sent `null` and when it left the
+/// key out, as JSON-RPC 2.0 servers do on errors. Use `into_result` to get
+/// the outcome of the call.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JsonRpcResponse<T = Value> {
+    /// Protocol version, read from the `jsonrpc` member
+    #[serde(default, rename = "jsonrpc")]
+    pub version: JsonRpcVersion,
+    /// Result of the call
+    #[serde(default)]
+    pub result: Option<T>,
+    /// Error of the call
+    #[serde(default)]
+    pub error: Option<RpcError>,
+    /// Id of the request this responds to
+    #[serde(default)]
+    pub id: RequestId,
+}
+
+impl<T: Serialize> Serialize for JsonRpcResponse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(version) = self.version.as_str() {
+            map.serialize_entry("jsonrpc", version)?;
+        }
+        match (self.version, &self.error) {
+            (JsonRpcVersion::V1, error) => {
+                map.serialize_entry("result", &self.result)?;
+                map.serialize_entry("error", error)?;
+            }
+            (JsonRpcVersion::V2, Some(error)) => map.serialize_entry("error", error)?,
+            (JsonRpcVersion::V2, None) => map.serialize_entry("result", &self.result)?,
+        }
+        map.serialize_entry("id", &self.id)?;
+        map.end()
+    }
+}
+
+/// Error returned by `JsonRpcResponse::into_result`
+#[derive(Error, Debug)]
+pub enum ResponseError {
+    /// The server returned an error
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+
+    /// The response has neither an error nor a result, and the result type
+    /// does not accept null
+    #[error("Missing result: {0}")]
+    MissingResult(serde_json::Error),
+}
+
+impl<T> JsonRpcResponse<T> {
+    /// Creates a successful response
+    pub fn success(id: RequestId, result: T) -> Self {
+        Self { version: JsonRpcVersion::V1, result: Some(result), error: None, id }
+    }
+
+    /// Creates an error response
+    pub fn failure(id: RequestId, error: RpcError) -> Self {
+        Self { version: JsonRpcVersion::V1, result: None, error: Some(error), id }
+    }
+
+    /// Sets the protocol version
+    pub fn with_version(mut self, version: JsonRpcVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Returns whether the response carries an error
+    pub fn is_error(&self) -> bool { self.error.is_some() }
+}
+
+impl<T: DeserializeOwned> JsonRpcResponse<T> {
+    /// Returns the result of the call, or the error the server returned
+    ///
+    /// An error takes precedence over a result. Without either, the result
+    /// is read from `null`, which succeeds for result types that accept it,
+    /// such as `()`, `Option<_>` and `serde_json::Value`.
+    pub fn into_result(self) -> std::result::Result<T, ResponseError> {
+        if let Some(error) = self.error {
+            return Err(ResponseError::Rpc(error));
+        }
+        match self.result {
+            Some(result) => Ok(result),
+            None => serde_json::from_value(Value::Null).map_err(ResponseError::MissingResult),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_into_result() {
+        let response: JsonRpcResponse<u64> =
+            serde_json::from_value(json!({ "result": 800000, "error": null, "id": 1 })).unwrap();
+        assert!(!response.is_error());
+        assert_eq!(response.into_result().unwrap(), 800000);
+
+        let response: JsonRpcResponse<u64> = serde_json::from_value(json!({
+            "result": null,
+            "error": { "code": -8, "message": "Block height out of range" },
+            "id": "1",
+        }))
+        .unwrap();
+        let ResponseError::Rpc(error) = response.into_result().unwrap_err() else { panic!() };
+        assert_eq!(error, RpcError::new(-8, "Block height out of range"));
+        assert_eq!(error.to_string(), "RPC error -8: Block height out of range");
+
+        // JSON-RPC 2.0 error responses leave out `result`
+        let response: JsonRpcResponse<u64> = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32601, "message": "Method not found", "data": "x" },
+            "id": 2,
+        }))
+        .unwrap();
+        assert_eq!(response.error.as_ref().unwrap().data, Some(json!("x")));
+        assert!(matches!(response.into_result(), Err(ResponseError::Rpc(_))));
+    }
+
+    #[test]
+    fn test_into_result_null() {
+        let null = json!({ "result": null, "error": null, "id": 1 });
+        let response: JsonRpcResponse<()> = serde_json::from_value(null.clone()).unwrap();
+        response.into_result().unwrap();
+        let response: JsonRpcResponse<Option<String>> =
+            serde_json::from_value(null.clone()).unwrap();
+        assert_eq!(response.into_result().unwrap(), None);
+        let response: JsonRpcResponse = serde_json::from_value(null.clone()).unwrap();
+        assert_eq!(response.into_result().unwrap(), Value::Null);
+        let response: JsonRpcResponse<u64> = serde_json::from_value(null).unwrap();
+        assert!(matches!(response.into_result(), Err(ResponseError::MissingResult(_))));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let response = JsonRpcResponse::success(RequestId::Number(1), "00ff".to_string());
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "result": "00ff", "error": null, "id": 1 })
+        );
+        let response =
+            JsonRpcResponse::<()>::failure(RequestId::from("a"), RpcError::new(-28, "Loading"));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "result": null, "error": { "code": -28, "message": "Loading" }, "id": "a" })
+        );
+    }
+
+    #[test]
+    fn test_request_id() {
+        let ids: Vec<RequestId> = serde_json::from_value(json!([7, "curltest", null])).unwrap();
+        assert_eq!(ids, vec![RequestId::from(7), RequestId::from("curltest"), RequestId::Null]);
+        assert_eq!(serde_json::to_value(&ids).unwrap(), json!([7, "curltest", null]));
+        assert!(serde_json::from_value::<RequestId>(json!(-1)).is_err());
+        assert_eq!(RequestId::from(7).to_string(), "7");
+        assert!(RequestId::default().is_null());
+
+        // A missing id is null
+        let response: JsonRpcResponse = serde_json::from_value(json!({ "result": 1 })).unwrap();
+        assert_eq!(response.id, RequestId::Null);
+
+        let generator = RequestIdGenerator::starting_at(5);
+        assert_eq!(generator.next_id(), RequestId::Number(5));
+        assert_eq!(generator.next_id(), RequestId::Number(6));
+        assert!(RequestIdGenerator::new().next_id() < RequestId::from(1));
+    }
+
+    #[test]
+    fn test_versions() {
+        let request = JsonRpcRequest::new("getblockcount", json!([])).with_id(1);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "id": 1, "method": "getblockcount", "params": [] })
+        );
+        let request = request.with_version(JsonRpcVersion::V2);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "getblockcount", "params": [] })
+        );
+        let round_trip: JsonRpcRequest =
+            serde_json::from_value(serde_json::to_value(&request).unwrap()).unwrap();
+        assert_eq!(round_trip, request);
+
+        let notification =
+            JsonRpcRequest::new("ping", Value::Null).with_version(JsonRpcVersion::V2);
+        assert!(notification.is_notification());
+        assert_eq!(
+            serde_json::to_value(&notification).unwrap(),
+            json!({ "jsonrpc": "2.0", "method": "ping" })
+        );
+        assert!(!JsonRpcRequest::new("ping", Value::Null).is_notification());
+
+        let response = JsonRpcResponse::<()>::failure(RequestId::from(1), RpcError::new(-1, "x"))
+            .with_version(JsonRpcVersion::V2);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "jsonrpc": "2.0", "error": { "code": -1, "message": "x" }, "id": 1 })
+        );
+        let response = JsonRpcResponse::<Option<u64>>::success(RequestId::from(1), None)
+            .with_version(JsonRpcVersion::V2);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "jsonrpc": "2.0", "result": null, "id": 1 })
+        );
+
+        let response: JsonRpcResponse =
+            serde_json::from_value(json!({ "jsonrpc": "2.0", "result": 1, "id": 1 })).unwrap();
+        assert_eq!(response.version, JsonRpcVersion::V2);
+        assert!(serde_json::from_value::<JsonRpcResponse>(json!({ "jsonrpc": "3.0" })).is_err());
+    }
+}
@@ -11,9 +11,180 @@
 //! - `BtcArgument` - Method argument specification
 //! - `BtcResult` - Method result specification
 //! - `ApiDefinition` - Complete API definition container
+//! - `ApiDefinitionSet` - API definitions keyed by Bitcoin Core version
+//! - `ApiDiff` - Differences between two API definitions
+//! - `CoreVersion` - Bitcoin Core release version
+//! - `Category` - Method category as listed by `help`
+//! - `MethodName` - Typed name of an RPC method
+//! - `RpcType` - Typed view of argument and result type names
+//! - `JsonRpcRequest`, `JsonRpcResponse` - JSON-RPC envelopes, with `RpcError`
+//! - `RpcErrorCode` - Typed Bitcoin Core RPC error code
+//! - `RequestBuilder` - Builds requests checked against an `ApiDefinition`
+//! - `DecodedValue` - Response tree typed by the method's result schema
+//!
+//! ## Cargo Features
+//! - `binary` - Compact CBOR encoding through `ApiDefinition::to_binary` and
+//!   `ApiDefinition::from_binary`
+//! - `codegen` - Rust source generation for result types through
+//!   `BtcMethod::result_types` (or `ApiDefinitionSet::result_types`, gated by
+//!   version feature), params structs through `BtcMethod::params_type` and
+//!   client traits through `ApiDefinition::client_trait`
+//! - `psbt` - Conversion of `PsbtString` to and from `bitcoin::Psbt`
 
+pub mod address_info;
+pub mod address_or_descriptor;
+pub mod address_type;
+pub mod amount;
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod block_stats;
+pub mod blockchain;
+pub mod builder;
+pub mod bump_fee;
+pub mod call_plan;
+pub mod category;
+pub mod chain_name;
+pub mod cli;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod decode;
+pub mod decoded_psbt;
+pub mod decoded_tx;
+pub mod definition_set;
+pub mod deployment;
+pub mod descriptor;
+pub mod diff;
+pub mod error_code;
+pub mod examples;
+pub mod fee_estimate;
+pub mod fee_rate;
+pub mod fingerprint;
+pub mod fund;
 pub mod hash_or_height;
+pub mod help_parser;
+pub mod ident;
+pub mod index;
+pub mod json_schema;
+pub mod jsonrpc;
+pub mod lint;
+pub mod mempool;
+pub mod method_name;
+pub mod mining;
+pub mod network;
+pub mod openrpc;
+pub mod outputs;
+pub mod psbt;
+pub mod raw_tx;
+pub mod request;
+pub mod result_path;
+pub mod result_variant;
+pub mod rpc_type;
+pub mod rustdoc;
+pub mod scan;
+pub mod search;
+pub mod txid_or_raw_tx;
+pub mod type_mapping;
 pub mod types;
+pub mod typescript;
+pub mod utxo;
+pub mod validate;
+pub mod verbosity;
+pub mod version;
+pub mod wallet;
+pub mod wallet_tx;
+pub mod warnings;
+pub mod zmq;
 
-pub use hash_or_height::HashOrHeight;
+pub use address_info::{EmbeddedAddressInfo, GetAddressInfoResult, ValidateAddressResult};
+pub use address_or_descriptor::{
+    AddressOrDescriptor, DerivationRange, DescriptorRange, DescriptorWithRange, InvalidRangeError,
+};
+pub use address_type::{AddressType, ParseAddressTypeError};
+pub use amount::BtcDenominated;
+pub use block_stats::{GetBlockStatsResult, ParseStatsFieldError, StatsField};
+pub use blockchain::{
+    ChainTip, ChainTipStatus, GetBlockHeaderResponse, GetBlockHeaderResult, GetBlockchainInfoResult,
+};
+pub use builder::{BtcArgumentBuilder, BtcMethodBuilder, BtcResultBuilder};
+pub use bump_fee::{BumpFeeOptions, BumpFeeResult};
+pub use call_plan::CallPlan;
+pub use category::Category;
+pub use chain_name::{ChainName, UnknownChainError};
+pub use decode::DecodedValue;
+pub use decoded_psbt::{
+    DecodedPsbt, DecodedPsbtInput, DecodedPsbtOutput, DecodedScript, PsbtBip32Deriv,
+    PsbtGlobalXpub, PsbtProprietary, PsbtTaprootBip32Deriv, PsbtTaprootLeaf, PsbtTaprootScript,
+    PsbtTaprootScriptPathSig, PsbtWitnessUtxo,
+};
+pub use decoded_tx::{
+    DecodedPrevout, DecodedScriptPubKey, DecodedScriptSig, DecodedTransaction, DecodedVin,
+    DecodedVout,
+};
+pub use definition_set::ApiDefinitionSet;
+pub use deployment::{
+    Bip9Info, Bip9Statistics, Bip9Status, Deployment, DeploymentType, GetDeploymentInfoResult,
+};
+pub use descriptor::{
+    add_descriptor_checksum, descriptor_checksum, verify_descriptor_checksum,
+    DescriptorChecksumError, GetDescriptorInfoResult,
+};
+pub use diff::{ApiDiff, ArgumentChange, MethodDiff, ResultChange};
+pub use error_code::{ErrorCategory, RpcErrorCode};
+pub use examples::{Example, ExampleKind};
+pub use fee_estimate::EstimateSmartFeeResult;
+pub use fee_rate::{FeeRateParam, FeeRateUnit};
+pub use fingerprint::Fingerprint;
+pub use fund::{
+    EstimateMode, FundRawTransactionOptions, FundRawTransactionResult, FundingInput, InputWeight,
+    SolvingData, WalletCreateFundedPsbtOptions, WalletCreateFundedPsbtResult,
+};
+pub use hash_or_height::{HashOrHeight, ParseHashOrHeightError, TipOffset};
+pub use ident::RustIdent;
+pub use index::{IndexInfo, IndexName, IndexStatus};
+pub use jsonrpc::{
+    JsonRpcRequest, JsonRpcResponse, JsonRpcVersion, RequestId, RequestIdGenerator, ResponseError,
+    RpcError,
+};
+pub use lint::{LintIssue, LintKind, LintLocation, LintSeverity};
+pub use mempool::{
+    GetMempoolInfoResult, MempoolAcceptFees, MempoolAcceptResult, MempoolEntry, MempoolEntryFees,
+    TestMempoolAcceptResult,
+};
+pub use method_name::MethodName;
+pub use mining::{GetMiningInfoResult, NextBlockInfo};
+pub use network::{
+    ConnectionType, GetNetworkInfoResult, LocalAddress, NetworkReachability, NetworkType, PeerInfo,
+    TransportProtocol,
+};
+pub use outputs::{Recipients, TxOutput};
+pub use psbt::{ParsePsbtStringError, PsbtString};
+pub use raw_tx::{ParseRawTxHexError, RawTxHex};
+pub use request::RequestBuilder;
+pub use result_path::DeepResults;
+pub use result_variant::{ResultCondition, ResultVariant};
+pub use rpc_type::RpcType;
+pub use scan::{ScanAction, ScanObject, ScanTxOutSetResult, ScanUnspent};
+pub use search::{MatchLocation, SearchMatch};
+pub use txid_or_raw_tx::{ParseTxidOrRawTxError, TxidOrRawTx};
+pub use type_mapping::{TypeMapper, TypeMapping};
 pub use types::{ApiDefinition, BtcArgument, BtcMethod, BtcResult, Result, SchemaError};
+pub use utxo::{
+    GetTxOutResponse, GetTxOutResult, GetTxOutSetInfoResult, ParseTxOutSetHashTypeError,
+    TxOutSetBlockInfo, TxOutSetHashType, TxOutSetUnspendables,
+};
+pub use validate::{
+    validate_response, JsonType, SchemaNode, ValidationError, ValidationErrorKind, ValidationMode,
+    ValidationOptions,
+};
+pub use verbosity::{Verbosity, VerbosityError};
+pub use version::{CoreVersion, ParseVersionError};
+pub use wallet::{
+    CreateWalletOptions, CreateWalletOptionsBuilder, CreateWalletResult, GetWalletInfoResult,
+    LastProcessedBlock, WalletScanning,
+};
+pub use wallet_tx::{
+    Bip125Replaceable, GetTransactionDetail, GetTransactionResult, ListSinceBlockOptions,
+    ListSinceBlockResult, ListTransactionsEntry, ListUnspentEntry, TransactionCategory,
+};
+pub use warnings::Warnings;
+pub use zmq::{ParseZmqNotificationTypeError, ZmqNotification, ZmqNotificationType};
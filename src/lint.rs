@@ -0,0 +1,405 @@
+//! Schema linting
+//!
+//! Structural checks over an `ApiDefinition` that catch malformed entries
+//! (empty argument names, objects without fields, duplicate keys, ...) before
+//! they reach code generators. `BtcMethod::check_consistency` runs the same
+//! checks on a single method.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::rpc_type::RpcType;
+use crate::types::{ApiDefinition, BtcMethod, BtcResult, Result, SchemaError};
+
+/// Severity of a lint finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintSeverity {
+    /// Suspicious but usable entry
+    Warning,
+    /// Entry that consumers cannot reliably process
+    Error,
+}
+
+/// Where in a method definition a lint finding was raised
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintLocation {
+    /// The method itself
+    Method,
+    /// An argument, by position
+    Argument(usize),
+    /// A result node, by `/`-separated path (top-level results by index,
+    /// nested results by key name or index)
+    Result(String),
+}
+
+impl fmt::Display for LintLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Method => write!(f, "method"),
+            Self::Argument(index) => write!(f, "argument {}", index),
+            Self::Result(path) => write!(f, "result {}", path),
+        }
+    }
+}
+
+/// The kind of problem a lint finding reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// The map key under which a method is stored differs from its name
+    MethodKeyMismatch {
+        /// Key in `ApiDefinition::rpcs`
+        key: String,
+    },
+    /// An argument has no names
+    EmptyArgumentNames,
+    /// Two arguments share the same name
+    DuplicateArgumentName(String),
+    /// `argument_names` does not match the names of `arguments`
+    ArgumentNamesMismatch {
+        /// Contents of `argument_names`
+        argument_names: Vec<String>,
+        /// Primary names of `arguments`
+        arguments: Vec<String>,
+    },
+    /// A required argument follows an optional one, so it cannot be passed
+    /// positionally without the optional one
+    ///
+    /// Reported as a warning: Core has such signatures (`walletcreatefundedpsbt`
+    /// takes an optional `inputs` before the required `outputs`).
+    RequiredAfterOptional(String),
+    /// An `object` result has no inner fields
+    ObjectWithoutInner,
+    /// A field of an `object` result has an empty key name
+    EmptyKeyName,
+    /// Two fields of an object result share the same key name
+    DuplicateKeyName(String),
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MethodKeyMismatch { key } => write!(f, "stored under mismatched key {:?}", key),
+            Self::EmptyArgumentNames => write!(f, "argument has no names"),
+            Self::DuplicateArgumentName(name) => write!(f, "duplicate argument name {:?}", name),
+            Self::ArgumentNamesMismatch { argument_names, arguments } => write!(
+                f,
+                "argument_names {:?} does not match arguments {:?}",
+                argument_names, arguments
+            ),
+            Self::RequiredAfterOptional(name) =>
+                write!(f, "required argument {:?} follows an optional argument", name),
+            Self::ObjectWithoutInner => write!(f, "object result has no inner fields"),
+            Self::EmptyKeyName => write!(f, "object field has no key name"),
+            Self::DuplicateKeyName(name) => write!(f, "duplicate result key {:?}", name),
+        }
+    }
+}
+
+/// A single lint finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// Name of the method the finding belongs to
+    pub method: String,
+    /// Location within the method
+    pub location: LintLocation,
+    /// Problem that was found
+    pub kind: LintKind,
+    /// Severity of the problem
+    pub severity: LintSeverity,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Warning => "warning",
+            LintSeverity::Error => "error",
+        };
+        write!(f, "{}: {} ({}): {}", severity, self.method, self.location, self.kind)
+    }
+}
+
+impl ApiDefinition {
+    /// Runs all structural checks and returns the findings, ordered by method
+    pub fn lint(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        for (key, method) in &self.rpcs {
+            if *key != method.name {
+                issues.push(LintIssue {
+                    method: method.name.clone(),
+                    location: LintLocation::Method,
+                    kind: LintKind::MethodKeyMismatch { key: key.clone() },
+                    severity: LintSeverity::Error,
+                });
+            }
+            lint_method(method, &mut issues);
+        }
+        issues
+    }
+}
+
+impl BtcMethod {
+    /// Checks the method definition for internal consistency
+    ///
+    /// Runs the same checks as `ApiDefinition::lint`: `argument_names` must
+    /// match `arguments`, required arguments must not follow optional ones,
+    /// and object result fields need key names, among others.
+    pub fn check_consistency(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        lint_method(self, &mut issues);
+        issues
+    }
+}
+
+/// Fails with the first error-level finding for a method, if any
+pub(crate) fn ensure_valid_method(method: &BtcMethod) -> Result<()> {
+    let mut issues = Vec::new();
+    lint_method(method, &mut issues);
+    match issues.into_iter().find(|issue| issue.severity == LintSeverity::Error) {
+        Some(issue) => Err(SchemaError::InvalidDefinition(issue.to_string())),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn lint_method(method: &BtcMethod, issues: &mut Vec<LintIssue>) {
+    let mut push = |location, kind, severity| {
+        issues.push(LintIssue { method: method.name.clone(), location, kind, severity })
+    };
+
+    let mut seen = BTreeSet::new();
+    for (index, arg) in method.arguments.iter().enumerate() {
+        if arg.names.is_empty() {
+            push(LintLocation::Argument(index), LintKind::EmptyArgumentNames, LintSeverity::Error);
+        }
+        for name in &arg.names {
+            if !seen.insert(name.as_str()) {
+                push(
+                    LintLocation::Argument(index),
+                    LintKind::DuplicateArgumentName(name.clone()),
+                    LintSeverity::Error,
+                );
+            }
+        }
+    }
+
+    if let Some(first_optional) = method.arguments.iter().position(|arg| !arg.required) {
+        for (index, arg) in method.arguments.iter().enumerate().skip(first_optional) {
+            if arg.required {
+                push(
+                    LintLocation::Argument(index),
+                    LintKind::RequiredAfterOptional(arg.names.first().cloned().unwrap_or_default()),
+                    LintSeverity::Warning,
+                );
+            }
+        }
+    }
+
+    let arguments: Vec<String> =
+        method.arguments.iter().filter_map(|arg| arg.names.first().cloned()).collect();
+    if method.argument_names != arguments {
+        push(
+            LintLocation::Method,
+            LintKind::ArgumentNamesMismatch {
+                argument_names: method.argument_names.clone(),
+                arguments,
+            },
+            LintSeverity::Error,
+        );
+    }
+
+    for (path, result) in method.iter_results_deep() {
+        lint_result(&path, result, &mut push);
+    }
+}
+
+fn lint_result<F>(path: &str, result: &BtcResult, push: &mut F)
+where
+    F: FnMut(LintLocation, LintKind, LintSeverity),
+{
+    if result.rpc_type() == RpcType::Object && result.inner.is_empty() && !result.skip_type_check {
+        push(
+            LintLocation::Result(path.to_string()),
+            LintKind::ObjectWithoutInner,
+            LintSeverity::Warning,
+        );
+    }
+
+    if result.rpc_type() == RpcType::Object {
+        let mut seen = BTreeSet::new();
+        for (index, field) in result.inner.iter().enumerate() {
+            if field.key_name.is_empty() && !field.is_elision() {
+                let field_path =
+                    if path.is_empty() { index.to_string() } else { format!("{}/{}", path, index) };
+                push(
+                    LintLocation::Result(field_path),
+                    LintKind::EmptyKeyName,
+                    LintSeverity::Warning,
+                );
+            }
+            if !field.key_name.is_empty() && !seen.insert(field.key_name.as_str()) {
+                push(
+                    LintLocation::Result(path.to_string()),
+                    LintKind::DuplicateKeyName(field.key_name.clone()),
+                    LintSeverity::Error,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BtcArgument;
+
+    fn argument(names: &[&str]) -> BtcArgument {
+        BtcArgument {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            required: true,
+            type_: "string".to_string(),
+            ..BtcArgument::default()
+        }
+    }
+
+    fn field(key_name: &str, type_: &str) -> BtcResult {
+        BtcResult {
+            type_: type_.to_string(),
+            key_name: key_name.to_string(),
+            ..BtcResult::default()
+        }
+    }
+
+    fn api(method: BtcMethod) -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(method.name.clone(), method);
+        api_def
+    }
+
+    #[test]
+    fn test_lint_clean_definition() {
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["blockhash".to_string()],
+            arguments: vec![argument(&["blockhash"])],
+            results: vec![BtcResult {
+                type_: "object".to_string(),
+                inner: vec![field("hash", "hex")],
+                ..BtcResult::default()
+            }],
+            ..BtcMethod::default()
+        };
+        assert!(api(method).lint().is_empty());
+    }
+
+    #[test]
+    fn test_lint_argument_problems() {
+        let method = BtcMethod {
+            name: "send".to_string(),
+            argument_names: vec!["outputs".to_string()],
+            arguments: vec![argument(&[]), argument(&["outputs"]), argument(&["outputs"])],
+            ..BtcMethod::default()
+        };
+        let kinds: Vec<LintKind> = api(method).lint().into_iter().map(|issue| issue.kind).collect();
+        assert_eq!(kinds.len(), 3);
+        assert_eq!(kinds[0], LintKind::EmptyArgumentNames);
+        assert_eq!(kinds[1], LintKind::DuplicateArgumentName("outputs".to_string()));
+        assert!(matches!(kinds[2], LintKind::ArgumentNamesMismatch { .. }));
+    }
+
+    #[test]
+    fn test_lint_result_problems() {
+        let method = BtcMethod {
+            name: "getinfo".to_string(),
+            results: vec![BtcResult {
+                type_: "object".to_string(),
+                inner: vec![
+                    field("fees", "object"),
+                    field("size", "number"),
+                    field("size", "number"),
+                ],
+                ..BtcResult::default()
+            }],
+            ..BtcMethod::default()
+        };
+        let issues = api(method).lint();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].kind, LintKind::DuplicateKeyName("size".to_string()));
+        assert_eq!(issues[0].location, LintLocation::Result("0".to_string()));
+        assert_eq!(issues[1].kind, LintKind::ObjectWithoutInner);
+        assert_eq!(issues[1].severity, LintSeverity::Warning);
+        assert_eq!(
+            issues[1].to_string(),
+            "warning: getinfo (result 0/fees): object result has no inner fields"
+        );
+    }
+
+    #[test]
+    fn test_lint_method_key_mismatch() {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblockcount".to_string(),
+            BtcMethod { name: "getblockhash".to_string(), ..BtcMethod::default() },
+        );
+        let issues = api_def.lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].kind,
+            LintKind::MethodKeyMismatch { key: "getblockcount".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_check_consistency() {
+        let mut method = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["blockhash".to_string(), "verbosity".to_string()],
+            arguments: vec![
+                BtcArgument { required: false, ..argument(&["verbosity"]) },
+                argument(&["blockhash"]),
+            ],
+            results: vec![BtcResult {
+                type_: "object".to_string(),
+                inner: vec![field("hash", "hex"), field("", "number"), field("", "elision")],
+                ..BtcResult::default()
+            }],
+            ..BtcMethod::default()
+        };
+        let kinds: Vec<LintKind> =
+            method.check_consistency().into_iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LintKind::RequiredAfterOptional("blockhash".to_string()),
+                LintKind::ArgumentNamesMismatch {
+                    argument_names: vec!["blockhash".to_string(), "verbosity".to_string()],
+                    arguments: vec!["verbosity".to_string(), "blockhash".to_string()],
+                },
+                LintKind::EmptyKeyName,
+            ]
+        );
+        assert_eq!(method.check_consistency()[2].location, LintLocation::Result("0/1".to_string()));
+
+        method.arguments.reverse();
+        method.arguments[1].required = false;
+        method.results[0].inner[1].key_name = "height".to_string();
+        assert!(method.check_consistency().is_empty());
+    }
+
+    #[test]
+    fn test_required_after_optional_is_accepted() {
+        // Core's walletcreatefundedpsbt takes an optional `inputs` before the
+        // required `outputs`
+        let method = BtcMethod::builder("walletcreatefundedpsbt")
+            .argument(BtcArgument::builder("inputs", "array").build().unwrap())
+            .argument(BtcArgument::builder("outputs", "array").required(true).build().unwrap())
+            .argument(BtcArgument::builder("locktime", "number").build().unwrap())
+            .build()
+            .unwrap();
+        let issues = method.check_consistency();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, LintKind::RequiredAfterOptional("outputs".to_string()));
+        assert_eq!(issues[0].severity, LintSeverity::Warning);
+
+        let mut api_def = ApiDefinition::new();
+        assert!(api_def.insert_method(method).unwrap().is_none());
+    }
+}
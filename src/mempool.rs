@@ -0,0 +1,232 @@
+//! Mempool result types
+//!
+//! This module provides `MempoolEntry`, returned by `getmempoolentry` and by
+//! the verbose forms of `getrawmempool`, `getmempoolancestors` and
+//! `getmempooldescendants`, `GetMempoolInfoResult`, returned by
+//! `getmempoolinfo`, and `MempoolAcceptResult`, returned for each
+//! transaction by `testmempoolaccept`.
+
+use bitcoin::{Amount, Txid, Wtxid};
+use serde::{Deserialize, Serialize};
+
+/// Mempool transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    /// Virtual size
+    pub vsize: u64,
+    /// Weight
+    pub weight: u64,
+    /// Time the transaction entered the mempool, in seconds since epoch
+    pub time: u64,
+    /// Chain height when the transaction entered the mempool
+    pub height: u32,
+    /// Number of in-mempool descendants, including this one
+    pub descendantcount: u64,
+    /// Virtual size of the in-mempool descendants, including this one
+    pub descendantsize: u64,
+    /// Number of in-mempool ancestors, including this one
+    pub ancestorcount: u64,
+    /// Virtual size of the in-mempool ancestors, including this one
+    pub ancestorsize: u64,
+    /// Witness transaction id
+    pub wtxid: Wtxid,
+    /// Fees of the transaction and its package
+    pub fees: MempoolEntryFees,
+    /// Unconfirmed transactions this one spends from
+    pub depends: Vec<Txid>,
+    /// Unconfirmed transactions spending from this one
+    pub spentby: Vec<Txid>,
+    /// Whether the transaction can be replaced under BIP125 rules
+    #[serde(rename = "bip125-replaceable")]
+    pub bip125_replaceable: bool,
+    /// Whether the wallet has not yet seen the transaction relayed
+    pub unbroadcast: bool,
+}
+
+/// Fees of a mempool transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolEntryFees {
+    /// Fee of the transaction
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub base: Amount,
+    /// Fee including prioritisation
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub modified: Amount,
+    /// Modified fees of the in-mempool ancestors, including this one
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub ancestor: Amount,
+    /// Modified fees of the in-mempool descendants, including this one
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub descendant: Amount,
+}
+
+/// Result of `getmempoolinfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetMempoolInfoResult {
+    /// Whether the mempool has been loaded from disk
+    pub loaded: bool,
+    /// Number of transactions
+    pub size: u64,
+    /// Sum of the virtual sizes of the transactions
+    pub bytes: u64,
+    /// Memory usage, in bytes
+    pub usage: u64,
+    /// Sum of the fees of the transactions
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub total_fee: Amount,
+    /// Maximum memory usage, in bytes
+    pub maxmempool: u64,
+    /// Minimum fee rate for entering the mempool, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub mempoolminfee: Amount,
+    /// Minimum fee rate for relaying transactions, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub minrelaytxfee: Amount,
+    /// Minimum fee rate increment for replacement, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub incrementalrelayfee: Amount,
+    /// Number of transactions the node has not yet seen relayed
+    pub unbroadcastcount: u64,
+    /// Whether any transaction can be replaced, without BIP125 signalling
+    pub fullrbf: bool,
+    /// Whether bare multisig outputs are relayed (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permitbaremultisig: Option<bool>,
+    /// Maximum size of relayed data carrier outputs (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxdatacarriersize: Option<u64>,
+}
+
+/// Result of `testmempoolaccept`, in the order of the submitted
+/// transactions
+pub type TestMempoolAcceptResult = Vec<MempoolAcceptResult>;
+
+/// Mempool acceptance of a transaction in `testmempoolaccept`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolAcceptResult {
+    /// Transaction id
+    pub txid: Txid,
+    /// Witness transaction id
+    pub wtxid: Wtxid,
+    /// Why the package was rejected, for packages failing as a whole
+    #[serde(rename = "package-error", default, skip_serializing_if = "Option::is_none")]
+    pub package_error: Option<String>,
+    /// Whether the transaction would be accepted, absent if the package was
+    /// not fully validated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<bool>,
+    /// Virtual size, if accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vsize: Option<u64>,
+    /// Fees, if accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees: Option<MempoolAcceptFees>,
+    /// Why the transaction was rejected
+    #[serde(rename = "reject-reason", default, skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
+    /// Details of the rejection (Bitcoin Core 29+)
+    #[serde(rename = "reject-details", default, skip_serializing_if = "Option::is_none")]
+    pub reject_details: Option<String>,
+}
+
+impl MempoolAcceptResult {
+    /// Returns whether the transaction would be accepted
+    pub fn is_allowed(&self) -> bool { self.allowed == Some(true) }
+}
+
+/// Fees of a transaction accepted by `testmempoolaccept`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolAcceptFees {
+    /// Fee of the transaction
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub base: Amount,
+    /// Fee rate per kvB used for the feerate checks, which may be that of a
+    /// package
+    #[serde(rename = "effective-feerate", with = "bitcoin::amount::serde::as_btc")]
+    pub effective_feerate: Amount,
+    /// Transactions whose fees and sizes make up `effective_feerate`
+    #[serde(rename = "effective-includes")]
+    pub effective_includes: Vec<Wtxid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_mempool_entry() {
+        let value = json!({
+            "vsize": 141,
+            "weight": 561,
+            "time": 1_700_000_000,
+            "height": 840_000,
+            "descendantcount": 1,
+            "descendantsize": 141,
+            "ancestorcount": 2,
+            "ancestorsize": 282,
+            "wtxid": TXID,
+            "fees": { "base": 0.00000141, "modified": 0.00000141, "ancestor": 0.00000282, "descendant": 0.00000141 },
+            "depends": [TXID],
+            "spentby": [],
+            "bip125-replaceable": true,
+            "unbroadcast": false,
+        });
+        let entry: MempoolEntry = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(entry.fees.ancestor, Amount::from_sat(282));
+        assert_eq!(entry.depends, vec![TXID.parse::<Txid>().unwrap()]);
+        assert!(entry.bip125_replaceable);
+        assert_eq!(serde_json::to_value(&entry).unwrap(), value);
+    }
+
+    #[test]
+    fn test_get_mempool_info() {
+        let value = json!({
+            "loaded": true,
+            "size": 52_000,
+            "bytes": 30_000_000,
+            "usage": 160_000_000,
+            "total_fee": 1.25,
+            "maxmempool": 300_000_000,
+            "mempoolminfee": 0.00001,
+            "minrelaytxfee": 0.00001,
+            "incrementalrelayfee": 0.00001,
+            "unbroadcastcount": 0,
+            "fullrbf": true,
+        });
+        let info: GetMempoolInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(info.total_fee, Amount::from_sat(125_000_000));
+        assert_eq!(info.mempoolminfee, Amount::from_sat(1_000));
+        assert_eq!(info.permitbaremultisig, None);
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+    }
+
+    #[test]
+    fn test_mempool_accept() {
+        let value = json!([
+            {
+                "txid": TXID,
+                "wtxid": TXID,
+                "allowed": true,
+                "vsize": 141,
+                "fees": { "base": 0.00000141, "effective-feerate": 0.00001, "effective-includes": [TXID] },
+            },
+            {
+                "txid": TXID,
+                "wtxid": TXID,
+                "allowed": false,
+                "reject-reason": "min relay fee not met",
+                "reject-details": "min relay fee not met, 0 < 141",
+            },
+        ]);
+        let results: TestMempoolAcceptResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(results[0].is_allowed());
+        assert_eq!(results[0].fees.as_ref().unwrap().effective_feerate, Amount::from_sat(1_000));
+        assert!(!results[1].is_allowed());
+        assert_eq!(results[1].reject_reason.as_deref(), Some("min relay fee not met"));
+        assert_eq!(serde_json::to_value(&results).unwrap(), value);
+    }
+}
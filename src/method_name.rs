@@ -0,0 +1,336 @@
+//! Typed RPC method names
+//!
+//! `MethodName` lists the methods of Bitcoin Core's JSON-RPC interface, so
+//! request builders can name methods without stringly-typed typos. Names not
+//! in the list, such as methods added by newer releases, are kept in
+//! `MethodName::Unknown`.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::category::Category;
+use crate::types::BtcMethod;
+
+macro_rules! method_names {
+    ($($category:ident => { $($variant:ident => $name:literal,)* })*) => {
+        /// Name of a Bitcoin Core RPC method
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum MethodName {
+            $($(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*)*
+            /// A method not known to this crate
+            Unknown(String),
+        }
+
+        impl MethodName {
+            /// All known methods, grouped by category in `help` order
+            pub const ALL: &'static [MethodName] = &[$($(MethodName::$variant,)*)*];
+
+            /// Returns the method name as sent in requests
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($(Self::$variant => $name,)*)*
+                    Self::Unknown(name) => name,
+                }
+            }
+
+            /// Returns the category the method is listed under in `help`, if known
+            pub fn category(&self) -> Option<Category> {
+                match self {
+                    $($(Self::$variant => Some(Category::$category),)*)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl From<&str> for MethodName {
+            fn from(name: &str) -> Self {
+                match name {
+                    $($($name => Self::$variant,)*)*
+                    name => Self::Unknown(name.to_string()),
+                }
+            }
+        }
+    };
+}
+
+method_names! {
+    Blockchain => {
+        DumpTxOutSet => "dumptxoutset",
+        GetBestBlockHash => "getbestblockhash",
+        GetBlock => "getblock",
+        GetBlockchainInfo => "getblockchaininfo",
+        GetBlockCount => "getblockcount",
+        GetBlockFilter => "getblockfilter",
+        GetBlockFromPeer => "getblockfrompeer",
+        GetBlockHash => "getblockhash",
+        GetBlockHeader => "getblockheader",
+        GetBlockStats => "getblockstats",
+        GetChainStates => "getchainstates",
+        GetChainTips => "getchaintips",
+        GetChainTxStats => "getchaintxstats",
+        GetDeploymentInfo => "getdeploymentinfo",
+        GetDescriptorActivity => "getdescriptoractivity",
+        GetDifficulty => "getdifficulty",
+        GetMempoolAncestors => "getmempoolancestors",
+        GetMempoolDescendants => "getmempooldescendants",
+        GetMempoolEntry => "getmempoolentry",
+        GetMempoolInfo => "getmempoolinfo",
+        GetRawMempool => "getrawmempool",
+        GetTxOut => "gettxout",
+        GetTxOutProof => "gettxoutproof",
+        GetTxOutSetInfo => "gettxoutsetinfo",
+        GetTxSpendingPrevout => "gettxspendingprevout",
+        ImportMempool => "importmempool",
+        LoadTxOutSet => "loadtxoutset",
+        PreciousBlock => "preciousblock",
+        PruneBlockchain => "pruneblockchain",
+        SaveMempool => "savemempool",
+        ScanBlocks => "scanblocks",
+        ScanTxOutSet => "scantxoutset",
+        VerifyChain => "verifychain",
+        VerifyTxOutProof => "verifytxoutproof",
+        WaitForBlock => "waitforblock",
+        WaitForBlockHeight => "waitforblockheight",
+        WaitForNewBlock => "waitfornewblock",
+    }
+    Control => {
+        GetMemoryInfo => "getmemoryinfo",
+        GetRpcInfo => "getrpcinfo",
+        Help => "help",
+        Logging => "logging",
+        Stop => "stop",
+        Uptime => "uptime",
+    }
+    Generating => {
+        GenerateBlock => "generateblock",
+        GenerateToAddress => "generatetoaddress",
+        GenerateToDescriptor => "generatetodescriptor",
+    }
+    Mining => {
+        GetBlockTemplate => "getblocktemplate",
+        GetMiningInfo => "getmininginfo",
+        GetNetworkHashPs => "getnetworkhashps",
+        GetPrioritisedTransactions => "getprioritisedtransactions",
+        PrioritiseTransaction => "prioritisetransaction",
+        SubmitBlock => "submitblock",
+        SubmitHeader => "submitheader",
+    }
+    Network => {
+        AddNode => "addnode",
+        ClearBanned => "clearbanned",
+        DisconnectNode => "disconnectnode",
+        GetAddedNodeInfo => "getaddednodeinfo",
+        GetAddrmanInfo => "getaddrmaninfo",
+        GetConnectionCount => "getconnectioncount",
+        GetNetTotals => "getnettotals",
+        GetNetworkInfo => "getnetworkinfo",
+        GetNodeAddresses => "getnodeaddresses",
+        GetPeerInfo => "getpeerinfo",
+        ListBanned => "listbanned",
+        Ping => "ping",
+        SetBan => "setban",
+        SetNetworkActive => "setnetworkactive",
+    }
+    Rawtransactions => {
+        AnalyzePsbt => "analyzepsbt",
+        CombinePsbt => "combinepsbt",
+        CombineRawTransaction => "combinerawtransaction",
+        ConvertToPsbt => "converttopsbt",
+        CreatePsbt => "createpsbt",
+        CreateRawTransaction => "createrawtransaction",
+        DecodePsbt => "decodepsbt",
+        DecodeRawTransaction => "decoderawtransaction",
+        DecodeScript => "decodescript",
+        DescriptorProcessPsbt => "descriptorprocesspsbt",
+        FinalizePsbt => "finalizepsbt",
+        FundRawTransaction => "fundrawtransaction",
+        GetRawTransaction => "getrawtransaction",
+        JoinPsbts => "joinpsbts",
+        SendRawTransaction => "sendrawtransaction",
+        SignRawTransactionWithKey => "signrawtransactionwithkey",
+        SubmitPackage => "submitpackage",
+        TestMempoolAccept => "testmempoolaccept",
+        UtxoUpdatePsbt => "utxoupdatepsbt",
+    }
+    Signer => {
+        EnumerateSigners => "enumeratesigners",
+    }
+    Util => {
+        CreateMultisig => "createmultisig",
+        DeriveAddresses => "deriveaddresses",
+        EstimateSmartFee => "estimatesmartfee",
+        GetDescriptorInfo => "getdescriptorinfo",
+        GetIndexInfo => "getindexinfo",
+        SignMessageWithPrivKey => "signmessagewithprivkey",
+        ValidateAddress => "validateaddress",
+        VerifyMessage => "verifymessage",
+    }
+    Wallet => {
+        AbandonTransaction => "abandontransaction",
+        AbortRescan => "abortrescan",
+        AddMultisigAddress => "addmultisigaddress",
+        BackupWallet => "backupwallet",
+        BumpFee => "bumpfee",
+        CreateWallet => "createwallet",
+        CreateWalletDescriptor => "createwalletdescriptor",
+        DumpPrivKey => "dumpprivkey",
+        DumpWallet => "dumpwallet",
+        EncryptWallet => "encryptwallet",
+        GetAddressesByLabel => "getaddressesbylabel",
+        GetAddressInfo => "getaddressinfo",
+        GetBalance => "getbalance",
+        GetBalances => "getbalances",
+        GetHdKeys => "gethdkeys",
+        GetNewAddress => "getnewaddress",
+        GetRawChangeAddress => "getrawchangeaddress",
+        GetReceivedByAddress => "getreceivedbyaddress",
+        GetReceivedByLabel => "getreceivedbylabel",
+        GetTransaction => "gettransaction",
+        GetUnconfirmedBalance => "getunconfirmedbalance",
+        GetWalletInfo => "getwalletinfo",
+        ImportAddress => "importaddress",
+        ImportDescriptors => "importdescriptors",
+        ImportMulti => "importmulti",
+        ImportPrivKey => "importprivkey",
+        ImportPrunedFunds => "importprunedfunds",
+        ImportPubKey => "importpubkey",
+        ImportWallet => "importwallet",
+        KeypoolRefill => "keypoolrefill",
+        ListAddressGroupings => "listaddressgroupings",
+        ListDescriptors => "listdescriptors",
+        ListLabels => "listlabels",
+        ListLockUnspent => "listlockunspent",
+        ListReceivedByAddress => "listreceivedbyaddress",
+        ListReceivedByLabel => "listreceivedbylabel",
+        ListSinceBlock => "listsinceblock",
+        ListTransactions => "listtransactions",
+        ListUnspent => "listunspent",
+        ListWalletDir => "listwalletdir",
+        ListWallets => "listwallets",
+        LoadWallet => "loadwallet",
+        LockUnspent => "lockunspent",
+        MigrateWallet => "migratewallet",
+        NewKeypool => "newkeypool",
+        PsbtBumpFee => "psbtbumpfee",
+        RemovePrunedFunds => "removeprunedfunds",
+        RescanBlockchain => "rescanblockchain",
+        RestoreWallet => "restorewallet",
+        Send => "send",
+        SendAll => "sendall",
+        SendMany => "sendmany",
+        SendToAddress => "sendtoaddress",
+        SetHdSeed => "sethdseed",
+        SetLabel => "setlabel",
+        SetTxFee => "settxfee",
+        SetWalletFlag => "setwalletflag",
+        SignMessage => "signmessage",
+        SignRawTransactionWithWallet => "signrawtransactionwithwallet",
+        SimulateRawTransaction => "simulaterawtransaction",
+        UnloadWallet => "unloadwallet",
+        UpgradeWallet => "upgradewallet",
+        WalletCreateFundedPsbt => "walletcreatefundedpsbt",
+        WalletDisplayAddress => "walletdisplayaddress",
+        WalletLock => "walletlock",
+        WalletPassphrase => "walletpassphrase",
+        WalletPassphraseChange => "walletpassphrasechange",
+        WalletProcessPsbt => "walletprocesspsbt",
+    }
+    Zmq => {
+        GetZmqNotifications => "getzmqnotifications",
+    }
+    Hidden => {
+        AddConnection => "addconnection",
+        AddPeerAddress => "addpeeraddress",
+        Echo => "echo",
+        EchoIpc => "echoipc",
+        EchoJson => "echojson",
+        EstimateRawFee => "estimaterawfee",
+        Generate => "generate",
+        GetOrphanTxs => "getorphantxs",
+        GetRawAddrman => "getrawaddrman",
+        InvalidateBlock => "invalidateblock",
+        MockScheduler => "mockscheduler",
+        ReconsiderBlock => "reconsiderblock",
+        SendMsgToPeer => "sendmsgtopeer",
+        SetMockTime => "setmocktime",
+        SyncWithValidationInterfaceQueue => "syncwithvalidationinterfacequeue",
+    }
+}
+
+impl MethodName {
+    /// Returns whether the method is one of the known methods
+    pub fn is_known(&self) -> bool { !matches!(self, Self::Unknown(_)) }
+}
+
+impl fmt::Display for MethodName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for MethodName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Ok(Self::from(s)) }
+}
+
+impl Serialize for MethodName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MethodName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from(name.as_str()))
+    }
+}
+
+impl BtcMethod {
+    /// Returns the typed name of the method
+    pub fn method_name(&self) -> MethodName { MethodName::from(self.name.as_str()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_method_name_round_trip() {
+        assert_eq!("getblock".parse::<MethodName>().unwrap(), MethodName::GetBlock);
+        assert_eq!(MethodName::GetBlock.to_string(), "getblock");
+        assert_eq!(MethodName::GetBlock.category(), Some(Category::Blockchain));
+        assert_eq!(MethodName::SetMockTime.category(), Some(Category::Hidden));
+
+        let unknown = MethodName::from("getblok");
+        assert_eq!(unknown, MethodName::Unknown("getblok".to_string()));
+        assert!(!unknown.is_known());
+        assert_eq!(unknown.as_str(), "getblok");
+        assert_eq!(unknown.category(), None);
+
+        for name in MethodName::ALL {
+            assert!(name.is_known());
+            assert_eq!(&MethodName::from(name.as_str()), name);
+        }
+        let distinct: BTreeSet<&str> = MethodName::ALL.iter().map(MethodName::as_str).collect();
+        assert_eq!(distinct.len(), MethodName::ALL.len());
+    }
+
+    #[test]
+    fn test_method_name_serde() {
+        let json = serde_json::to_string(&MethodName::SendToAddress).unwrap();
+        assert_eq!(json, "\"sendtoaddress\"");
+        let name: MethodName = serde_json::from_str("\"newmethod\"").unwrap();
+        assert_eq!(name, MethodName::Unknown("newmethod".to_string()));
+
+        let method = BtcMethod { name: "getblockcount".to_string(), ..BtcMethod::default() };
+        assert_eq!(method.method_name(), MethodName::GetBlockCount);
+    }
+}
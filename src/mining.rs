@@ -0,0 +1,105 @@
+//! Mining result types
+//!
+//! This module provides `GetMiningInfoResult`, returned by `getmininginfo`.
+
+use bitcoin::pow::{CompactTarget, Target};
+use serde::{Deserialize, Serialize};
+
+use crate::blockchain::compact_target_hex;
+use crate::chain_name::ChainName;
+use crate::warnings::Warnings;
+
+/// Result of `getmininginfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetMiningInfoResult {
+    /// Height of the active chain
+    pub blocks: u32,
+    /// Weight of the last block template, if one was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currentblockweight: Option<u64>,
+    /// Number of transactions in the last block template, if one was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currentblocktx: Option<u64>,
+    /// Compact target of the tip (Bitcoin Core 29+)
+    #[serde(default, with = "compact_target_hex::opt", skip_serializing_if = "Option::is_none")]
+    pub bits: Option<CompactTarget>,
+    /// Difficulty of the tip
+    pub difficulty: f64,
+    /// Target of the tip (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+    /// Estimated network hash rate, in hashes per second
+    pub networkhashps: f64,
+    /// Number of transactions in the mempool
+    pub pooledtx: u64,
+    /// Network name
+    pub chain: ChainName,
+    /// Block signing challenge, as hex, on signet (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signet_challenge: Option<String>,
+    /// Values for the next block (Bitcoin Core 29+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<NextBlockInfo>,
+    /// Node warnings
+    pub warnings: Warnings,
+}
+
+/// Values for the block following the tip
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NextBlockInfo {
+    /// Height of the next block
+    pub height: u32,
+    /// Compact target of the next block
+    #[serde(with = "compact_target_hex")]
+    pub bits: CompactTarget,
+    /// Difficulty of the next block
+    pub difficulty: f64,
+    /// Target of the next block
+    pub target: Target,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_get_mining_info() {
+        let value = json!({
+            "blocks": 840_000,
+            "bits": "17034219",
+            "difficulty": 86388558925171.02,
+            "target": "0000000000000000000342190000000000000000000000000000000000000000",
+            "networkhashps": 6.2e20,
+            "pooledtx": 52_000,
+            "chain": "main",
+            "next": {
+                "height": 840_001,
+                "bits": "17034219",
+                "difficulty": 86388558925171.02,
+                "target": "0000000000000000000342190000000000000000000000000000000000000000",
+            },
+            "warnings": [],
+        });
+        let info: GetMiningInfoResult = serde_json::from_value(value.clone()).unwrap();
+        let next = info.next.as_ref().unwrap();
+        assert_eq!(next.target, Target::from_compact(next.bits));
+        assert_eq!(info.currentblockweight, None);
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+
+        let old = json!({
+            "blocks": 840_000,
+            "currentblockweight": 3_996_000,
+            "currentblocktx": 3_000,
+            "difficulty": 86388558925171.02,
+            "networkhashps": 6.2e20,
+            "pooledtx": 52_000,
+            "chain": "main",
+            "warnings": "",
+        });
+        let info: GetMiningInfoResult = serde_json::from_value(old).unwrap();
+        assert_eq!(info.bits, None);
+        assert!(info.warnings.is_empty());
+    }
+}
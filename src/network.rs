@@ -0,0 +1,348 @@
+//! Network result types
+//!
+//! This module provides `GetNetworkInfoResult`, returned by `getnetworkinfo`,
+//! `PeerInfo`, an entry of `getpeerinfo`, and `NetworkType`, the kind of
+//! network an address belongs to.
+
+use std::collections::BTreeMap;
+
+use bitcoin::p2p::ServiceFlags;
+use bitcoin::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::warnings::Warnings;
+
+/// Serde functions for `ServiceFlags` as the 16 hex digits of `services`
+/// fields
+pub(crate) mod service_flags_hex {
+    use bitcoin::p2p::ServiceFlags;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        services: &ServiceFlags,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:016x}", services.to_u64()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<ServiceFlags, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        u64::from_str_radix(&hex, 16)
+            .map(ServiceFlags::from)
+            .map_err(|_| de::Error::custom(format!("invalid service flags: {:?}", hex)))
+    }
+}
+
+/// Kind of network an address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkType {
+    /// IPv4
+    Ipv4,
+    /// IPv6
+    Ipv6,
+    /// Tor onion service
+    Onion,
+    /// I2P
+    I2p,
+    /// CJDNS
+    Cjdns,
+    /// Local or otherwise unroutable address, for peers only
+    NotPubliclyRoutable,
+    /// Internal address, for peers only
+    Internal,
+}
+
+/// Result of `getnetworkinfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetNetworkInfoResult {
+    /// Node version, e.g. 290000 for 29.0.0
+    pub version: u32,
+    /// User agent
+    pub subversion: String,
+    /// P2P protocol version
+    pub protocolversion: u32,
+    /// Services offered to the network
+    #[serde(with = "service_flags_hex")]
+    pub localservices: ServiceFlags,
+    /// Names of the offered services
+    pub localservicesnames: Vec<String>,
+    /// Whether transactions are relayed to peers
+    pub localrelay: bool,
+    /// Time offset to the peers, in seconds
+    pub timeoffset: i64,
+    /// Whether P2P networking is enabled
+    pub networkactive: bool,
+    /// Number of connections
+    pub connections: u32,
+    /// Number of inbound connections
+    pub connections_in: u32,
+    /// Number of outbound connections
+    pub connections_out: u32,
+    /// Reachability of each network
+    pub networks: Vec<NetworkReachability>,
+    /// Minimum fee rate for relaying transactions, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub relayfee: Amount,
+    /// Minimum fee rate increment for replacement, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub incrementalfee: Amount,
+    /// Addresses the node is reachable at
+    pub localaddresses: Vec<LocalAddress>,
+    /// Node warnings
+    pub warnings: Warnings,
+}
+
+impl GetNetworkInfoResult {
+    /// Returns whether a network is reachable
+    pub fn is_reachable(&self, network: NetworkType) -> bool {
+        self.networks.iter().any(|net| net.name == network && net.reachable)
+    }
+}
+
+/// Reachability of a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkReachability {
+    /// Network
+    pub name: NetworkType,
+    /// Whether only this network was allowed with `-onlynet`
+    pub limited: bool,
+    /// Whether the network is reachable
+    pub reachable: bool,
+    /// Proxy used for the network, empty without one
+    pub proxy: String,
+    /// Whether stream isolation is used for the proxy
+    pub proxy_randomize_credentials: bool,
+}
+
+/// Address the node is reachable at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalAddress {
+    /// Network address
+    pub address: String,
+    /// Port
+    pub port: u16,
+    /// Relative score
+    pub score: u32,
+}
+
+/// Kind of a peer connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectionType {
+    /// Connection initiated by the peer
+    Inbound,
+    /// Automatic outbound connection relaying transactions, blocks and
+    /// addresses
+    OutboundFullRelay,
+    /// Automatic outbound connection relaying only blocks
+    BlockRelayOnly,
+    /// Connection added with `-addnode`, `-connect` or `addnode`
+    Manual,
+    /// Short-lived connection to fetch addresses
+    AddrFetch,
+    /// Short-lived connection to test that an address is reachable
+    Feeler,
+}
+
+/// P2P transport protocol of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportProtocol {
+    /// Inbound connection whose protocol is not yet known
+    Detecting,
+    /// Unencrypted transport
+    V1,
+    /// BIP324 encrypted transport
+    V2,
+}
+
+/// Entry of `getpeerinfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerInfo {
+    /// Peer index
+    pub id: u64,
+    /// Address and port of the peer
+    pub addr: String,
+    /// Local address and port of the connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addrbind: Option<String>,
+    /// Local address as reported by the peer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addrlocal: Option<String>,
+    /// Network of the peer
+    pub network: NetworkType,
+    /// Autonomous system number of the peer, with `-asmap`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapped_as: Option<u32>,
+    /// Services offered by the peer
+    #[serde(with = "service_flags_hex")]
+    pub services: ServiceFlags,
+    /// Names of the offered services
+    pub servicesnames: Vec<String>,
+    /// Whether the peer asked for transactions to be relayed
+    pub relaytxes: bool,
+    /// Time of the last send, in seconds since epoch
+    pub lastsend: u64,
+    /// Time of the last receive, in seconds since epoch
+    pub lastrecv: u64,
+    /// Time of the last valid transaction received from the peer
+    pub last_transaction: u64,
+    /// Time of the last block received from the peer
+    pub last_block: u64,
+    /// Total bytes sent
+    pub bytessent: u64,
+    /// Total bytes received
+    pub bytesrecv: u64,
+    /// Time of connection, in seconds since epoch
+    pub conntime: u64,
+    /// Time offset of the peer, in seconds
+    pub timeoffset: i64,
+    /// Last ping time, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pingtime: Option<f64>,
+    /// Minimum observed ping time, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minping: Option<f64>,
+    /// Time waited for an outstanding ping, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pingwait: Option<f64>,
+    /// Protocol version of the peer
+    pub version: u32,
+    /// User agent of the peer
+    pub subver: String,
+    /// Whether the connection is inbound
+    pub inbound: bool,
+    /// Whether high-bandwidth compact block relay was selected to the peer
+    pub bip152_hb_to: bool,
+    /// Whether the peer selected high-bandwidth compact block relay from us
+    pub bip152_hb_from: bool,
+    /// Height of the peer's chain at connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startingheight: Option<i64>,
+    /// Height of the headers presynced from the peer, -1 if none
+    pub presynced_headers: i64,
+    /// Height of the last header in common with the peer, -1 if unknown
+    pub synced_headers: i64,
+    /// Height of the last block in common with the peer, -1 if unknown
+    pub synced_blocks: i64,
+    /// Heights of the blocks requested from the peer
+    pub inflight: Vec<u32>,
+    /// Whether addresses are relayed to the peer
+    pub addr_relay_enabled: bool,
+    /// Number of addresses processed from the peer
+    pub addr_processed: u64,
+    /// Number of addresses from the peer dropped by rate limiting
+    pub addr_rate_limited: u64,
+    /// Permissions granted to the peer
+    pub permissions: Vec<String>,
+    /// Minimum fee rate for transactions announced to the peer, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub minfeefilter: Amount,
+    /// Bytes sent by message type
+    pub bytessent_per_msg: BTreeMap<String, u64>,
+    /// Bytes received by message type
+    pub bytesrecv_per_msg: BTreeMap<String, u64>,
+    /// Kind of connection
+    pub connection_type: ConnectionType,
+    /// P2P transport protocol (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport_protocol_type: Option<TransportProtocol>,
+    /// BIP324 session id, as hex, empty for v1 connections (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_get_network_info() {
+        let value = json!({
+            "version": 290000,
+            "subversion": "/Satoshi:29.0.0/",
+            "protocolversion": 70016,
+            "localservices": "0000000000000c09",
+            "localservicesnames": ["NETWORK", "WITNESS", "NETWORK_LIMITED", "P2P_V2"],
+            "localrelay": true,
+            "timeoffset": 0,
+            "networkactive": true,
+            "connections": 10,
+            "connections_in": 0,
+            "connections_out": 10,
+            "networks": [
+                { "name": "ipv4", "limited": false, "reachable": true, "proxy": "", "proxy_randomize_credentials": false },
+                { "name": "onion", "limited": true, "reachable": false, "proxy": "", "proxy_randomize_credentials": false },
+            ],
+            "relayfee": 0.00001,
+            "incrementalfee": 0.00001,
+            "localaddresses": [{ "address": "203.0.113.5", "port": 8333, "score": 1 }],
+            "warnings": [],
+        });
+        let info: GetNetworkInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(info.localservices.has(ServiceFlags::WITNESS));
+        assert!(info.localservices.has(ServiceFlags::P2P_V2));
+        assert_eq!(info.relayfee, Amount::from_sat(1000));
+        assert!(info.is_reachable(NetworkType::Ipv4));
+        assert!(!info.is_reachable(NetworkType::Onion));
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+
+        assert_eq!(
+            serde_json::from_value::<NetworkType>(json!("not_publicly_routable")).unwrap(),
+            NetworkType::NotPubliclyRoutable
+        );
+    }
+
+    #[test]
+    fn test_peer_info() {
+        let value = json!({
+            "id": 7,
+            "addr": "203.0.113.9:8333",
+            "addrbind": "192.0.2.1:50000",
+            "network": "ipv4",
+            "services": "0000000000000c09",
+            "servicesnames": ["NETWORK", "WITNESS", "NETWORK_LIMITED", "P2P_V2"],
+            "relaytxes": true,
+            "lastsend": 1_713_571_767,
+            "lastrecv": 1_713_571_767,
+            "last_transaction": 1_713_571_700,
+            "last_block": 0,
+            "bytessent": 18_000,
+            "bytesrecv": 21_000,
+            "conntime": 1_713_571_000,
+            "timeoffset": -1,
+            "pingtime": 0.0315,
+            "minping": 0.03,
+            "version": 70016,
+            "subver": "/Satoshi:29.0.0/",
+            "inbound": false,
+            "bip152_hb_to": false,
+            "bip152_hb_from": false,
+            "presynced_headers": -1,
+            "synced_headers": 840_000,
+            "synced_blocks": 840_000,
+            "inflight": [],
+            "addr_relay_enabled": true,
+            "addr_processed": 1000,
+            "addr_rate_limited": 0,
+            "permissions": [],
+            "minfeefilter": 0.00001,
+            "bytessent_per_msg": { "ping": 320, "tx": 12_000 },
+            "bytesrecv_per_msg": { "pong": 320 },
+            "connection_type": "outbound-full-relay",
+            "transport_protocol_type": "v2",
+            "session_id": "ab",
+        });
+        let peer: PeerInfo = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(peer.connection_type, ConnectionType::OutboundFullRelay);
+        assert_eq!(peer.transport_protocol_type, Some(TransportProtocol::V2));
+        assert_eq!(peer.bytessent_per_msg["tx"], 12_000);
+        assert_eq!(peer.minfeefilter, Amount::from_sat(1000));
+        assert_eq!(serde_json::to_value(&peer).unwrap(), value);
+    }
+}
@@ -0,0 +1,107 @@
+//! OpenRPC document export
+//!
+//! Renders an `ApiDefinition` as an [OpenRPC](https://spec.open-rpc.org)
+//! document so the Bitcoin Core API can be consumed by generic OpenRPC
+//! tooling such as playgrounds, validators and documentation generators.
+
+use serde_json::{json, Value};
+
+use crate::json_schema::{argument_schema, results_schema};
+use crate::types::{ApiDefinition, BtcMethod};
+
+/// Version of the OpenRPC specification the exported documents follow
+pub const OPENRPC_VERSION: &str = "1.3.2";
+
+impl ApiDefinition {
+    /// Exports this definition as an OpenRPC document
+    ///
+    /// Hidden arguments are omitted. Methods with several conditional results
+    /// describe their result schema with `anyOf`.
+    pub fn to_openrpc(&self) -> Value {
+        json!({
+            "openrpc": OPENRPC_VERSION,
+            "info": {
+                "title": "Bitcoin Core JSON-RPC",
+                "version": "unspecified",
+            },
+            "methods": self.rpcs.values().map(openrpc_method).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn openrpc_method(method: &BtcMethod) -> Value {
+    let params: Vec<Value> = method
+        .arguments
+        .iter()
+        .filter(|arg| !arg.hidden)
+        .map(|arg| {
+            let mut param = json!({
+                "name": arg.names.first().cloned().unwrap_or_default(),
+                "required": arg.required,
+                "schema": argument_schema(arg),
+            });
+            if !arg.description.is_empty() {
+                param["description"] = Value::String(arg.description.clone());
+            }
+            param
+        })
+        .collect();
+
+    let mut value = json!({
+        "name": method.name,
+        "paramStructure": "either",
+        "params": params,
+        "result": {
+            "name": format!("{}_result", method.name),
+            "schema": results_schema(&method.results),
+        },
+    });
+    if !method.description.is_empty() {
+        value["description"] = Value::String(method.description.clone());
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BtcArgument, BtcResult};
+
+    #[test]
+    fn test_to_openrpc() {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblockhash".to_string(),
+            BtcMethod {
+                name: "getblockhash".to_string(),
+                description: "Returns hash of block in best-block-chain at height provided."
+                    .to_string(),
+                argument_names: vec!["height".to_string()],
+                arguments: vec![BtcArgument {
+                    names: vec!["height".to_string()],
+                    description: "The height index".to_string(),
+                    required: true,
+                    type_: "number".to_string(),
+                    ..BtcArgument::default()
+                }],
+                results: vec![BtcResult { type_: "hex".to_string(), ..BtcResult::default() }],
+                ..BtcMethod::default()
+            },
+        );
+
+        let doc = api_def.to_openrpc();
+        assert_eq!(doc["openrpc"], OPENRPC_VERSION);
+        let method = &doc["methods"][0];
+        assert_eq!(method["name"], "getblockhash");
+        assert_eq!(method["params"][0]["name"], "height");
+        assert_eq!(method["params"][0]["required"], true);
+        assert_eq!(method["params"][0]["schema"]["type"], "number");
+        assert_eq!(method["result"]["schema"]["type"], "string");
+    }
+
+    #[test]
+    fn test_to_openrpc_empty() {
+        let doc = ApiDefinition::new().to_openrpc();
+        assert_eq!(doc["methods"], json!([]));
+    }
+}
@@ -0,0 +1,294 @@
+//! Transaction output arguments
+//!
+//! `createrawtransaction`, `createpsbt`, `walletcreatefundedpsbt` and `send`
+//! take their outputs as an array of single-key objects: `{"address": amount}`
+//! pays an amount in BTC to an address and `{"data": "hex"}` adds an
+//! `OP_RETURN` output. `TxOutput` is one entry of that array.
+//! `sendmany` instead takes `Recipients`, a single object mapping addresses to
+//! amounts, which converts to the array form for `send`.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::hex::{DisplayHex, FromHex};
+use bitcoin::{Address, Amount, Denomination};
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// One entry of an outputs array
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxOutput {
+    /// Payment of an amount to an address
+    Address {
+        /// Address to pay to
+        address: Address<NetworkUnchecked>,
+        /// Amount to pay
+        amount: Amount,
+    },
+    /// `OP_RETURN` output carrying data
+    Data(Vec<u8>),
+}
+
+impl TxOutput {
+    /// Creates a payment output
+    pub fn address(address: impl Into<Address<NetworkUnchecked>>, amount: Amount) -> Self {
+        Self::Address { address: address.into(), amount }
+    }
+
+    /// Creates a data output
+    pub fn data(data: impl Into<Vec<u8>>) -> Self { Self::Data(data.into()) }
+
+    /// Returns whether this is a data output
+    pub fn is_data(&self) -> bool { matches!(self, Self::Data(_)) }
+
+    /// Returns the amount paid, zero for a data output
+    pub fn amount(&self) -> Amount {
+        match self {
+            Self::Address { amount, .. } => *amount,
+            Self::Data(_) => Amount::ZERO,
+        }
+    }
+}
+
+/// Amounts to pay to addresses, in insertion order
+///
+/// Serializes as an object of addresses to amounts in BTC, the `amounts`
+/// argument of `sendmany`. Each address appears once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recipients {
+    entries: Vec<(Address<NetworkUnchecked>, Amount)>,
+}
+
+impl Recipients {
+    /// Creates an empty set of recipients
+    pub fn new() -> Self { Self::default() }
+
+    /// Sets the amount paid to an address, returning the previous amount
+    pub fn insert(
+        &mut self,
+        address: impl Into<Address<NetworkUnchecked>>,
+        amount: Amount,
+    ) -> Option<Amount> {
+        let address = address.into();
+        match self.entries.iter_mut().find(|(a, _)| *a == address) {
+            Some((_, existing)) => Some(std::mem::replace(existing, amount)),
+            None => {
+                self.entries.push((address, amount));
+                None
+            }
+        }
+    }
+
+    /// Returns the amount paid to an address
+    pub fn get(&self, address: &Address<NetworkUnchecked>) -> Option<Amount> {
+        self.entries.iter().find(|(a, _)| a == address).map(|(_, amount)| *amount)
+    }
+
+    /// Returns the number of recipients
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns whether there are no recipients
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Iterates over the recipients in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&Address<NetworkUnchecked>, Amount)> {
+        self.entries.iter().map(|(address, amount)| (address, *amount))
+    }
+
+    /// Returns the sum of the amounts, or `None` on overflow
+    pub fn total(&self) -> Option<Amount> {
+        self.entries.iter().try_fold(Amount::ZERO, |total, (_, amount)| total.checked_add(*amount))
+    }
+
+    /// Returns the recipients as an outputs array, for `send`
+    ///
+    /// Data outputs can be appended as `TxOutput::Data`.
+    pub fn to_outputs(&self) -> Vec<TxOutput> {
+        self.iter().map(|(address, amount)| TxOutput::address(address.clone(), amount)).collect()
+    }
+}
+
+impl<A: Into<Address<NetworkUnchecked>>> FromIterator<(A, Amount)> for Recipients {
+    fn from_iter<I: IntoIterator<Item = (A, Amount)>>(iter: I) -> Self {
+        let mut recipients = Self::new();
+        for (address, amount) in iter {
+            recipients.insert(address, amount);
+        }
+        recipients
+    }
+}
+
+impl From<Recipients> for Vec<TxOutput> {
+    fn from(recipients: Recipients) -> Self {
+        recipients
+            .entries
+            .into_iter()
+            .map(|(address, amount)| TxOutput::Address { address, amount })
+            .collect()
+    }
+}
+
+impl Serialize for Recipients {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (address, amount) in &self.entries {
+            map.serialize_entry(&address.assume_checked_ref().to_string(), &amount.to_btc())?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Recipients {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_map(RecipientsVisitor)
+    }
+}
+
+/// Reads recipients entry by entry, so they keep the order of the input
+struct RecipientsVisitor;
+
+impl<'de> de::Visitor<'de> for RecipientsVisitor {
+    type Value = Recipients;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an object of addresses to amounts")
+    }
+
+    fn visit_map<M: de::MapAccess<'de>>(
+        self,
+        mut map: M,
+    ) -> std::result::Result<Recipients, M::Error> {
+        let mut recipients = Recipients::new();
+        while let Some((key, value)) = map.next_entry::<String, Value>()? {
+            let address = key
+                .parse::<Address<NetworkUnchecked>>()
+                .map_err(|_| de::Error::custom(format!("invalid address: {:?}", key)))?;
+            let amount = amount_from_btc_value(&value)
+                .ok_or_else(|| de::Error::custom(format!("invalid amount: {}", value)))?;
+            recipients.insert(address, amount);
+        }
+        Ok(recipients)
+    }
+}
+
+/// Parses an amount in BTC, given as a JSON number or string
+pub(crate) fn amount_from_btc_value(value: &Value) -> Option<Amount> {
+    match value {
+        Value::Number(number) => Amount::from_btc(number.as_f64()?).ok(),
+        Value::String(s) => Amount::from_str_in(s, Denomination::Bitcoin).ok(),
+        _ => None,
+    }
+}
+
+impl Serialize for TxOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            Self::Address { address, amount } =>
+                map.serialize_entry(&address.assume_checked_ref().to_string(), &amount.to_btc())?,
+            Self::Data(data) => map.serialize_entry("data", &data.to_lower_hex_string())?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TxOutput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let map = Map::<String, Value>::deserialize(deserializer)?;
+        if map.len() != 1 {
+            return Err(de::Error::custom(format!(
+                "expected an object with one key, got {} keys",
+                map.len()
+            )));
+        }
+        let (key, value) = map.into_iter().next().expect("one entry");
+        if key == "data" {
+            let hex =
+                value.as_str().ok_or_else(|| de::Error::custom("data must be a hex string"))?;
+            let data = Vec::from_hex(hex)
+                .map_err(|_| de::Error::custom(format!("invalid data hex: {:?}", hex)))?;
+            return Ok(Self::Data(data));
+        }
+        let address = key
+            .parse::<Address<NetworkUnchecked>>()
+            .map_err(|_| de::Error::custom(format!("invalid address: {:?}", key)))?;
+        let amount = amount_from_btc_value(&value)
+            .ok_or_else(|| de::Error::custom(format!("invalid amount: {}", value)))?;
+        Ok(Self::Address { address, amount })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+    const OTHER: &str = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+
+    fn address(s: &str) -> Address<NetworkUnchecked> { s.parse().unwrap() }
+
+    #[test]
+    fn test_tx_output_serde() {
+        let outputs = vec![
+            TxOutput::address(ADDRESS.parse::<Address<_>>().unwrap(), Amount::from_sat(150_000)),
+            TxOutput::data(b"hi".to_vec()),
+        ];
+        let value = serde_json::to_value(&outputs).unwrap();
+        assert_eq!(value, json!([{ ADDRESS: 0.0015 }, { "data": "6869" }]));
+        assert_eq!(serde_json::from_value::<Vec<TxOutput>>(value).unwrap(), outputs);
+        assert_eq!(outputs[1].amount(), Amount::ZERO);
+        assert!(outputs[1].is_data());
+    }
+
+    #[test]
+    fn test_tx_output_deserialize() {
+        let output: TxOutput = serde_json::from_value(json!({ ADDRESS: "0.5" })).unwrap();
+        assert_eq!(output.amount(), Amount::from_sat(50_000_000));
+        assert!(serde_json::from_value::<TxOutput>(json!({})).is_err());
+        assert!(serde_json::from_value::<TxOutput>(json!({ ADDRESS: 1, "data": "00" })).is_err());
+        assert!(serde_json::from_value::<TxOutput>(json!({ "data": "zz" })).is_err());
+        assert!(serde_json::from_value::<TxOutput>(json!({ "nope": 1 })).is_err());
+        assert!(serde_json::from_value::<TxOutput>(json!({ ADDRESS: -1 })).is_err());
+    }
+
+    #[test]
+    fn test_recipients() {
+        let mut recipients: Recipients = [
+            (address(OTHER), Amount::from_sat(1_000)),
+            (address(ADDRESS), Amount::from_sat(2_000)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            recipients.insert(address(OTHER), Amount::from_sat(3_000)),
+            Some(Amount::from_sat(1_000))
+        );
+        assert_eq!(recipients.len(), 2);
+        assert_eq!(recipients.get(&address(OTHER)), Some(Amount::from_sat(3_000)));
+        assert_eq!(recipients.total(), Some(Amount::from_sat(5_000)));
+
+        let value = serde_json::to_value(&recipients).unwrap();
+        assert_eq!(value, json!({ OTHER: 0.00003, ADDRESS: 0.00002 }));
+        let parsed: Recipients = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.get(&address(ADDRESS)), Some(Amount::from_sat(2_000)));
+        assert!(serde_json::from_value::<Recipients>(json!({ "data": "00" })).is_err());
+
+        let mut outputs = recipients.to_outputs();
+        outputs.push(TxOutput::data(vec![0xab]));
+        assert_eq!(
+            serde_json::to_value(&outputs).unwrap(),
+            json!([{ OTHER: 0.00003 }, { ADDRESS: 0.00002 }, { "data": "ab" }])
+        );
+        assert_eq!(Vec::<TxOutput>::from(recipients), outputs[..2]);
+    }
+
+    #[test]
+    fn test_recipients_keep_input_order() {
+        let json = format!(r#"{{"{}":0.00002,"{}":0.00001}}"#, ADDRESS, OTHER);
+        let recipients: Recipients = serde_json::from_str(&json).unwrap();
+        let addresses: Vec<_> = recipients.iter().map(|(address, _)| address.clone()).collect();
+        assert_eq!(addresses, vec![address(ADDRESS), address(OTHER)]);
+        assert_eq!(serde_json::to_string(&recipients).unwrap(), json);
+    }
+}
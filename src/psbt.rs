@@ -0,0 +1,166 @@
+//! Base64 PSBT argument and result type
+//!
+//! Bitcoin Core passes PSBTs as base64 strings. `PsbtString` checks the
+//! encoding and the PSBT magic on construction, so hex or other strings are
+//! rejected before reaching the node. With the `psbt` feature it converts to
+//! and from `bitcoin::Psbt`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Base64 of the PSBT magic bytes `psbt\xff`, up to the last full character
+const MAGIC_BASE64: &str = "cHNidP";
+
+/// Hex of the PSBT magic bytes
+const MAGIC_HEX: &str = "70736274ff";
+
+/// Base64-encoded PSBT
+///
+/// Serializes as the base64 string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PsbtString(String);
+
+impl PsbtString {
+    /// Validates a base64-encoded PSBT
+    pub fn new(s: impl Into<String>) -> std::result::Result<Self, ParsePsbtStringError> {
+        let s = s.into();
+        if s.get(..MAGIC_HEX.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(MAGIC_HEX)) {
+            return Err(ParsePsbtStringError::Hex);
+        }
+        if !is_base64(&s) {
+            return Err(ParsePsbtStringError::InvalidBase64(s));
+        }
+        if !s.starts_with(MAGIC_BASE64) {
+            return Err(ParsePsbtStringError::NotPsbt);
+        }
+        Ok(Self(s))
+    }
+
+    /// Returns the base64 string
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// Returns the base64 string, consuming the PSBT
+    pub fn into_string(self) -> String { self.0 }
+
+    /// Decodes the PSBT
+    #[cfg(feature = "psbt")]
+    pub fn to_psbt(&self) -> std::result::Result<bitcoin::Psbt, bitcoin::psbt::PsbtParseError> {
+        self.0.parse()
+    }
+}
+
+/// Returns whether a string is padded standard base64
+fn is_base64(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() || !bytes.len().is_multiple_of(4) {
+        return false;
+    }
+    let data = s.trim_end_matches('=');
+    bytes.len() - data.len() <= 2
+        && data.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+/// Error returned for a string that is not a base64-encoded PSBT
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParsePsbtStringError {
+    /// Hex-encoded PSBT, which the RPC interface does not accept
+    #[error("PSBT is hex encoded; base64 is expected")]
+    Hex,
+    /// Not padded standard base64
+    #[error("Invalid base64: {0:?}")]
+    InvalidBase64(String),
+    /// Valid base64 without the PSBT magic bytes
+    #[error("Base64 does not start with the PSBT magic bytes")]
+    NotPsbt,
+}
+
+impl fmt::Display for PsbtString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+impl FromStr for PsbtString {
+    type Err = ParsePsbtStringError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Self::new(s) }
+}
+
+impl AsRef<str> for PsbtString {
+    fn as_ref(&self) -> &str { &self.0 }
+}
+
+impl From<PsbtString> for String {
+    fn from(psbt: PsbtString) -> Self { psbt.0 }
+}
+
+#[cfg(feature = "psbt")]
+impl From<&bitcoin::Psbt> for PsbtString {
+    fn from(psbt: &bitcoin::Psbt) -> Self { Self(psbt.to_string()) }
+}
+
+#[cfg(feature = "psbt")]
+impl From<bitcoin::Psbt> for PsbtString {
+    fn from(psbt: bitcoin::Psbt) -> Self { Self::from(&psbt) }
+}
+
+impl Serialize for PsbtString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for PsbtString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PSBT: &str = "cHNidP8BAAoCAAAAAAAAAAAAAAAA";
+
+    #[test]
+    fn test_psbt_string_new() {
+        assert_eq!(PsbtString::new(PSBT).unwrap().as_str(), PSBT);
+        assert_eq!(PsbtString::new("70736274ff0100"), Err(ParsePsbtStringError::Hex));
+        assert_eq!(
+            PsbtString::new("cHNidP8"),
+            Err(ParsePsbtStringError::InvalidBase64("cHNidP8".into()))
+        );
+        assert!(PsbtString::new("cHNidP8B!AAA").is_err());
+        assert!(PsbtString::new("cHNi=P8B").is_err());
+        assert!(PsbtString::new("").is_err());
+        assert!(PsbtString::new("7073627\u{e9}").is_err());
+        assert_eq!(PsbtString::new("AAAA"), Err(ParsePsbtStringError::NotPsbt));
+    }
+
+    #[test]
+    fn test_psbt_string_serde() {
+        let psbt: PsbtString = serde_json::from_str(&format!("{:?}", PSBT)).unwrap();
+        assert_eq!(serde_json::to_string(&psbt).unwrap(), format!("{:?}", PSBT));
+        assert!(serde_json::from_str::<PsbtString>("\"70736274ff01000a\"").is_err());
+    }
+
+    #[cfg(feature = "psbt")]
+    #[test]
+    fn test_psbt_string_decode() {
+        use bitcoin::absolute::LockTime;
+        use bitcoin::transaction::Version;
+        use bitcoin::{Psbt, Transaction};
+
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let encoded = PsbtString::from(&psbt);
+        assert_eq!(encoded.as_str().parse::<PsbtString>().unwrap(), encoded);
+        assert_eq!(encoded.to_psbt().unwrap(), psbt);
+    }
+}
@@ -0,0 +1,115 @@
+//! Raw transaction hex argument and result type
+//!
+//! `RawTxHex` holds a serialized transaction as hex, the form taken by
+//! `sendrawtransaction`, `decoderawtransaction` and `testmempoolaccept` and
+//! returned by `createrawtransaction` and `fundrawtransaction`. The hex is
+//! checked on construction and decoded to a `Transaction` on demand.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::consensus::encode;
+use bitcoin::Transaction;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Serialized transaction, as lowercase hex
+///
+/// Serializes as the hex string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawTxHex(String);
+
+impl RawTxHex {
+    /// Validates transaction hex, normalizing it to lowercase
+    pub fn new(hex: impl Into<String>) -> std::result::Result<Self, ParseRawTxHexError> {
+        let hex = hex.into();
+        if hex.is_empty()
+            || !hex.len().is_multiple_of(2)
+            || !hex.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            return Err(ParseRawTxHexError(hex));
+        }
+        Ok(Self(hex.to_ascii_lowercase()))
+    }
+
+    /// Returns the hex string
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// Returns the hex string, consuming the transaction
+    pub fn into_string(self) -> String { self.0 }
+
+    /// Decodes the transaction
+    pub fn to_transaction(&self) -> std::result::Result<Transaction, encode::FromHexError> {
+        encode::deserialize_hex(&self.0)
+    }
+}
+
+/// Error returned for a string that is not transaction hex
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid transaction hex: {0:?}")]
+pub struct ParseRawTxHexError(String);
+
+impl fmt::Display for RawTxHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+impl FromStr for RawTxHex {
+    type Err = ParseRawTxHexError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Self::new(s) }
+}
+
+impl AsRef<str> for RawTxHex {
+    fn as_ref(&self) -> &str { &self.0 }
+}
+
+impl From<RawTxHex> for String {
+    fn from(hex: RawTxHex) -> Self { hex.0 }
+}
+
+impl From<&Transaction> for RawTxHex {
+    fn from(tx: &Transaction) -> Self { Self(encode::serialize_hex(tx)) }
+}
+
+impl From<Transaction> for RawTxHex {
+    fn from(tx: Transaction) -> Self { Self::from(&tx) }
+}
+
+impl Serialize for RawTxHex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawTxHex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW_TX: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d0104ffffffff0100f2052a0100000043410496b538e853519c726a2c91e61ec11600ae1390813a627c66fb8be7947be63c52da7589379515d4e0a604f8141781e62294721166bf621e73a82cbf2342c858eeac00000000";
+
+    #[test]
+    fn test_raw_tx_hex() {
+        let hex = RawTxHex::new(RAW_TX.to_ascii_uppercase()).unwrap();
+        assert_eq!(hex.as_str(), RAW_TX);
+        let tx = hex.to_transaction().unwrap();
+        assert_eq!(RawTxHex::from(tx), hex);
+
+        assert_eq!(RawTxHex::new("abc"), Err(ParseRawTxHexError("abc".to_string())));
+        assert!("zz".parse::<RawTxHex>().is_err());
+        assert!("".parse::<RawTxHex>().is_err());
+        assert!(RawTxHex::new("00ff").unwrap().to_transaction().is_err());
+    }
+
+    #[test]
+    fn test_raw_tx_hex_serde() {
+        let hex: RawTxHex = serde_json::from_value(serde_json::json!(RAW_TX)).unwrap();
+        assert_eq!(serde_json::to_value(&hex).unwrap(), serde_json::json!(RAW_TX));
+        assert!(serde_json::from_str::<RawTxHex>("\"0g\"").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Schema-validated request construction
+//!
+//! `RequestBuilder` turns a method name and parameters into a
+//! `JsonRpcRequest` after checking them against an `ApiDefinition`, so every
+//! transport gets the same checks: unknown methods and invalid parameters
+//! are rejected before anything is sent to the node.
+
+use serde_json::{Map, Value};
+
+use crate::jsonrpc::{JsonRpcRequest, JsonRpcVersion, RequestIdGenerator};
+use crate::rpc_type::RpcType;
+use crate::types::{ApiDefinition, BtcArgument, BtcMethod, Result, SchemaError};
+use crate::validate::ValidationOptions;
+
+/// Builds JSON-RPC requests checked against an API definition
+///
+/// Each request gets the next id of the builder's `RequestIdGenerator`.
+#[derive(Debug)]
+pub struct RequestBuilder<'a> {
+    api_def: &'a ApiDefinition,
+    version: JsonRpcVersion,
+    options: ValidationOptions,
+    ids: RequestIdGenerator,
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// Creates a builder for 1.0-style requests with the default validation options
+    pub fn new(api_def: &'a ApiDefinition) -> Self {
+        Self {
+            api_def,
+            version: JsonRpcVersion::default(),
+            options: ValidationOptions::default(),
+            ids: RequestIdGenerator::new(),
+        }
+    }
+
+    /// Sets the protocol version of built requests
+    pub fn with_version(mut self, version: JsonRpcVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the options parameters are validated with
+    pub fn with_validation(mut self, options: ValidationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the generator request ids are taken from
+    pub fn with_ids(mut self, ids: RequestIdGenerator) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Builds a request from positional (array), named (object) or no
+    /// (`null`) parameters
+    ///
+    /// String values given for non-string arguments are coerced as on the
+    /// command line (`"6"` → `6`). Either form is sent as
+    /// `BtcMethod::call_plan` plans it: positionally where possible, which
+    /// every Bitcoin Core version understands, and by name when an omitted
+    /// argument without a default precedes a given one. `null` stands for
+    /// an omitted argument.
+    ///
+    /// Fails with `SchemaError::UnknownMethod` for a method not in the
+    /// definition and `SchemaError::InvalidParams` for parameters that do
+    /// not validate.
+    pub fn build(&self, method: &str, params: Value) -> Result<JsonRpcRequest> {
+        let btc_method = self.method(method)?;
+        let params = match params {
+            Value::Null => Value::Array(Vec::new()),
+            Value::Array(params) => Value::Array(
+                params
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| match btc_method.arguments.get(index) {
+                        Some(arg) => coerce(arg, value),
+                        None => value,
+                    })
+                    .collect(),
+            ),
+            Value::Object(params) => Value::Object(
+                params
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = match btc_method.named_param_schema(&name) {
+                            Some(arg) => coerce(arg, value),
+                            None => value,
+                        };
+                        (name, value)
+                    })
+                    .collect(),
+            ),
+            params => params,
+        };
+        btc_method
+            .validate_params_with(&params, &self.options)
+            .map_err(SchemaError::InvalidParams)?;
+
+        let params = match params {
+            Value::Array(params) => btc_method.params_to_named(params)?,
+            Value::Object(params) => params,
+            _ => Map::new(),
+        };
+        let params = btc_method.call_plan(&params)?.into_params();
+        Ok(JsonRpcRequest::new(method, params)
+            .with_version(self.version)
+            .with_id(self.ids.next_id()))
+    }
+
+    /// Builds a request from `bitcoin-cli` style string arguments
+    ///
+    /// The arguments are converted with `BtcMethod::coerce_cli_args`, then
+    /// checked as in `build`.
+    pub fn build_cli<S: AsRef<str>>(
+        &self,
+        method: &str,
+        args: &[S],
+        named: bool,
+    ) -> Result<JsonRpcRequest> {
+        let params = self.method(method)?.coerce_cli_args(args, named)?;
+        self.build(method, params)
+    }
+
+    fn method(&self, method: &str) -> Result<&'a BtcMethod> {
+        self.api_def
+            .get_method(method)
+            .ok_or_else(|| SchemaError::UnknownMethod(method.to_string()))
+    }
+}
+
+/// Converts a string given for a non-string argument as on the command line,
+/// keeping it unchanged when it does not parse
+fn coerce(arg: &BtcArgument, value: Value) -> Value {
+    match &value {
+        Value::String(text) if !arg.rpc_type().alternatives().iter().any(RpcType::is_string) =>
+            arg.coerce(text).unwrap_or(value),
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::jsonrpc::RequestId;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    fn api() -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        api_def
+            .insert_method(BtcMethod {
+                name: "getblock".to_string(),
+                argument_names: vec!["blockhash".to_string(), "verbosity".to_string()],
+                arguments: vec![
+                    BtcArgument {
+                        names: vec!["blockhash".to_string()],
+                        type_: "hex".to_string(),
+                        required: true,
+                        ..BtcArgument::default()
+                    },
+                    BtcArgument {
+                        names: vec!["verbosity".to_string()],
+                        type_: "number".to_string(),
+                        default: Some(json!(1)),
+                        ..BtcArgument::default()
+                    },
+                ],
+                ..BtcMethod::default()
+            })
+            .unwrap();
+        api_def
+    }
+
+    #[test]
+    fn test_build() {
+        let api_def = api();
+        let builder = RequestBuilder::new(&api_def).with_version(JsonRpcVersion::V2);
+
+        let request = builder.build("getblock", json!([HASH, "2"])).unwrap();
+        assert_eq!(request.params, json!([HASH, 2]));
+        assert_eq!(request.id, RequestId::Number(0));
+        assert_eq!(request.version, JsonRpcVersion::V2);
+
+        let request =
+            builder.build("getblock", json!({ "blockhash": HASH, "verbosity": 1 })).unwrap();
+        assert_eq!(request.params, json!([HASH]));
+        assert_eq!(request.id, RequestId::Number(1));
+
+        let request = builder.build("getblock", json!([HASH, null])).unwrap();
+        assert_eq!(request.params, json!([HASH]));
+
+        let request = builder.build_cli("getblock", &[HASH, "0"], false).unwrap();
+        assert_eq!(request.params, json!([HASH, 0]));
+    }
+
+    #[test]
+    fn test_build_rejects() {
+        let api_def = api();
+        let builder = RequestBuilder::new(&api_def).with_ids(RequestIdGenerator::starting_at(9));
+
+        assert!(matches!(
+            builder.build("getblok", Value::Null),
+            Err(SchemaError::UnknownMethod(method)) if method == "getblok"
+        ));
+        let Err(SchemaError::InvalidParams(errors)) = builder.build("getblock", json!([HASH, "x"]))
+        else {
+            panic!("expected invalid params");
+        };
+        assert_eq!(errors[0].pointer, "/1");
+        assert!(builder.build("getblock", Value::Null).is_err());
+        assert!(builder.build("getblock", json!({ "hash": HASH })).is_err());
+        assert_eq!(builder.build("getblock", json!([HASH])).unwrap().id, RequestId::Number(9));
+    }
+}
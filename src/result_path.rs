@@ -0,0 +1,237 @@
+//! Path addressing into result trees
+//!
+//! Result nodes are addressed with `/`-separated paths: a nested node's
+//! segment is its key name, or its index among its siblings when it has no
+//! key name (array elements). Method-level paths start with the index of the
+//! top-level result, e.g. `0/tx/0/txid`. This is the same convention used by
+//! `ApiDiff`, `LintLocation::Result` and `MatchLocation::ResultKey`.
+//!
+//! `BtcResult::iter_deep` and `BtcMethod::iter_results_deep` walk a whole
+//! tree and yield each node with its path.
+
+use crate::rpc_type::RpcType;
+use crate::types::{BtcMethod, BtcResult};
+
+/// Pre-order iterator over a result tree, yielding `(path, node)` pairs
+///
+/// Created by `BtcResult::iter_deep` and `BtcMethod::iter_results_deep`.
+#[derive(Debug, Clone)]
+pub struct DeepResults<'a> {
+    stack: Vec<(String, &'a BtcResult)>,
+}
+
+impl<'a> Iterator for DeepResults<'a> {
+    type Item = (String, &'a BtcResult);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.stack.pop()?;
+        for (index, child) in node.inner.iter().enumerate().rev() {
+            let segment =
+                if child.key_name.is_empty() { index.to_string() } else { child.key_name.clone() };
+            let child_path =
+                if path.is_empty() { segment } else { format!("{}/{}", path, segment) };
+            self.stack.push((child_path, child));
+        }
+        Some((path, node))
+    }
+}
+
+impl BtcResult {
+    /// Iterates over this node and all nested nodes, depth first
+    ///
+    /// Paths are relative to this node, which itself has the empty path.
+    /// Elisions are included.
+    pub fn iter_deep(&self) -> DeepResults<'_> {
+        DeepResults { stack: vec![(String::new(), self)] }
+    }
+
+    /// Looks up a nested node by path, relative to this node
+    ///
+    /// A leading `/` is ignored and the empty path returns `self`. Any index
+    /// addresses the element of a homogeneous array, and any key addresses the
+    /// values of a map, so paths copied from real responses (`tx/5/txid`)
+    /// resolve too.
+    pub fn get_path(&self, path: &str) -> Option<&BtcResult> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('/').try_fold(self, |node, segment| node.child(segment))
+    }
+
+    /// Returns the paths of all leaf nodes below this node, relative to it
+    ///
+    /// Elisions are not included. A node without children yields the empty path.
+    pub fn leaf_paths(&self) -> Vec<String> { leaf_paths(self.iter_deep()) }
+
+    /// Returns every node in this tree whose key name is `name`, depth first
+    pub fn find_key(&self, name: &str) -> Vec<&BtcResult> {
+        self.iter_deep().map(|(_, node)| node).filter(|node| node.key_name == name).collect()
+    }
+
+    fn child(&self, segment: &str) -> Option<&BtcResult> {
+        if let Some(child) = self.inner.iter().find(|child| child.key_name == segment) {
+            return Some(child);
+        }
+        if let Ok(index) = segment.parse::<usize>() {
+            let items: Vec<&BtcResult> =
+                self.inner.iter().filter(|child| !child.is_elision()).collect();
+            return match (self.rpc_type(), items.as_slice()) {
+                (RpcType::Array, [item]) => Some(item),
+                _ => self
+                    .inner
+                    .get(index)
+                    .filter(|child| child.key_name.is_empty())
+                    .or_else(|| self.map_value()),
+            };
+        }
+        self.map_value()
+    }
+}
+
+impl BtcMethod {
+    /// Iterates over every result node of every top-level result, depth first
+    ///
+    /// Paths start with the index of the top-level result.
+    pub fn iter_results_deep(&self) -> DeepResults<'_> {
+        let stack = self.results.iter().enumerate().rev();
+        DeepResults { stack: stack.map(|(index, result)| (index.to_string(), result)).collect() }
+    }
+
+    /// Looks up a result node by path, starting with the top-level result index
+    pub fn get_result(&self, path: &str) -> Option<&BtcResult> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let (index, rest) = path.split_once('/').unwrap_or((path, ""));
+        self.results.get(index.parse::<usize>().ok()?)?.get_path(rest)
+    }
+
+    /// Returns the paths of all leaf result nodes, in schema order
+    pub fn leaf_paths(&self) -> Vec<String> { leaf_paths(self.iter_results_deep()) }
+
+    /// Returns every result node with key name `name`, with its path
+    pub fn find_result_key(&self, name: &str) -> Vec<(String, &BtcResult)> {
+        self.iter_results_deep().filter(|(_, node)| node.key_name == name).collect()
+    }
+}
+
+fn leaf_paths(nodes: DeepResults<'_>) -> Vec<String> {
+    nodes
+        .filter(|(_, node)| !node.is_elision() && node.inner.iter().all(BtcResult::is_elision))
+        .map(|(path, _)| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            key_name: key_name.to_string(),
+            type_: type_.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    fn getblock() -> BtcMethod {
+        let vin = node("vin", "array", vec![node("", "object", vec![node("txid", "hex", vec![])])]);
+        let tx = node(
+            "tx",
+            "array",
+            vec![
+                node("", "object", vec![node("txid", "hex", vec![]), vin]),
+                node("", "elision", vec![]),
+            ],
+        );
+        BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![
+                node("", "hex", vec![]),
+                node("", "object", vec![node("hash", "hex", vec![]), tx]),
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    #[test]
+    fn test_get_path() {
+        let method = getblock();
+        let block = &method.results[1];
+        assert_eq!(block.get_path("tx/0/vin/0/txid").unwrap().type_, "hex");
+        assert_eq!(block.get_path("/tx/7/txid").unwrap().key_name, "txid");
+        assert_eq!(block.get_path("").unwrap(), block);
+        assert!(block.get_path("tx/0/missing").is_none());
+        assert!(block.get_path("hash/0").is_none());
+
+        assert_eq!(method.get_result("1/hash").unwrap().key_name, "hash");
+        assert_eq!(method.get_result("0").unwrap().type_, "hex");
+        assert!(method.get_result("2").is_none());
+        assert!(method.get_result("x/hash").is_none());
+    }
+
+    #[test]
+    fn test_get_path_through_map() {
+        let mempool = node(
+            "",
+            "object",
+            vec![BtcResult {
+                dynamic_key: true,
+                ..node("transactionid", "object", vec![node("vsize", "number", vec![])])
+            }],
+        );
+        assert_eq!(mempool.get_path("abcd/vsize").unwrap().type_, "number");
+    }
+
+    #[test]
+    fn test_iter_deep() {
+        let method = getblock();
+        let paths: Vec<String> = method.results[1].iter_deep().map(|(path, _)| path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "",
+                "hash",
+                "tx",
+                "tx/0",
+                "tx/0/txid",
+                "tx/0/vin",
+                "tx/0/vin/0",
+                "tx/0/vin/0/txid",
+                "tx/1"
+            ]
+        );
+
+        let nodes: Vec<(String, &BtcResult)> = method.iter_results_deep().collect();
+        assert_eq!(nodes.len(), 10);
+        assert_eq!(nodes[0].0, "0");
+        assert_eq!(nodes[1].0, "1");
+        assert!(nodes[9].1.is_elision());
+        for (path, node) in nodes.iter().filter(|(_, node)| !node.is_elision()) {
+            assert_eq!(method.get_result(path), Some(*node));
+        }
+    }
+
+    #[test]
+    fn test_find_key() {
+        let method = getblock();
+        let txids = method.results[1].find_key("txid");
+        assert_eq!(txids.len(), 2);
+        assert!(txids.iter().all(|node| node.type_ == "hex"));
+        assert!(method.results[1].find_key("fee").is_empty());
+
+        let paths: Vec<String> =
+            method.find_result_key("txid").into_iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, vec!["1/tx/0/txid", "1/tx/0/vin/0/txid"]);
+    }
+
+    #[test]
+    fn test_leaf_paths() {
+        assert_eq!(
+            getblock().leaf_paths(),
+            vec!["0", "1/hash", "1/tx/0/txid", "1/tx/0/vin/0/txid"]
+        );
+        assert_eq!(getblock().results[1].leaf_paths()[0], "hash");
+        assert_eq!(node("", "string", vec![]).leaf_paths(), vec![""]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Result conditions and verbosity-dependent result variants
+//!
+//! Methods such as `getblock` and `getrawtransaction` return entirely
+//! different result trees depending on a verbosity argument. Each alternative
+//! is a top-level `BtcResult` with a free-text `condition`; this module parses
+//! those conditions and exposes the alternatives as variants that can be
+//! selected by verbosity level.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::types::{BtcMethod, BtcResult};
+
+/// Structured form of a result's `condition` text
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultCondition {
+    /// No condition; the result is always returned
+    Always,
+    /// Returned when the verbosity argument has this level
+    ///
+    /// Boolean `verbose` flags map to `0` (false) and `1` (true).
+    VerbosityEquals(u8),
+    /// Returned when an argument has a specific value (e.g. `if include_removed = true`)
+    ArgumentEquals {
+        /// Name of the argument
+        argument: String,
+        /// Value the argument must have
+        value: Value,
+    },
+    /// Condition that is not machine-readable (e.g. `when wallet is encrypted`)
+    Other(String),
+}
+
+impl ResultCondition {
+    /// Returns the verbosity level for `VerbosityEquals` conditions
+    pub fn verbosity(&self) -> Option<u8> {
+        match self {
+            Self::VerbosityEquals(level) => Some(*level),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for ResultCondition {
+    /// Parses condition text such as `for verbosity = 1`,
+    /// `if verbosity is not set or set to 0`, `for verbose = true` or
+    /// `if include_removed = true`
+    fn from(text: &str) -> Self {
+        let text = text.trim();
+        if text.is_empty() {
+            return Self::Always;
+        }
+        if let Some(level) = parse_verbosity(text) {
+            return Self::VerbosityEquals(level);
+        }
+        parse_argument_equals(text).unwrap_or_else(|| Self::Other(text.to_string()))
+    }
+}
+
+impl fmt::Display for ResultCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Always => Ok(()),
+            Self::VerbosityEquals(level) => write!(f, "for verbosity = {}", level),
+            Self::ArgumentEquals { argument, value } => write!(f, "if {} = {}", argument, value),
+            Self::Other(text) => f.write_str(text),
+        }
+    }
+}
+
+impl BtcResult {
+    /// Returns the parsed form of `condition`
+    pub fn parsed_condition(&self) -> ResultCondition {
+        ResultCondition::from(self.condition.as_str())
+    }
+}
+
+/// One alternative result tree of a method
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultVariant<'a> {
+    /// Verbosity level the variant applies to, if its condition names one
+    pub verbosity: Option<u8>,
+    /// Parsed condition under which the variant is returned
+    pub condition: ResultCondition,
+    /// The result tree returned under this condition
+    pub result: &'a BtcResult,
+}
+
+impl BtcMethod {
+    /// Returns the top-level results as variants, in schema order
+    pub fn result_variants(&self) -> Vec<ResultVariant<'_>> {
+        self.results
+            .iter()
+            .map(|result| {
+                let condition = result.parsed_condition();
+                ResultVariant { verbosity: condition.verbosity(), condition, result }
+            })
+            .collect()
+    }
+
+    /// Returns the verbosity levels with a dedicated result, in ascending order
+    pub fn verbosity_levels(&self) -> Vec<u8> {
+        let mut levels: Vec<u8> =
+            self.result_variants().iter().filter_map(|variant| variant.verbosity).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        levels
+    }
+
+    /// Selects the result tree returned for a verbosity level
+    ///
+    /// Methods with a single unconditional result return it for every level.
+    pub fn result_for_verbosity(&self, verbosity: u8) -> Option<&BtcResult> {
+        let variants = self.result_variants();
+        let exact = variants.iter().find(|variant| variant.verbosity == Some(verbosity));
+        match (exact, variants.as_slice()) {
+            (Some(variant), _) => Some(variant.result),
+            (None, [only]) if only.condition == ResultCondition::Always => Some(only.result),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the verbosity level from a condition mentioning `verbosity` or `verbose`
+fn parse_verbosity(condition: &str) -> Option<u8> {
+    let lower = condition.to_lowercase();
+    let (_, rest) = lower.split_once("verbos")?;
+    rest.split(|c: char| !c.is_ascii_alphanumeric()).find_map(|token| match token {
+        "true" => Some(1),
+        "false" => Some(0),
+        token => token.parse().ok(),
+    })
+}
+
+/// Parses `<for|if|when> <argument> <=|is> <JSON literal>`
+fn parse_argument_equals(text: &str) -> Option<ResultCondition> {
+    let mut words = text.split_whitespace();
+    if !matches!(words.next()?, "for" | "if" | "when") {
+        return None;
+    }
+    let argument = words.next()?;
+    if !argument.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    if !matches!(words.next()?, "=" | "==" | "is") {
+        return None;
+    }
+    let value = serde_json::from_str(&words.collect::<Vec<_>>().join(" ")).ok()?;
+    Some(ResultCondition::ArgumentEquals { argument: argument.to_string(), value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(type_: &str, condition: &str) -> BtcResult {
+        BtcResult {
+            type_: type_.to_string(),
+            condition: condition.to_string(),
+            ..BtcResult::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        assert_eq!(parse_verbosity("for verbosity = 1"), Some(1));
+        assert_eq!(parse_verbosity("if verbosity is not set or set to 0"), Some(0));
+        assert_eq!(parse_verbosity("for verbose = true"), Some(1));
+        assert_eq!(parse_verbosity("for verbose = false"), Some(0));
+        assert_eq!(parse_verbosity("if blockhash is given"), None);
+        assert_eq!(parse_verbosity(""), None);
+    }
+
+    #[test]
+    fn test_result_condition_parse() {
+        assert_eq!(ResultCondition::from(""), ResultCondition::Always);
+        assert_eq!(ResultCondition::from("for verbosity = 2"), ResultCondition::VerbosityEquals(2));
+        assert_eq!(
+            ResultCondition::from("if include_removed = true"),
+            ResultCondition::ArgumentEquals {
+                argument: "include_removed".to_string(),
+                value: serde_json::json!(true),
+            }
+        );
+        assert_eq!(
+            ResultCondition::from("if mode is \"stats\""),
+            ResultCondition::ArgumentEquals {
+                argument: "mode".to_string(),
+                value: serde_json::json!("stats"),
+            }
+        );
+        assert_eq!(
+            ResultCondition::from("when wallet is encrypted"),
+            ResultCondition::Other("when wallet is encrypted".to_string())
+        );
+        assert_eq!(ResultCondition::VerbosityEquals(1).to_string(), "for verbosity = 1");
+
+        let result =
+            BtcResult { condition: "for verbose = false".to_string(), ..BtcResult::default() };
+        assert_eq!(result.parsed_condition().verbosity(), Some(0));
+    }
+
+    #[test]
+    fn test_result_for_verbosity() {
+        let getblock = BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![
+                result("hex", "for verbosity = 0"),
+                result("object", "for verbosity = 1"),
+                result("object", "for verbosity = 2"),
+            ],
+            ..BtcMethod::default()
+        };
+        assert_eq!(getblock.verbosity_levels(), vec![0, 1, 2]);
+        assert_eq!(getblock.result_for_verbosity(0).unwrap().type_, "hex");
+        assert_eq!(getblock.result_for_verbosity(2).unwrap().condition, "for verbosity = 2");
+        assert!(getblock.result_for_verbosity(3).is_none());
+
+        let getblockcount = BtcMethod {
+            name: "getblockcount".to_string(),
+            results: vec![result("number", "")],
+            ..BtcMethod::default()
+        };
+        assert!(getblockcount.verbosity_levels().is_empty());
+        assert_eq!(getblockcount.result_for_verbosity(1).unwrap().type_, "number");
+    }
+}
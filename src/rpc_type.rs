@@ -0,0 +1,365 @@
+//! Typed RPC value types
+//!
+//! This module provides the `RpcType` enum, a typed view of the `type`
+//! strings used by `BtcArgument` and `BtcResult`.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::{Amount, Denomination, SignedAmount};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::types::{BtcArgument, BtcResult};
+
+/// Type of an RPC argument or result value
+///
+/// Unknown type names are preserved in `Other`, so parsing never fails and
+/// `as_str` round-trips every known name. `numeric` is accepted as an alias
+/// of `number`, and `time` as an alias of `timestamp`. Unions are written as
+/// `string or number` (`string|number` is accepted as well).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RpcType {
+    /// JSON string
+    String,
+    /// JSON number
+    Number,
+    /// JSON boolean
+    Boolean,
+    /// JSON object with a fixed set of keys
+    Object,
+    /// JSON object whose keys are data (e.g. txid → entry)
+    ObjectDynamic,
+    /// JSON object whose keys are chosen by the user (e.g. address → amount)
+    ObjectUserKeys,
+    /// JSON object whose keys are also accepted as named parameters
+    ObjectNamedParams,
+    /// JSON array of homogeneous items
+    Array,
+    /// JSON array with a fixed number of positional items
+    ArrayFixed,
+    /// BTC amount, as a JSON number (or string when passed as an argument)
+    Amount,
+    /// Hex-encoded string
+    Hex,
+    /// UNIX timestamp in seconds
+    Time,
+    /// Descriptor range, as an end index or a `[begin, end]` pair
+    Range,
+    /// JSON null
+    None,
+    /// Any JSON value
+    Any,
+    /// Placeholder for omitted entries (`...`)
+    Elision,
+    /// Type name not known to this crate
+    Other(String),
+    /// Any of several types (e.g. a block hash or a height)
+    OneOf(Vec<RpcType>),
+}
+
+impl RpcType {
+    /// Returns the canonical type name
+    pub fn as_str(&self) -> Cow<'_, str> {
+        let name = match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Object => "object",
+            Self::ObjectDynamic => "object_dynamic",
+            Self::ObjectUserKeys => "object_user_keys",
+            Self::ObjectNamedParams => "object_named_params",
+            Self::Array => "array",
+            Self::ArrayFixed => "array_fixed",
+            Self::Amount => "amount",
+            Self::Hex => "hex",
+            Self::Time => "timestamp",
+            Self::Range => "range",
+            Self::None => "none",
+            Self::Any => "any",
+            Self::Elision => "elision",
+            Self::Other(name) => name,
+            Self::OneOf(types) => {
+                let names: Vec<Cow<'_, str>> = types.iter().map(RpcType::as_str).collect();
+                return Cow::Owned(names.join(" or "));
+            }
+        };
+        Cow::Borrowed(name)
+    }
+
+    /// Returns the member types of a union, or the type itself otherwise
+    pub fn alternatives(&self) -> &[RpcType] {
+        match self {
+            Self::OneOf(types) => types,
+            other => std::slice::from_ref(other),
+        }
+    }
+
+    /// Returns true for object types
+    pub fn is_object(&self) -> bool {
+        matches!(
+            self,
+            Self::Object | Self::ObjectDynamic | Self::ObjectUserKeys | Self::ObjectNamedParams
+        )
+    }
+
+    /// Returns true for array types
+    pub fn is_array(&self) -> bool { matches!(self, Self::Array | Self::ArrayFixed) }
+
+    /// Returns true for types represented as JSON numbers
+    pub fn is_numeric(&self) -> bool { matches!(self, Self::Number | Self::Amount | Self::Time) }
+
+    /// Returns true for types represented as JSON strings
+    pub fn is_string(&self) -> bool { matches!(self, Self::String | Self::Hex) }
+
+    /// Returns whether a JSON value has the shape of this type
+    ///
+    /// Amounts must be non-negative with at most eight decimals, as Bitcoin
+    /// Core parses them, whether given as JSON numbers or as decimal BTC
+    /// strings, and hex must be an even-length string of hex digits. `Any`,
+    /// `Elision` and `Other` accept every value.
+    pub fn matches(&self, value: &Value) -> bool { self.matches_amount_sign(value, false) }
+
+    /// Returns whether a JSON value has the shape of this type in a result
+    ///
+    /// Results may hold negative amounts, such as the fee of a transaction.
+    pub(crate) fn matches_result(&self, value: &Value) -> bool {
+        self.matches_amount_sign(value, true)
+    }
+
+    fn matches_amount_sign(&self, value: &Value, signed: bool) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Hex => value
+                .as_str()
+                .is_some_and(|s| s.len() % 2 == 0 && s.chars().all(|c| c.is_ascii_hexdigit())),
+            Self::Number => value.is_number(),
+            Self::Amount => {
+                let btc = match value {
+                    Value::Number(n) => n.as_f64().map(|btc| btc.to_string()),
+                    Value::String(s) => Some(s.clone()),
+                    _ => None,
+                };
+                btc.is_some_and(|btc| {
+                    if signed {
+                        SignedAmount::from_str_in(&btc, Denomination::Bitcoin).is_ok()
+                    } else {
+                        Amount::from_str_in(&btc, Denomination::Bitcoin).is_ok()
+                    }
+                })
+            }
+            Self::Time => value.is_u64() || value.is_i64(),
+            Self::Boolean => value.is_boolean(),
+            Self::Object | Self::ObjectDynamic | Self::ObjectUserKeys | Self::ObjectNamedParams =>
+                value.is_object(),
+            Self::Array | Self::ArrayFixed => value.is_array(),
+            Self::Range => match value {
+                Value::Number(n) => n.is_u64(),
+                Value::Array(bounds) => bounds.len() == 2 && bounds.iter().all(Value::is_u64),
+                _ => false,
+            },
+            Self::None => value.is_null(),
+            Self::Any | Self::Elision | Self::Other(_) => true,
+            Self::OneOf(types) =>
+                types.iter().any(|rpc_type| rpc_type.matches_amount_sign(value, signed)),
+        }
+    }
+}
+
+impl From<&str> for RpcType {
+    fn from(name: &str) -> Self {
+        let parts: Vec<&str> = name.split(" or ").flat_map(|part| part.split('|')).collect();
+        if parts.len() > 1 {
+            let mut types: Vec<RpcType> = Vec::new();
+            for rpc_type in parts.into_iter().map(|part| Self::from(part.trim())) {
+                if !types.contains(&rpc_type) {
+                    types.push(rpc_type);
+                }
+            }
+            return if types.len() == 1 { types.remove(0) } else { Self::OneOf(types) };
+        }
+
+        match name {
+            "string" => Self::String,
+            "number" | "numeric" => Self::Number,
+            "boolean" => Self::Boolean,
+            "object" => Self::Object,
+            "object_dynamic" => Self::ObjectDynamic,
+            "object_user_keys" => Self::ObjectUserKeys,
+            "object_named_params" => Self::ObjectNamedParams,
+            "array" => Self::Array,
+            "array_fixed" => Self::ArrayFixed,
+            "amount" => Self::Amount,
+            "hex" => Self::Hex,
+            "time" | "timestamp" => Self::Time,
+            "range" => Self::Range,
+            "none" => Self::None,
+            "any" => Self::Any,
+            "elision" => Self::Elision,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl FromStr for RpcType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Ok(Self::from(s)) }
+}
+
+impl fmt::Display for RpcType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.as_str()) }
+}
+
+impl Serialize for RpcType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::from(s.as_str()))
+    }
+}
+
+impl BtcArgument {
+    /// Returns the typed view of `type_`
+    ///
+    /// When `type_str` describes a union that `type_` does not (e.g. a
+    /// `hash_or_height` typed as `number` but documented as
+    /// `string or numeric`), the union is returned.
+    pub fn rpc_type(&self) -> RpcType {
+        let rpc_type = RpcType::from(self.type_.as_str());
+        let display = self.type_str.as_ref().and_then(|type_str| type_str.get(1));
+        match display.map(|display| RpcType::from(display.as_str())) {
+            Some(union @ RpcType::OneOf(_)) if !matches!(rpc_type, RpcType::OneOf(_)) => union,
+            _ => rpc_type,
+        }
+    }
+}
+
+impl BtcResult {
+    /// Returns the typed view of `type_`
+    pub fn rpc_type(&self) -> RpcType { RpcType::from(self.type_.as_str()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_type_parse() {
+        assert_eq!(RpcType::from("object"), RpcType::Object);
+        assert_eq!(RpcType::from("numeric"), RpcType::Number);
+        assert_eq!(RpcType::from("time"), RpcType::Time);
+        assert_eq!("elision".parse::<RpcType>().unwrap(), RpcType::Elision);
+        assert_eq!(RpcType::from("str_hex"), RpcType::Other("str_hex".to_string()));
+    }
+
+    #[test]
+    fn test_rpc_type_round_trip() {
+        for name in [
+            "string",
+            "number",
+            "boolean",
+            "object",
+            "object_dynamic",
+            "object_user_keys",
+            "object_named_params",
+            "array",
+            "array_fixed",
+            "amount",
+            "hex",
+            "timestamp",
+            "range",
+            "none",
+            "any",
+            "elision",
+            "custom",
+        ] {
+            assert_eq!(RpcType::from(name).as_str(), name);
+            let json = serde_json::to_string(&RpcType::from(name)).unwrap();
+            assert_eq!(serde_json::from_str::<RpcType>(&json).unwrap(), RpcType::from(name));
+        }
+    }
+
+    #[test]
+    fn test_rpc_type_predicates() {
+        assert!(RpcType::ObjectDynamic.is_object());
+        assert!(RpcType::ArrayFixed.is_array());
+        assert!(RpcType::Amount.is_numeric());
+        assert!(RpcType::Hex.is_string());
+        assert!(!RpcType::Any.is_object());
+    }
+
+    #[test]
+    fn test_rpc_type_matches() {
+        use serde_json::json;
+
+        assert!(RpcType::Hex.matches(&json!("00ff")));
+        assert!(!RpcType::Hex.matches(&json!("0xff")));
+        assert!(!RpcType::Hex.matches(&json!("abc")));
+        assert!(RpcType::Amount.matches(&json!(0.1)));
+        assert!(RpcType::Amount.matches(&json!("0.1")));
+        for invalid in ["NaN", "inf", "-1e400", "0.000000001", "-0.1", ""] {
+            assert!(!RpcType::Amount.matches(&json!(invalid)), "{}", invalid);
+        }
+        assert!(!RpcType::Amount.matches(&json!(-1)));
+        assert!(!RpcType::Amount.matches(&json!(0.000000001)));
+        assert!(RpcType::Amount.matches_result(&json!(-1)));
+        assert!(RpcType::Amount.matches_result(&json!("-0.1")));
+        assert!(!RpcType::Amount.matches_result(&json!(-0.000000001)));
+        assert!(!RpcType::Amount.matches(&json!(true)));
+        assert!(RpcType::Range.matches(&json!(100)));
+        assert!(RpcType::Range.matches(&json!([0, 100])));
+        assert!(!RpcType::Range.matches(&json!([-1, 100])));
+        assert!(RpcType::ObjectUserKeys.matches(&json!({ "addr": 1 })));
+        assert!(!RpcType::Boolean.matches(&json!("true")));
+        assert!(RpcType::Other("custom".to_string()).matches(&json!(null)));
+    }
+
+    #[test]
+    fn test_rpc_type_union() {
+        use serde_json::json;
+
+        let union = RpcType::from("string or numeric");
+        assert_eq!(union, RpcType::OneOf(vec![RpcType::String, RpcType::Number]));
+        assert_eq!(union.as_str(), "string or number");
+        assert_eq!(
+            RpcType::from("hex|number"),
+            RpcType::OneOf(vec![RpcType::Hex, RpcType::Number])
+        );
+        assert_eq!(RpcType::from("number or numeric"), RpcType::Number);
+        assert_eq!(union.alternatives().len(), 2);
+        assert_eq!(RpcType::Hex.alternatives(), &[RpcType::Hex]);
+
+        assert!(union.matches(&json!("00ff")));
+        assert!(union.matches(&json!(100)));
+        assert!(!union.matches(&json!(true)));
+
+        let json = serde_json::to_string(&union).unwrap();
+        assert_eq!(json, "\"string or number\"");
+        assert_eq!(serde_json::from_str::<RpcType>(&json).unwrap(), union);
+
+        let hash_or_height = BtcArgument {
+            type_: "number".to_string(),
+            type_str: Some(vec!["".to_string(), "string or numeric".to_string()]),
+            ..BtcArgument::default()
+        };
+        assert_eq!(hash_or_height.rpc_type(), union);
+        let round_trip: BtcArgument =
+            serde_json::from_value(serde_json::to_value(&hash_or_height).unwrap()).unwrap();
+        assert_eq!(round_trip, hash_or_height);
+    }
+
+    #[test]
+    fn test_rpc_type_accessors() {
+        let result = BtcResult { type_: "object".to_string(), ..BtcResult::default() };
+        assert_eq!(result.rpc_type(), RpcType::Object);
+        let arg = BtcArgument { type_: "amount".to_string(), ..BtcArgument::default() };
+        assert_eq!(arg.rpc_type(), RpcType::Amount);
+    }
+}
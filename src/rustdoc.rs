@@ -0,0 +1,287 @@
+//! Rustdoc comment generation
+//!
+//! Schema descriptions are `help` text laid out for a terminal. Pasted into
+//! `///` comments they break rustdoc: indented lines become doctests,
+//! `[..]` intra-doc links and `<..>` HTML tags. This module turns them into
+//! comment blocks that render as written, for any generator emitting Rust.
+
+use std::fmt::Write;
+
+use crate::types::{BtcArgument, BtcMethod};
+
+/// Maximum length of generated comment lines, including indentation
+pub const DOC_WIDTH: usize = 100;
+
+/// Formats text as `///` lines with the given indentation
+///
+/// Prose is escaped and wrapped to `DOC_WIDTH`. Runs of indented lines and
+/// `> ` example prompts are preformatted and are fenced as `text` blocks, so
+/// rustdoc neither reflows nor compiles them.
+pub fn doc_comment(text: &str, indent: &str) -> String {
+    comment_lines(&markdown(text, DOC_WIDTH.saturating_sub(indent.len() + 4)), indent)
+}
+
+/// Escapes characters that rustdoc would read as markdown links, HTML or
+/// headings
+///
+/// Bare URLs are wrapped in `<..>` so they become links.
+pub fn escape_markdown(line: &str) -> String {
+    let mut escaped = String::new();
+    for (index, word) in line.split(' ').enumerate() {
+        if index > 0 {
+            escaped.push(' ');
+        }
+        if word.starts_with("http://") || word.starts_with("https://") {
+            let url = word.trim_end_matches(['.', ',', ')', ';', ':']);
+            write!(escaped, "<{}>{}", url, &word[url.len()..]).unwrap();
+            continue;
+        }
+        for c in word.chars() {
+            if matches!(c, '[' | ']' | '<' | '>' | '\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+    match escaped.starts_with('#') {
+        true => format!("\\{}", escaped),
+        false => escaped,
+    }
+}
+
+impl BtcArgument {
+    /// Formats the argument as a markdown list item for an `# Arguments` section
+    ///
+    /// The description is collapsed onto one line, followed by the default
+    /// value if there is one.
+    pub fn rustdoc_item(&self) -> String {
+        let name = self.names.first().map(String::as_str).unwrap_or_default();
+        let mut item = format!("* `{}`", name);
+        let description = self.description.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !description.is_empty() {
+            write!(item, " - {}", escape_markdown(&description)).unwrap();
+        }
+        if let Some(default) = &self.default {
+            write!(item, " (default: `{}`)", default).unwrap();
+        }
+        item
+    }
+}
+
+impl BtcMethod {
+    /// Formats the method's documentation as `///` lines
+    ///
+    /// The description is cut at the `Arguments:`, `Result:` and `Examples:`
+    /// sections of the help text, which are replaced by an `# Arguments`
+    /// list built from the argument docs and an `# Examples` section with
+    /// the examples fenced.
+    pub fn rustdoc(&self, indent: &str) -> String {
+        let width = DOC_WIDTH.saturating_sub(indent.len() + 4);
+        let description: Vec<&str> =
+            self.description.lines().take_while(|line| !is_help_section(line)).collect();
+        let mut lines = markdown(&description.join("\n"), width);
+
+        let arguments: Vec<&BtcArgument> =
+            self.arguments.iter().filter(|arg| !arg.hidden).collect();
+        if !arguments.is_empty() {
+            lines.extend(["".to_string(), "# Arguments".to_string(), "".to_string()]);
+            for arg in arguments {
+                lines.extend(wrap(&arg.rustdoc_item(), width, "  "));
+            }
+        }
+
+        let examples: Vec<&str> =
+            self.examples.lines().filter(|line| !is_help_section(line)).collect();
+        let examples = markdown(&examples.join("\n"), width);
+        if !examples.is_empty() {
+            lines.extend(["".to_string(), "# Examples".to_string(), "".to_string()]);
+            lines.extend(examples);
+        }
+
+        while lines.first().is_some_and(String::is_empty) {
+            lines.remove(0);
+        }
+        comment_lines(&lines, indent)
+    }
+}
+
+/// Whether a line starts a section of the help text (`Result (verbose=true):`)
+fn is_help_section(line: &str) -> bool {
+    let line = line.trim();
+    line == "Arguments:"
+        || line == "Examples:"
+        || (line.starts_with("Result") && line.ends_with(':') && !line.contains(". "))
+}
+
+/// Converts help text into markdown lines of at most `width` characters
+fn markdown(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if is_preformatted(line) {
+            block.push(line);
+            continue;
+        }
+        if !block.is_empty() {
+            fence(&mut lines, &std::mem::take(&mut block));
+        }
+        match line.is_empty() {
+            true => lines.push(String::new()),
+            false => lines.extend(wrap(&escape_markdown(line.trim()), width, "")),
+        }
+    }
+    if !block.is_empty() {
+        fence(&mut lines, &block);
+    }
+
+    // Collapse blank runs and trim the ends
+    lines.dedup_by(|line, previous| line.is_empty() && previous.is_empty());
+    while lines.first().is_some_and(String::is_empty) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Whether a line is laid out by hand rather than prose
+fn is_preformatted(line: &str) -> bool {
+    (line.starts_with(char::is_whitespace) && !line.trim().is_empty()) || line.starts_with("> ")
+}
+
+/// Appends a block of preformatted lines as a fenced `text` block
+fn fence(lines: &mut Vec<String>, block: &[&str]) {
+    // The fence must be longer than any backtick run inside the block
+    let longest = block.iter().map(|line| longest_run(line, '`')).max().unwrap_or_default();
+    let fence = "`".repeat(longest.max(2) + 1);
+    if lines.last().is_some_and(|line| !line.is_empty()) {
+        lines.push(String::new());
+    }
+    lines.push(format!("{}text", fence));
+    lines.extend(block.iter().map(|line| line.to_string()));
+    lines.push(fence);
+    lines.push(String::new());
+}
+
+fn longest_run(line: &str, c: char) -> usize {
+    line.split(|other| other != c).map(str::len).max().unwrap_or_default()
+}
+
+/// Wraps a line at spaces, indenting continuation lines by `hanging`
+///
+/// Words that would start a continuation line as a markdown list item or
+/// heading stay on the previous line.
+fn wrap(line: &str, width: usize, hanging: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ').filter(|word| !word.is_empty()) {
+        let fits = current.len() + 1 + word.len() <= width;
+        if current.trim().is_empty() {
+            current.push_str(word);
+        } else if fits || starts_block(word) {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::replace(&mut current, format!("{}{}", hanging, word)));
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+/// Whether a word at the start of a line would begin a list or heading
+fn starts_block(word: &str) -> bool {
+    matches!(word, "-" | "+" | "*" | ">")
+        || word.starts_with('#')
+        || word
+            .strip_suffix(['.', ')'])
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Prefixes markdown lines with the indentation and `///`
+fn comment_lines(lines: &[String], indent: &str) -> String {
+    let mut comment = String::new();
+    for line in lines {
+        match line.is_empty() {
+            true => writeln!(comment, "{}///", indent).unwrap(),
+            false => writeln!(comment, "{}/// {}", indent, line).unwrap(),
+        }
+    }
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(escape_markdown("[txid,...] <n>"), "\\[txid,...\\] \\<n\\>");
+        assert_eq!(escape_markdown("# of blocks"), "\\# of blocks");
+        assert_eq!(
+            escape_markdown("See https://bitcoincore.org/en/doc/."),
+            "See <https://bitcoincore.org/en/doc/>."
+        );
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        let text =
+            "\nReturns an object.\n\n  {\n    \"hash\" : \"hex\"\n  }\nUse [verbose] for more.\n";
+        assert_eq!(
+            doc_comment(text, "    "),
+            "    /// Returns an object.\n    ///\n    /// ```text\n    ///   {\n    ///     \"hash\" : \"hex\"\n    ///   }\n    /// ```\n    ///\n    /// Use \\[verbose\\] for more.\n"
+        );
+
+        let long = "word ".repeat(30) + "- item";
+        let comment = doc_comment(&long, "");
+        assert!(comment.lines().all(|line| line.len() <= DOC_WIDTH));
+        assert_eq!(comment.lines().count(), 2);
+        assert!(comment.ends_with("word - item\n"), "{}", comment);
+        assert_eq!(doc_comment("\n\n", ""), "");
+    }
+
+    #[test]
+    fn test_method_rustdoc() {
+        let method = BtcMethod {
+            name: "getblockhash".to_string(),
+            description: "\nReturns hash of block at height provided.\n\nArguments:\n1. height (numeric, required)\n"
+                .to_string(),
+            examples: "\nExamples:\n> bitcoin-cli getblockhash 1000\n> curl --data-binary '{\"method\": \"getblockhash\"}' http://127.0.0.1:8332/\n"
+                .to_string(),
+            arguments: vec![
+                BtcArgument {
+                    names: vec!["height".to_string()],
+                    description: "The height index".to_string(),
+                    ..BtcArgument::default()
+                },
+                BtcArgument {
+                    names: vec!["verbose".to_string()],
+                    default: Some(json!(false)),
+                    ..BtcArgument::default()
+                },
+            ],
+            ..BtcMethod::default()
+        };
+        assert_eq!(
+            method.rustdoc(""),
+            r#"/// Returns hash of block at height provided.
+///
+/// # Arguments
+///
+/// * `height` - The height index
+/// * `verbose` (default: `false`)
+///
+/// # Examples
+///
+/// ```text
+/// > bitcoin-cli getblockhash 1000
+/// > curl --data-binary '{"method": "getblockhash"}' http://127.0.0.1:8332/
+/// ```
+"#
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! `scantxoutset` request and result types
+//!
+//! This module provides the `ScanAction` and `ScanObject` parameters of
+//! `scantxoutset` and the `ScanTxOutSetResult` returned by a finished scan.
+
+use bitcoin::{Amount, BlockHash, ScriptBuf, Txid};
+use serde::{Deserialize, Serialize};
+
+use crate::address_or_descriptor::DescriptorWithRange;
+
+/// Action of a `scantxoutset` call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanAction {
+    /// Start a scan; returns a `ScanTxOutSetResult` when it finishes
+    Start,
+    /// Abort the running scan
+    Abort,
+    /// Report the progress of the running scan
+    Status,
+}
+
+/// Output descriptor to scan for, with a range for ranged descriptors
+///
+/// Addresses are scanned for as `addr(...)` descriptors, which
+/// `From<AddressOrDescriptor>` produces.
+pub type ScanObject = DescriptorWithRange;
+
+/// Result of a finished `scantxoutset start`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanTxOutSetResult {
+    /// Whether the scan was completed
+    pub success: bool,
+    /// Number of unspent transaction outputs scanned
+    pub txouts: u64,
+    /// Height of the block the scan was done at
+    pub height: u64,
+    /// Hash of the block the scan was done at
+    pub bestblock: BlockHash,
+    /// Unspent outputs matching the scan objects
+    pub unspents: Vec<ScanUnspent>,
+    /// Total amount of the matching outputs
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub total_amount: Amount,
+}
+
+/// Unspent output found by `scantxoutset`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanUnspent {
+    /// Id of the transaction creating the output
+    pub txid: Txid,
+    /// Index of the output in the transaction
+    pub vout: u32,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: ScriptBuf,
+    /// Inferred descriptor for the output
+    pub desc: String,
+    /// Amount of the output
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: Amount,
+    /// Whether the output is a coinbase output (Bitcoin Core 25+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<bool>,
+    /// Height of the block containing the output
+    pub height: u64,
+    /// Hash of the block containing the output (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Number of confirmations of the output (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::address_or_descriptor::{AddressOrDescriptor, DescriptorRange};
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_scan_params() {
+        assert_eq!(serde_json::to_value(ScanAction::Start).unwrap(), json!("start"));
+        assert_eq!(
+            serde_json::from_value::<ScanAction>(json!("status")).unwrap(),
+            ScanAction::Status
+        );
+
+        let objects = vec![
+            ScanObject::new("addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)"),
+            ScanObject::new("wpkh(xpub/0/*)").with_range(DescriptorRange::Bounds(0, 99)),
+        ];
+        let value = serde_json::to_value(&objects).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                { "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)" },
+                { "desc": "wpkh(xpub/0/*)", "range": [0, 99] },
+            ])
+        );
+        assert_eq!(serde_json::from_value::<Vec<ScanObject>>(value).unwrap(), objects);
+
+        let address = serde_json::from_value::<AddressOrDescriptor>(json!(
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+        ))
+        .unwrap();
+        assert_eq!(ScanObject::from(address), objects[0]);
+    }
+
+    #[test]
+    fn test_scan_txout_set_result() {
+        let value = json!({
+            "success": true,
+            "txouts": 185_000_000,
+            "height": 840_000,
+            "bestblock": HASH,
+            "unspents": [{
+                "txid": TXID,
+                "vout": 0,
+                "scriptPubKey": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)#0000000",
+                "amount": 0.5,
+                "coinbase": false,
+                "height": 839_000,
+            }],
+            "total_amount": 0.5,
+        });
+        let result: ScanTxOutSetResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(result.total_amount, Amount::from_sat(50_000_000));
+        assert_eq!(result.unspents[0].txid, TXID.parse().unwrap());
+        assert_eq!(result.unspents[0].blockhash, None);
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+    }
+}
@@ -0,0 +1,166 @@
+//! Search over methods, arguments and result keys
+//!
+//! Case-insensitive substring search across an `ApiDefinition`, returning
+//! typed match locations.
+
+use crate::types::ApiDefinition;
+
+/// Where a search query matched within a method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchLocation {
+    /// The method name
+    MethodName,
+    /// The method description
+    Description,
+    /// One of the names of an argument
+    Argument {
+        /// Position of the argument
+        index: usize,
+        /// The matching argument name
+        name: String,
+    },
+    /// The key name of a result node
+    ResultKey {
+        /// `/`-separated path of the result node (top-level results by index,
+        /// nested results by key name or index)
+        path: String,
+    },
+}
+
+/// A single search hit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Name of the method containing the match
+    pub method: String,
+    /// Location of the match within the method
+    pub location: MatchLocation,
+}
+
+impl ApiDefinition {
+    /// Searches method names, descriptions, argument names and nested result
+    /// key names for `query` (case-insensitive substring match)
+    ///
+    /// Matches are ordered by method name, then by location in the order listed
+    /// in `MatchLocation`.
+    pub fn search(&self, query: &str) -> Vec<SearchMatch> {
+        let query = query.to_lowercase();
+        let matches = |text: &str| !query.is_empty() && text.to_lowercase().contains(&query);
+        let mut hits = Vec::new();
+
+        for method in self.rpcs.values() {
+            let mut push =
+                |location| hits.push(SearchMatch { method: method.name.clone(), location });
+
+            if matches(&method.name) {
+                push(MatchLocation::MethodName);
+            }
+            if matches(&method.description) {
+                push(MatchLocation::Description);
+            }
+            for (index, arg) in method.arguments.iter().enumerate() {
+                if let Some(name) = arg.names.iter().find(|name| matches(name)) {
+                    push(MatchLocation::Argument { index, name: name.clone() });
+                }
+            }
+            for (path, result) in method.iter_results_deep() {
+                if matches(&result.key_name) {
+                    push(MatchLocation::ResultKey { path });
+                }
+            }
+        }
+
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BtcArgument, BtcMethod, BtcResult};
+
+    fn result(key_name: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            type_: if inner.is_empty() { "string" } else { "object" }.to_string(),
+            key_name: key_name.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    fn api() -> ApiDefinition {
+        let mut api_def = ApiDefinition::new();
+        api_def
+            .insert_method(BtcMethod {
+                name: "getblockchaininfo".to_string(),
+                description: "Returns an object containing various state info.".to_string(),
+                results: vec![result(
+                    "",
+                    vec![result("chain", vec![]), result("warnings", vec![])],
+                )],
+                ..BtcMethod::default()
+            })
+            .unwrap();
+        api_def
+            .insert_method(BtcMethod {
+                name: "getnetworkinfo".to_string(),
+                description: "Returns P2P networking state info.".to_string(),
+                results: vec![result(
+                    "",
+                    vec![result("networks", vec![result("", vec![result("name", vec![])])])],
+                )],
+                ..BtcMethod::default()
+            })
+            .unwrap();
+        api_def
+            .insert_method(BtcMethod {
+                name: "getblock".to_string(),
+                argument_names: vec!["blockhash".to_string()],
+                arguments: vec![BtcArgument {
+                    names: vec!["blockhash".to_string()],
+                    ..BtcArgument::default()
+                }],
+                ..BtcMethod::default()
+            })
+            .unwrap();
+        api_def
+    }
+
+    #[test]
+    fn test_search_result_keys() {
+        let hits = api().search("warnings");
+        assert_eq!(
+            hits,
+            vec![SearchMatch {
+                method: "getblockchaininfo".to_string(),
+                location: MatchLocation::ResultKey { path: "0/warnings".to_string() },
+            }]
+        );
+
+        let hits = api().search("NAME");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].location,
+            MatchLocation::ResultKey { path: "0/networks/0/name".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_search_names_descriptions_and_arguments() {
+        let hits = api().search("block");
+        let locations: Vec<(&str, &MatchLocation)> =
+            hits.iter().map(|hit| (hit.method.as_str(), &hit.location)).collect();
+        assert_eq!(
+            locations,
+            vec![
+                ("getblock", &MatchLocation::MethodName),
+                ("getblock", &MatchLocation::Argument { index: 0, name: "blockhash".to_string() }),
+                ("getblockchaininfo", &MatchLocation::MethodName),
+            ]
+        );
+
+        let hits = api().search("state info");
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|hit| hit.location == MatchLocation::Description));
+        assert!(api().search("").is_empty());
+    }
+}
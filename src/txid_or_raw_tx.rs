@@ -0,0 +1,199 @@
+//! Txid or raw transaction type for Bitcoin RPC APIs
+//!
+//! This module provides the `TxidOrRawTx` enum which represents either a
+//! transaction id (bitcoin::Txid) or a serialized transaction for Bitcoin RPC
+//! APIs where a transaction can be referred to either way.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::consensus::encode;
+use bitcoin::hex::{DisplayHex, FromHex};
+use bitcoin::{Transaction, Txid};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Size in bytes of the smallest serialized transaction: version, empty
+/// input and output counts and lock time
+pub const MIN_RAW_TX_SIZE: usize = 10;
+
+/// Represents either a transaction id or a serialized transaction
+///
+/// Both are sent as hex strings; 64 hex characters are a txid, longer
+/// strings a raw transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TxidOrRawTx {
+    /// Transaction id
+    Txid(Txid),
+    /// Consensus-serialized transaction
+    RawTx(Vec<u8>),
+}
+
+impl TxidOrRawTx {
+    /// Returns true if this represents a transaction id
+    pub fn is_txid(&self) -> bool { matches!(self, Self::Txid(_)) }
+
+    /// Returns true if this represents a serialized transaction
+    pub fn is_raw_tx(&self) -> bool { matches!(self, Self::RawTx(_)) }
+
+    /// Returns the transaction id if this is a Txid variant, otherwise None
+    pub fn as_txid(&self) -> Option<&Txid> {
+        if let Self::Txid(txid) = self {
+            Some(txid)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the serialized transaction if this is a RawTx variant, otherwise None
+    pub fn as_raw_tx(&self) -> Option<&[u8]> {
+        if let Self::RawTx(bytes) = self {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a serialized transaction from hex
+    ///
+    /// Fails unless the hex is valid and at least `MIN_RAW_TX_SIZE` bytes
+    /// long. The bytes are not decoded, so partial transactions are accepted.
+    pub fn raw_tx_from_hex(hex: &str) -> std::result::Result<Self, ParseTxidOrRawTxError> {
+        let bytes =
+            Vec::from_hex(hex).map_err(|_| ParseTxidOrRawTxError::InvalidHex(hex.to_string()))?;
+        if bytes.len() < MIN_RAW_TX_SIZE {
+            return Err(ParseTxidOrRawTxError::TooShort(hex.to_string()));
+        }
+        Ok(Self::RawTx(bytes))
+    }
+
+    /// Returns the JSON value sent for this in RPC parameters
+    pub fn to_json_value(&self) -> Value { Value::String(self.to_string()) }
+}
+
+impl fmt::Display for TxidOrRawTx {
+    /// Formats either form as lowercase hex
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Txid(txid) => write!(f, "{}", txid),
+            Self::RawTx(bytes) => write!(f, "{}", bytes.as_hex()),
+        }
+    }
+}
+
+/// Error returned when parsing a `TxidOrRawTx` fails
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseTxidOrRawTxError {
+    /// Not a string of hex digits, or an odd number of them
+    #[error("Invalid hex: {0:?}")]
+    InvalidHex(String),
+    /// Valid hex, but neither a txid nor long enough for a transaction
+    #[error("Too short for a serialized transaction: {0:?}")]
+    TooShort(String),
+}
+
+impl FromStr for TxidOrRawTx {
+    type Err = ParseTxidOrRawTxError;
+
+    /// Parses 64 hex characters as a txid and longer hex as a serialized
+    /// transaction
+    ///
+    /// Surrounding whitespace is ignored.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.len() == 64 {
+            return trimmed
+                .parse()
+                .map(Self::Txid)
+                .map_err(|_| ParseTxidOrRawTxError::InvalidHex(s.to_string()));
+        }
+        Self::raw_tx_from_hex(trimmed).map_err(|err| match err {
+            ParseTxidOrRawTxError::InvalidHex(_) =>
+                ParseTxidOrRawTxError::InvalidHex(s.to_string()),
+            ParseTxidOrRawTxError::TooShort(_) => ParseTxidOrRawTxError::TooShort(s.to_string()),
+        })
+    }
+}
+
+impl TryFrom<Value> for TxidOrRawTx {
+    type Error = ParseTxidOrRawTxError;
+
+    /// Converts a JSON string as `FromStr`
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
+        match value {
+            Value::String(hex) => hex.parse(),
+            value => Err(ParseTxidOrRawTxError::InvalidHex(value.to_string())),
+        }
+    }
+}
+
+impl Serialize for TxidOrRawTx {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxidOrRawTx {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+impl From<Txid> for TxidOrRawTx {
+    fn from(txid: Txid) -> Self { Self::Txid(txid) }
+}
+
+impl From<&Transaction> for TxidOrRawTx {
+    fn from(tx: &Transaction) -> Self { Self::RawTx(encode::serialize(tx)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    const RAW_TX: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d0104ffffffff0100f2052a0100000043410496b538e853519c726a2c91e61ec11600ae1390813a627c66fb8be7947be63c52da7589379515d4e0a604f8141781e62294721166bf621e73a82cbf2342c858eeac00000000";
+
+    #[test]
+    fn test_txid_or_raw_tx_from_str() {
+        let txid: TxidOrRawTx = TXID.parse().unwrap();
+        assert!(txid.is_txid());
+        assert_eq!(txid.as_txid(), Some(&TXID.parse().unwrap()));
+        assert_eq!(txid.to_string(), TXID);
+
+        let raw: TxidOrRawTx = RAW_TX.parse().unwrap();
+        assert!(raw.is_raw_tx());
+        assert_eq!(raw.as_raw_tx().map(<[u8]>::len), Some(RAW_TX.len() / 2));
+        assert_eq!(raw.to_string(), RAW_TX);
+
+        let tx: Transaction = encode::deserialize_hex(RAW_TX).unwrap();
+        assert_eq!(TxidOrRawTx::from(&tx), raw);
+
+        assert_eq!(
+            "zz".repeat(32).parse::<TxidOrRawTx>(),
+            Err(ParseTxidOrRawTxError::InvalidHex("zz".repeat(32)))
+        );
+        assert_eq!(
+            "abc".parse::<TxidOrRawTx>(),
+            Err(ParseTxidOrRawTxError::InvalidHex("abc".to_string()))
+        );
+        assert_eq!(
+            "00ff".parse::<TxidOrRawTx>(),
+            Err(ParseTxidOrRawTxError::TooShort("00ff".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_txid_or_raw_tx_serde() {
+        let raw = TxidOrRawTx::raw_tx_from_hex(RAW_TX).unwrap();
+        assert_eq!(serde_json::to_value(&raw).unwrap(), serde_json::json!(RAW_TX));
+        assert_eq!(raw.to_json_value(), serde_json::json!(RAW_TX));
+        assert_eq!(serde_json::from_value::<TxidOrRawTx>(serde_json::json!(RAW_TX)).unwrap(), raw);
+        assert_eq!(TxidOrRawTx::try_from(serde_json::json!(TXID)).unwrap().to_string(), TXID);
+
+        assert!(serde_json::from_str::<TxidOrRawTx>("\"00ff\"").is_err());
+        assert!(serde_json::from_str::<TxidOrRawTx>("1").is_err());
+        assert!(TxidOrRawTx::try_from(Value::Null).is_err());
+    }
+}
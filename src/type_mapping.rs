@@ -0,0 +1,379 @@
+//! Mapping from schema types to Rust types
+//!
+//! `TypeMapping` resolves the Rust type used to represent an argument or
+//! result, based on its `type`, `type_str` and name, so code generators share
+//! one mapping instead of each reimplementing it. Types are returned as fully
+//! qualified paths (e.g. `bitcoin::Txid`); optionality is left to the caller.
+//!
+//! Generators take any `TypeMapper`, so consumers can also wrap or replace
+//! `TypeMapping` entirely.
+
+use std::collections::BTreeMap;
+
+use crate::fee_rate::FeeRateUnit;
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcResult};
+
+/// Fallback for values without a more precise mapping
+const JSON_VALUE: &str = "serde_json::Value";
+
+/// Type of fee rate arguments and options, whose unit depends on the name
+pub(crate) const FEE_RATE_PARAM: &str = "bitcoin_rpc_types::FeeRateParam";
+
+/// Maps schema nodes to Rust type paths
+///
+/// Nodes are identified by a dotted path: the method name followed by the
+/// argument name, or by the key names leading to a result node
+/// (`getblockchaininfo.bestblockhash`, `getblock.tx.txid`). Array elements
+/// and map values add no segment.
+pub trait TypeMapper {
+    /// Returns the Rust type for an argument, or a field of one
+    fn argument_type(&self, path: &str, arg: &BtcArgument) -> String;
+
+    /// Returns the Rust type for a result node
+    fn result_type(&self, path: &str, result: &BtcResult) -> String;
+}
+
+/// Resolves schema types to Rust type paths
+///
+/// Resolution order is: path overrides, name overrides, type overrides,
+/// built-in name rules (e.g. `txid` → `bitcoin::Txid`), then built-in type
+/// rules. Numbers are `u64` unless their name is known to be reported as -1
+/// (`startingheight`) or with a fractional part (`pingtime`). Amounts are
+/// `bitcoin::Amount` in arguments and `bitcoin::SignedAmount` in results,
+/// where e.g. `gettransaction` reports negative amounts and fees.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TypeMapping {
+    types: BTreeMap<String, String>,
+    names: BTreeMap<String, String>,
+    argument_paths: BTreeMap<String, String>,
+    result_paths: BTreeMap<String, String>,
+}
+
+impl TypeMapping {
+    /// Creates a mapping with only the built-in rules
+    pub fn new() -> Self { Self::default() }
+
+    /// Maps every value of a schema type (e.g. `amount`) to a Rust type
+    pub fn with_type_override(mut self, type_: impl Into<String>, rust: impl Into<String>) -> Self {
+        self.types.insert(type_.into(), rust.into());
+        self
+    }
+
+    /// Maps every argument or result key with this name to a Rust type
+    pub fn with_name_override(mut self, name: impl Into<String>, rust: impl Into<String>) -> Self {
+        self.names.insert(name.into(), rust.into());
+        self
+    }
+
+    /// Maps one argument, by dotted path (`getblock.blockhash`), to a Rust type
+    pub fn with_argument_path_override(
+        mut self,
+        path: impl Into<String>,
+        rust: impl Into<String>,
+    ) -> Self {
+        self.argument_paths.insert(path.into(), rust.into());
+        self
+    }
+
+    /// Maps one result node, by dotted path (`getblockchaininfo.bestblockhash`),
+    /// to a Rust type
+    pub fn with_result_path_override(
+        mut self,
+        path: impl Into<String>,
+        rust: impl Into<String>,
+    ) -> Self {
+        self.result_paths.insert(path.into(), rust.into());
+        self
+    }
+
+    /// Returns the Rust type for an argument, without path overrides
+    pub fn argument_type(&self, arg: &BtcArgument) -> String { self.resolve_argument(None, arg) }
+
+    /// Returns the Rust type for a result node, without path overrides
+    pub fn result_type(&self, result: &BtcResult) -> String { self.resolve_result(None, result) }
+
+    fn resolve_argument(&self, path: Option<&str>, arg: &BtcArgument) -> String {
+        if let Some(rust) = path.and_then(|path| self.argument_paths.get(path)) {
+            return rust.clone();
+        }
+        let name = arg.names.first().map(String::as_str).unwrap_or_default();
+        if let Some(rust) = self.names.get(name).or_else(|| self.types.get(&arg.type_)) {
+            return rust.clone();
+        }
+
+        if let Some(rust) = builtin_name_type(name, &arg.rpc_type()) {
+            return rust.to_string();
+        }
+
+        // Unions and untyped values have no single Rust type
+        match arg.rpc_type() {
+            RpcType::Array => match arg.inner.as_slice() {
+                [item] => format!("Vec<{}>", self.resolve_argument(path, item)),
+                _ => format!("Vec<{}>", JSON_VALUE),
+            },
+            RpcType::ObjectUserKeys => match arg.inner.first() {
+                Some(value) => format!(
+                    "std::collections::BTreeMap<String, {}>",
+                    self.resolve_argument(path, value)
+                ),
+                None => format!("std::collections::BTreeMap<String, {}>", JSON_VALUE),
+            },
+            rpc_type => builtin_type(&rpc_type).to_string(),
+        }
+    }
+
+    fn resolve_result(&self, path: Option<&str>, result: &BtcResult) -> String {
+        if let Some(rust) = path.and_then(|path| self.result_paths.get(path)) {
+            return rust.clone();
+        }
+        if let Some(rust) =
+            self.names.get(&result.key_name).or_else(|| self.types.get(&result.type_))
+        {
+            return rust.clone();
+        }
+        if let Some(rust) = builtin_name_type(&result.key_name, &result.rpc_type()) {
+            return rust.to_string();
+        }
+
+        if result.is_map() {
+            let value = result.map_value().map(|value| self.resolve_result(path, value));
+            return format!(
+                "std::collections::BTreeMap<String, {}>",
+                value.as_deref().unwrap_or(JSON_VALUE)
+            );
+        }
+
+        let items: Vec<&BtcResult> =
+            result.inner.iter().filter(|inner| !inner.is_elision()).collect();
+        match result.rpc_type() {
+            RpcType::Array => match items.as_slice() {
+                [item] => format!("Vec<{}>", self.resolve_result(path, item)),
+                _ => format!("Vec<{}>", JSON_VALUE),
+            },
+            RpcType::ArrayFixed => format!(
+                "({},)",
+                items
+                    .iter()
+                    .map(|item| self.resolve_result(path, item))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            // Wallet results report outgoing amounts and fees as negative
+            RpcType::Amount => "bitcoin::SignedAmount".to_string(),
+            rpc_type => builtin_type(&rpc_type).to_string(),
+        }
+    }
+}
+
+impl TypeMapper for TypeMapping {
+    fn argument_type(&self, path: &str, arg: &BtcArgument) -> String {
+        self.resolve_argument(Some(path), arg)
+    }
+
+    fn result_type(&self, path: &str, result: &BtcResult) -> String {
+        self.resolve_result(Some(path), result)
+    }
+}
+
+/// Appends a key name to a dotted node path, as passed to `TypeMapper`
+///
+/// An empty key (array element or map value) leaves the path unchanged.
+pub fn child_path(path: &str, key: &str) -> String {
+    match (path.is_empty(), key.is_empty()) {
+        (_, true) => path.to_string(),
+        (true, false) => key.to_string(),
+        (false, false) => format!("{}.{}", path, key),
+    }
+}
+
+/// Rust types implied by well-known argument and key names
+fn builtin_name_type(name: &str, rpc_type: &RpcType) -> Option<&'static str> {
+    // Fee rates come in sat/vB or BTC/kvB depending on the name, so they
+    // need the unit-aware `FeeRateParam` rather than an amount
+    if matches!(rpc_type, RpcType::Amount | RpcType::Number)
+        && FeeRateUnit::for_argument(name).is_some()
+    {
+        return Some(FEE_RATE_PARAM);
+    }
+    let rust = match (name, rpc_type) {
+        ("txid", RpcType::Hex | RpcType::String) => "bitcoin::Txid",
+        ("wtxid", RpcType::Hex | RpcType::String) => "bitcoin::Wtxid",
+        (
+            "blockhash" | "bestblockhash" | "previousblockhash" | "nextblockhash",
+            RpcType::Hex | RpcType::String,
+        ) => "bitcoin::BlockHash",
+        ("hash_or_height", _) => "bitcoin_rpc_types::HashOrHeight",
+        ("address_type" | "change_type", RpcType::String) => "bitcoin_rpc_types::AddressType",
+        // May be negative, e.g. -1 for an unknown height or a conflicted transaction
+        (
+            "confirmations" | "timeoffset" | "startingheight" | "synced_headers" | "synced_blocks"
+            | "presynced_headers",
+            RpcType::Number,
+        ) => "i64",
+        // Reported with a fractional part
+        (
+            "difficulty"
+            | "verificationprogress"
+            | "networkhashps"
+            | "pingtime"
+            | "minping"
+            | "pingwait",
+            RpcType::Number,
+        ) => "f64",
+        _ => return None,
+    };
+    Some(rust)
+}
+
+/// Rust types for schema types that need no further context
+fn builtin_type(rpc_type: &RpcType) -> &'static str {
+    match rpc_type {
+        RpcType::String | RpcType::Hex => "String",
+        RpcType::Number | RpcType::Time => "u64",
+        RpcType::Amount => "bitcoin::Amount",
+        RpcType::Boolean => "bool",
+        RpcType::None => "()",
+        _ => JSON_VALUE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, type_: &str) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            ..BtcArgument::default()
+        }
+    }
+
+    fn result(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            key_name: key_name.to_string(),
+            type_: type_.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    #[test]
+    fn test_builtin_mapping() {
+        let mapping = TypeMapping::new();
+        assert_eq!(mapping.argument_type(&arg("txid", "hex")), "bitcoin::Txid");
+        assert_eq!(mapping.argument_type(&arg("blockhash", "hex")), "bitcoin::BlockHash");
+        assert_eq!(
+            mapping.argument_type(&arg("address_type", "string")),
+            "bitcoin_rpc_types::AddressType"
+        );
+        assert_eq!(mapping.argument_type(&arg("hexstring", "hex")), "String");
+        assert_eq!(
+            mapping.argument_type(&arg("fee_rate", "amount")),
+            "bitcoin_rpc_types::FeeRateParam"
+        );
+        assert_eq!(
+            mapping.argument_type(&arg("maxfeerate", "amount")),
+            "bitcoin_rpc_types::FeeRateParam"
+        );
+        assert_eq!(
+            mapping.result_type(&result("feeRate", "amount", vec![])),
+            "bitcoin_rpc_types::FeeRateParam"
+        );
+        assert_eq!(mapping.argument_type(&arg("verbosity", "number")), "u64");
+        assert_eq!(mapping.argument_type(&arg("options", "object")), "serde_json::Value");
+
+        let union = BtcArgument {
+            type_str: Some(vec!["".to_string(), "string or numeric".to_string()]),
+            ..arg("height_or_hash", "string")
+        };
+        assert_eq!(mapping.argument_type(&union), "serde_json::Value");
+
+        assert_eq!(mapping.result_type(&result("confirmations", "number", vec![])), "i64");
+        assert_eq!(mapping.result_type(&result("difficulty", "number", vec![])), "f64");
+        assert_eq!(mapping.result_type(&result("synced_headers", "number", vec![])), "i64");
+        assert_eq!(mapping.result_type(&result("minping", "number", vec![])), "f64");
+        assert_eq!(mapping.argument_type(&arg("amount", "amount")), "bitcoin::Amount");
+        assert_eq!(mapping.result_type(&result("fee", "amount", vec![])), "bitcoin::SignedAmount");
+        assert_eq!(mapping.result_type(&result("", "none", vec![])), "()");
+    }
+
+    #[test]
+    fn test_container_mapping() {
+        let mapping = TypeMapping::new();
+        let tx =
+            result("tx", "array", vec![result("", "hex", vec![]), result("", "elision", vec![])]);
+        assert_eq!(mapping.result_type(&tx), "Vec<String>");
+
+        let mempool = result(
+            "",
+            "object_dynamic",
+            vec![result("", "object", vec![]), result("", "elision", vec![])],
+        );
+        assert_eq!(
+            mapping.result_type(&mempool),
+            "std::collections::BTreeMap<String, serde_json::Value>"
+        );
+
+        let verbose_mempool = result(
+            "",
+            "object",
+            vec![BtcResult { dynamic_key: true, ..result("transactionid", "number", vec![]) }],
+        );
+        assert_eq!(
+            mapping.result_type(&verbose_mempool),
+            "std::collections::BTreeMap<String, u64>"
+        );
+
+        let outputs = BtcArgument {
+            inner: vec![arg("address", "amount")],
+            ..arg("outputs", "object_user_keys")
+        };
+        assert_eq!(
+            mapping.argument_type(&outputs),
+            "std::collections::BTreeMap<String, bitcoin::Amount>"
+        );
+
+        let txids = BtcArgument { inner: vec![arg("txid", "hex")], ..arg("txids", "array") };
+        assert_eq!(mapping.argument_type(&txids), "Vec<bitcoin::Txid>");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mapping = TypeMapping::new()
+            .with_type_override("amount", "f64")
+            .with_name_override("txid", "String")
+            .with_name_override("options", "crate::FundOptions");
+        assert_eq!(mapping.argument_type(&arg("fee_rate", "amount")), "f64");
+        assert_eq!(mapping.argument_type(&arg("txid", "hex")), "String");
+        assert_eq!(mapping.argument_type(&arg("options", "object")), "crate::FundOptions");
+        assert_eq!(mapping.result_type(&result("fee", "amount", vec![])), "f64");
+    }
+
+    #[test]
+    fn test_path_overrides() {
+        let mapping = TypeMapping::new()
+            .with_result_path_override("getblockchaininfo.chain", "bitcoin::Network")
+            .with_result_path_override("getblock.tx", "crate::BlockTxids")
+            .with_argument_path_override("getblock.verbosity", "u8");
+
+        let chain = result("chain", "string", vec![]);
+        assert_eq!(
+            TypeMapper::result_type(&mapping, "getblockchaininfo.chain", &chain),
+            "bitcoin::Network"
+        );
+        assert_eq!(TypeMapper::result_type(&mapping, "getmininginfo.chain", &chain), "String");
+        let tx = result("tx", "array", vec![result("", "hex", vec![])]);
+        assert_eq!(TypeMapper::result_type(&mapping, "getblock.tx", &tx), "crate::BlockTxids");
+        assert_eq!(mapping.result_type(&tx), "Vec<String>");
+
+        let verbosity = arg("verbosity", "number");
+        assert_eq!(TypeMapper::argument_type(&mapping, "getblock.verbosity", &verbosity), "u8");
+        assert_eq!(mapping.argument_type(&verbosity), "u64");
+
+        assert_eq!(child_path("getblock", "tx"), "getblock.tx");
+        assert_eq!(child_path("getblock.tx", ""), "getblock.tx");
+        assert_eq!(child_path("", "tx"), "tx");
+    }
+}
@@ -4,13 +4,25 @@
 //! definitions, arguments, and results.
 
 use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::Path;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::category::Category;
+use crate::lint::ensure_valid_method;
+use crate::rpc_type::RpcType;
+use crate::validate::ValidationError;
+use crate::version::CoreVersion;
+
 /// Bitcoin method argument specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Non-exhaustive so fields can be added as Core's help grows; build values
+/// with `BtcArgument::builder` or from `BtcArgument::default()`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BtcArgument {
     /// Names of the argument
     pub names: Vec<String>,
@@ -33,10 +45,82 @@ pub struct BtcArgument {
     /// Type of the argument
     #[serde(rename = "type")]
     pub type_: String,
+    /// Whether the argument is deprecated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Explanation of the deprecation, such as the replacement to use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_note: Option<String>,
+    /// Value used when an optional argument is omitted
+    ///
+    /// `None` when the argument has no default or the default is only
+    /// described in prose (e.g. `default=wallet -txconfirmtarget`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Smallest accepted numeric value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    /// Largest accepted numeric value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    /// Exhaustive set of accepted values; empty means unrestricted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<serde_json::Value>,
+    /// Fields of an option object, or the element of an array argument
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inner: Vec<BtcArgument>,
+}
+
+impl BtcArgument {
+    /// Parses a default as printed by Bitcoin Core help (`default=0`,
+    /// `default=false`, `default="unset"`, `default=[]`)
+    ///
+    /// The `default=` prefix is optional. Returns `None` for descriptive
+    /// defaults that are not JSON literals.
+    pub fn parse_default(text: &str) -> Option<serde_json::Value> {
+        let text = text.trim();
+        let literal = text.strip_prefix("default=").unwrap_or(text).trim();
+        serde_json::from_str(literal).ok()
+    }
+
+    /// Checks a value against the argument's type, bounds and allowed values
+    ///
+    /// Bounds apply to numbers and to amounts passed as numeric strings.
+    pub fn validate_value(&self, value: &serde_json::Value) -> Result<()> {
+        let name = self.names.first().map(String::as_str).unwrap_or_default();
+        let invalid = |reason: String| Err(SchemaError::InvalidValue(format!("{name}: {reason}")));
+
+        let rpc_type = self.rpc_type();
+        if !rpc_type.matches(value) {
+            return invalid(format!("expected {rpc_type}, got {value}"));
+        }
+
+        let number = match value {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) if rpc_type == RpcType::Amount => s.parse().ok(),
+            _ => None,
+        };
+        if let Some(number) = number {
+            if let Some(minimum) = self.minimum.filter(|minimum| number < *minimum) {
+                return invalid(format!("{value} is less than the minimum {minimum}"));
+            }
+            if let Some(maximum) = self.maximum.filter(|maximum| number > *maximum) {
+                return invalid(format!("{value} is greater than the maximum {maximum}"));
+            }
+        }
+
+        if !self.allowed_values.is_empty() && !self.allowed_values.contains(value) {
+            return invalid(format!("{value} is not one of the allowed values"));
+        }
+        Ok(())
+    }
 }
 
 /// Bitcoin method result specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Non-exhaustive; build values with `BtcResult::builder` or `BtcResult::new`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BtcResult {
     /// Type of the result
     #[serde(rename = "type")]
@@ -58,6 +142,9 @@ pub struct BtcResult {
     /// Inner results for nested structures
     #[serde(default)]
     pub inner: Vec<BtcResult>,
+    /// Whether `key_name` is a placeholder for data-dependent keys (e.g. a txid)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dynamic_key: bool,
 }
 
 impl Default for BtcResult {
@@ -71,6 +158,7 @@ impl Default for BtcResult {
             key_name: String::new(),
             condition: String::new(),
             inner: Vec::new(),
+            dynamic_key: false,
         }
     }
 }
@@ -86,15 +174,56 @@ impl BtcResult {
         condition: String,
         inner: Vec<BtcResult>,
     ) -> Self {
-        Self { type_, optional, description, skip_type_check, key_name, condition, inner }
+        Self {
+            type_,
+            optional,
+            description,
+            skip_type_check,
+            key_name,
+            condition,
+            inner,
+            dynamic_key: false,
+        }
     }
 
     /// Returns whether the result is required (computed from optional)
     pub fn required(&self) -> bool { !self.optional }
+
+    /// Returns whether the node stands for omitted entries (`...`) rather than a value
+    pub fn is_elision(&self) -> bool {
+        self.rpc_type() == RpcType::Elision || self.key_name == "..."
+    }
+
+    /// Returns whether the result is a map keyed by data rather than a struct
+    ///
+    /// This holds for `object_dynamic` results and for objects whose fields all
+    /// have dynamic keys (e.g. `getrawmempool` verbose: txid → entry).
+    pub fn is_map(&self) -> bool {
+        match self.rpc_type() {
+            RpcType::ObjectDynamic => true,
+            RpcType::Object => {
+                let mut fields = self.inner.iter().filter(|field| !field.is_elision()).peekable();
+                fields.peek().is_some() && fields.all(|field| field.dynamic_key)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the node describing the values of a map result
+    pub fn map_value(&self) -> Option<&BtcResult> {
+        if !self.is_map() {
+            return None;
+        }
+        self.inner.iter().find(|field| !field.is_elision())
+    }
 }
 
 /// Bitcoin method definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Non-exhaustive; build values with `BtcMethod::builder` or from
+/// `BtcMethod::default()`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BtcMethod {
     /// Name of the method
     pub name: String,
@@ -110,10 +239,40 @@ pub struct BtcMethod {
     pub arguments: Vec<BtcArgument>,
     /// Results returned by the method
     pub results: Vec<BtcResult>,
+    /// Category the method is listed under in `help`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
+    /// First Bitcoin Core version providing the method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced_in: Option<CoreVersion>,
+    /// First Bitcoin Core version no longer providing the method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed_in: Option<CoreVersion>,
+    /// Whether the method is deprecated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Explanation of the deprecation, such as the replacement to use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_note: Option<String>,
+}
+
+impl BtcMethod {
+    /// Returns whether the method is available in the given Bitcoin Core version
+    ///
+    /// Missing bounds are treated as unbounded.
+    pub fn is_available_in(&self, version: CoreVersion) -> bool {
+        self.introduced_in.is_none_or(|introduced| introduced <= version)
+            && self.removed_in.is_none_or(|removed| version < removed)
+    }
+
+    /// Returns the deprecated arguments of the method
+    pub fn deprecated_arguments(&self) -> impl Iterator<Item = &BtcArgument> {
+        self.arguments.iter().filter(|arg| arg.deprecated)
+    }
 }
 
 /// A collection of all Bitcoin RPC methods and their details
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiDefinition {
     /// List of methods sorted by the method name
     pub rpcs: BTreeMap<String, BtcMethod>,
@@ -126,16 +285,143 @@ impl ApiDefinition {
     /// Loads an API definition from a JSON file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        let api_def: ApiDefinition = serde_json::from_str(&content)?;
+        content.parse()
+    }
+
+    /// Loads an API definition from a JSON reader
+    ///
+    /// The reader is not buffered internally; wrap unbuffered sources such as
+    /// files or sockets in a `std::io::BufReader`.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let api_def: ApiDefinition = serde_json::from_reader(reader)?;
         Ok(api_def)
     }
 
+    /// Loads an API definition from JSON bytes
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let api_def: ApiDefinition = serde_json::from_slice(bytes)?;
+        Ok(api_def)
+    }
+
+    /// Serializes the definition in canonical form
+    ///
+    /// The output is deterministic: methods, struct fields and all other object
+    /// keys are sorted alphabetically, indentation is two spaces, and the text
+    /// ends with a newline.
+    pub fn to_string_pretty(&self) -> Result<String> {
+        let value = canonical_value(self)?;
+        let mut json = serde_json::to_string_pretty(&value)?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Writes the definition to a JSON file in canonical form (see `to_string_pretty`)
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_string_pretty()?)?;
+        Ok(())
+    }
+
     /// Gets a method by name
     pub fn get_method(&self, name: &str) -> Option<&BtcMethod> { self.rpcs.get(name) }
+
+    /// Returns whether a method with the given name exists
+    pub fn contains_method(&self, name: &str) -> bool { self.rpcs.contains_key(name) }
+
+    /// Returns the methods, ordered by name
+    pub fn methods(&self) -> impl Iterator<Item = &BtcMethod> { self.rpcs.values() }
+
+    /// Returns the number of methods
+    pub fn len(&self) -> usize { self.rpcs.len() }
+
+    /// Returns whether the definition contains no methods
+    pub fn is_empty(&self) -> bool { self.rpcs.is_empty() }
+
+    /// Inserts a method under its own name, returning the method it replaced
+    ///
+    /// Fails with `SchemaError::InvalidDefinition` if the method name is empty,
+    /// `argument_names` does not match `arguments`, an argument has no names,
+    /// or two arguments share a name.
+    pub fn insert_method(&mut self, method: BtcMethod) -> Result<Option<BtcMethod>> {
+        if method.name.is_empty() {
+            return Err(SchemaError::InvalidDefinition("method name is empty".to_string()));
+        }
+        ensure_valid_method(&method)?;
+        Ok(self.rpcs.insert(method.name.clone(), method))
+    }
+
+    /// Returns a new definition containing only the named methods
+    ///
+    /// Names without a matching method are ignored.
+    pub fn subset(&self, names: &[&str]) -> ApiDefinition {
+        self.filter(|method| names.contains(&method.name.as_str()))
+    }
+
+    /// Returns a new definition containing only the methods matching `predicate`
+    pub fn filter<F>(&self, mut predicate: F) -> ApiDefinition
+    where
+        F: FnMut(&BtcMethod) -> bool,
+    {
+        let rpcs = self
+            .rpcs
+            .iter()
+            .filter(|(_, method)| predicate(method))
+            .map(|(name, method)| (name.clone(), method.clone()))
+            .collect();
+        ApiDefinition { rpcs }
+    }
+
+    /// Removes a method by name, returning it if it was present
+    pub fn remove_method(&mut self, name: &str) -> Option<BtcMethod> { self.rpcs.remove(name) }
+
+    /// Returns the methods available in the given Bitcoin Core version
+    pub fn methods_available_in(&self, version: CoreVersion) -> impl Iterator<Item = &BtcMethod> {
+        self.rpcs.values().filter(move |method| method.is_available_in(version))
+    }
+
+    /// Returns the deprecated methods, ordered by name
+    pub fn deprecated_methods(&self) -> impl Iterator<Item = &BtcMethod> {
+        self.rpcs.values().filter(|method| method.deprecated)
+    }
+}
+
+impl FromStr for ApiDefinition {
+    type Err = SchemaError;
+
+    /// Loads an API definition from a JSON string
+    fn from_str(s: &str) -> Result<Self> {
+        let api_def: ApiDefinition = serde_json::from_str(s)?;
+        Ok(api_def)
+    }
+}
+
+/// Converts a value to JSON with every object's keys in sorted order
+pub(crate) fn canonical_value<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    Ok(canonicalize(serde_json::to_value(value)?))
+}
+
+/// Recursively rebuilds every JSON object with its keys in sorted order
+///
+/// Another crate in the build may enable serde_json's `preserve_order`,
+/// which keeps struct fields in declaration order, so the keys are sorted
+/// explicitly rather than relying on the map type.
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries.into_iter().map(|(key, value)| (key, canonicalize(value))).collect(),
+            )
+        }
+        serde_json::Value::Array(values) =>
+            serde_json::Value::Array(values.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
 }
 
 /// Error types for schema operations
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SchemaError {
     /// JSON parsing error
     #[error("Failed to parse JSON: {0}")]
@@ -144,6 +430,34 @@ pub enum SchemaError {
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// Help text could not be parsed
+    #[error("Failed to parse help text: {0}")]
+    HelpParse(String),
+
+    /// Binary (CBOR) encoding or decoding error
+    #[error("Binary schema error: {0}")]
+    Binary(String),
+
+    /// Definition violates a schema invariant
+    #[error("Invalid definition: {0}")]
+    InvalidDefinition(String),
+
+    /// Value does not satisfy an argument's type or constraints
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+
+    /// Method is not part of the definition
+    #[error("Unknown method: {0}")]
+    UnknownMethod(String),
+
+    /// Request parameters do not match the method's arguments
+    #[error("Invalid parameters: {}", join_errors(.0))]
+    InvalidParams(Vec<ValidationError>),
+}
+
+fn join_errors(errors: &[ValidationError]) -> String {
+    errors.iter().map(ValidationError::to_string).collect::<Vec<_>>().join("; ")
 }
 
 /// Result type for schema operations
@@ -218,7 +532,9 @@ mod tests {
                 key_name: "inner_key".to_string(),
                 condition: "inner_condition".to_string(),
                 inner: vec![],
+                dynamic_key: false,
             }],
+            dynamic_key: false,
         };
 
         // Main result should have required = !optional = false
@@ -394,6 +710,7 @@ mod tests {
             argument_names: vec!["blockhash".to_string()],
             arguments: vec![],
             results: vec![],
+            ..BtcMethod::default()
         };
         api_def.rpcs.insert("getblock".to_string(), method);
 
@@ -405,4 +722,353 @@ mod tests {
         // Test getting non-existent method
         assert!(api_def.get_method("gettransaction").is_none());
     }
+
+    #[test]
+    fn test_btc_method_is_available_in() {
+        let method = BtcMethod {
+            name: "getdeploymentinfo".to_string(),
+            introduced_in: Some(CoreVersion::V25),
+            removed_in: Some(CoreVersion::V28),
+            ..BtcMethod::default()
+        };
+        assert!(!method.is_available_in(CoreVersion::new(24, 0)));
+        assert!(method.is_available_in(CoreVersion::V25));
+        assert!(method.is_available_in(CoreVersion::V27));
+        assert!(!method.is_available_in(CoreVersion::V28));
+
+        let unbounded = BtcMethod { name: "getblockcount".to_string(), ..BtcMethod::default() };
+        assert!(unbounded.is_available_in(CoreVersion::new(0, 21)));
+    }
+
+    #[test]
+    fn test_api_definition_methods_available_in() {
+        let mut api_def = ApiDefinition::new();
+        for method in [
+            BtcMethod { name: "getblockcount".to_string(), ..BtcMethod::default() },
+            BtcMethod {
+                name: "getinfo".to_string(),
+                removed_in: Some(CoreVersion::new(0, 16)),
+                ..BtcMethod::default()
+            },
+            BtcMethod {
+                name: "getdescriptoractivity".to_string(),
+                introduced_in: Some(CoreVersion::V29),
+                ..BtcMethod::default()
+            },
+        ] {
+            api_def.rpcs.insert(method.name.clone(), method);
+        }
+
+        let names: Vec<&str> =
+            api_def.methods_available_in(CoreVersion::V28).map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["getblockcount"]);
+
+        let names: Vec<&str> =
+            api_def.methods_available_in(CoreVersion::V29).map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["getblockcount", "getdescriptoractivity"]);
+    }
+
+    #[test]
+    fn test_deprecation_metadata() {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblock".to_string(),
+            BtcMethod {
+                name: "getblock".to_string(),
+                arguments: vec![
+                    BtcArgument { names: vec!["blockhash".to_string()], ..BtcArgument::default() },
+                    BtcArgument {
+                        names: vec!["verbose".to_string()],
+                        deprecated: true,
+                        deprecation_note: Some("Use verbosity instead".to_string()),
+                        ..BtcArgument::default()
+                    },
+                ],
+                ..BtcMethod::default()
+            },
+        );
+        api_def.rpcs.insert(
+            "getinfo".to_string(),
+            BtcMethod { name: "getinfo".to_string(), deprecated: true, ..BtcMethod::default() },
+        );
+
+        let deprecated: Vec<&str> = api_def.deprecated_methods().map(|m| m.name.as_str()).collect();
+        assert_eq!(deprecated, vec!["getinfo"]);
+
+        let getblock = api_def.get_method("getblock").unwrap();
+        let args: Vec<&BtcArgument> = getblock.deprecated_arguments().collect();
+        assert_eq!(args.len(), 1);
+        assert_eq!(args[0].deprecation_note.as_deref(), Some("Use verbosity instead"));
+
+        // Deprecation fields are optional when loading older schema files
+        let method: BtcMethod = serde_json::from_str(
+            r#"{"name": "getinfo", "description": "", "arguments": [], "results": []}"#,
+        )
+        .unwrap();
+        assert!(!method.deprecated);
+        assert!(method.deprecation_note.is_none());
+
+        // Unset flags are left out when serializing
+        let json = serde_json::to_value(api_def.get_method("getblock").unwrap()).unwrap();
+        assert!(json.get("deprecated").is_none());
+        assert!(json["arguments"][0].get("deprecated").is_none());
+        assert_eq!(json["arguments"][1]["deprecated"], true);
+        let json = serde_json::to_value(BtcResult::default()).unwrap();
+        assert!(json.get("dynamic_key").is_none());
+    }
+
+    #[test]
+    fn test_api_definition_insert_and_remove_method() {
+        let mut api_def = ApiDefinition::new();
+        assert!(api_def.is_empty());
+
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["blockhash".to_string()],
+            arguments: vec![BtcArgument {
+                names: vec!["blockhash".to_string()],
+                ..BtcArgument::default()
+            }],
+            ..BtcMethod::default()
+        };
+        assert!(api_def.insert_method(method.clone()).unwrap().is_none());
+        assert_eq!(api_def.insert_method(method.clone()).unwrap(), Some(method.clone()));
+        assert_eq!(api_def.len(), 1);
+        assert!(api_def.contains_method("getblock"));
+        assert_eq!(api_def.methods().next().unwrap().name, "getblock");
+
+        assert_eq!(api_def.remove_method("getblock"), Some(method));
+        assert!(api_def.remove_method("getblock").is_none());
+        assert!(api_def.is_empty());
+    }
+
+    #[test]
+    fn test_api_definition_insert_method_invariants() {
+        let mut api_def = ApiDefinition::new();
+
+        let unnamed = BtcMethod::default();
+        assert!(matches!(api_def.insert_method(unnamed), Err(SchemaError::InvalidDefinition(_))));
+
+        let mismatched = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["hash".to_string()],
+            arguments: vec![BtcArgument {
+                names: vec!["blockhash".to_string()],
+                ..BtcArgument::default()
+            }],
+            ..BtcMethod::default()
+        };
+        assert!(api_def.insert_method(mismatched).is_err());
+
+        let duplicate = BtcMethod {
+            name: "send".to_string(),
+            argument_names: vec!["outputs".to_string(), "outputs".to_string()],
+            arguments: vec![
+                BtcArgument { names: vec!["outputs".to_string()], ..BtcArgument::default() },
+                BtcArgument { names: vec!["outputs".to_string()], ..BtcArgument::default() },
+            ],
+            ..BtcMethod::default()
+        };
+        assert!(api_def.insert_method(duplicate).is_err());
+        assert!(api_def.is_empty());
+    }
+
+    #[test]
+    fn test_api_definition_to_string_pretty_is_deterministic() {
+        let getblock = BtcMethod {
+            name: "getblock".to_string(),
+            argument_names: vec!["blockhash".to_string()],
+            arguments: vec![BtcArgument {
+                names: vec!["blockhash".to_string()],
+                ..BtcArgument::default()
+            }],
+            ..BtcMethod::default()
+        };
+        let getblockcount = BtcMethod { name: "getblockcount".to_string(), ..BtcMethod::default() };
+
+        let mut first = ApiDefinition::new();
+        first.insert_method(getblock.clone()).unwrap();
+        first.insert_method(getblockcount.clone()).unwrap();
+        let mut second = ApiDefinition::new();
+        second.insert_method(getblockcount).unwrap();
+        second.insert_method(getblock).unwrap();
+
+        let json = first.to_string_pretty().unwrap();
+        assert_eq!(json, second.to_string_pretty().unwrap());
+        assert!(json.ends_with("}\n"));
+        assert!(json.starts_with("{\n  \"rpcs\": {\n    \"getblock\": {"));
+        assert!(json.find("\"getblock\"").unwrap() < json.find("\"getblockcount\"").unwrap());
+        // Struct fields are sorted too, not kept in declaration order
+        assert!(json.find("\"arguments\"").unwrap() < json.find("\"name\"").unwrap());
+
+        let round_trip: ApiDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, first);
+    }
+
+    #[test]
+    fn test_api_definition_to_file() {
+        let mut api_def = ApiDefinition::new();
+        api_def
+            .insert_method(BtcMethod { name: "uptime".to_string(), ..BtcMethod::default() })
+            .unwrap();
+
+        let temp_file = "test_to_file_api.json";
+        api_def.to_file(temp_file).unwrap();
+        let written = std::fs::read_to_string(temp_file).unwrap();
+        assert_eq!(written, api_def.to_string_pretty().unwrap());
+        assert_eq!(ApiDefinition::from_file(temp_file).unwrap(), api_def);
+
+        // Clean up
+        std::fs::remove_file(temp_file).unwrap();
+    }
+
+    #[test]
+    fn test_api_definition_from_str_slice_and_reader() {
+        let json = r#"{
+            "rpcs": {
+                "uptime": {
+                    "name": "uptime",
+                    "description": "Returns the total uptime of the server.",
+                    "arguments": [],
+                    "results": [{"type": "number", "description": "The number of seconds"}]
+                }
+            }
+        }"#;
+
+        let from_str: ApiDefinition = json.parse().unwrap();
+        let from_slice = ApiDefinition::from_slice(json.as_bytes()).unwrap();
+        let from_reader = ApiDefinition::from_reader(std::io::Cursor::new(json)).unwrap();
+        assert_eq!(from_str.len(), 1);
+        assert_eq!(from_str, from_slice);
+        assert_eq!(from_str, from_reader);
+
+        assert!(matches!("{".parse::<ApiDefinition>(), Err(SchemaError::JsonParse(_))));
+        assert!(matches!(ApiDefinition::from_slice(b"[]"), Err(SchemaError::JsonParse(_))));
+        assert!(ApiDefinition::from_reader(&b"null"[..]).is_err());
+    }
+
+    #[test]
+    fn test_api_definition_subset_and_filter() {
+        let mut api_def = ApiDefinition::new();
+        for (name, category) in [
+            ("getbalance", Category::Wallet),
+            ("getblock", Category::Blockchain),
+            ("listunspent", Category::Wallet),
+        ] {
+            api_def
+                .insert_method(BtcMethod {
+                    name: name.to_string(),
+                    category: Some(category),
+                    ..BtcMethod::default()
+                })
+                .unwrap();
+        }
+
+        let subset = api_def.subset(&["getblock", "listunspent", "nonexistent"]);
+        assert_eq!(
+            subset.methods().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+            vec!["getblock", "listunspent"]
+        );
+
+        let wallet = api_def.filter(|method| method.category == Some(Category::Wallet));
+        assert_eq!(wallet.len(), 2);
+        assert!(!wallet.contains_method("getblock"));
+
+        // Projections remain diffable and serializable
+        assert_eq!(api_def.diff(&wallet).removed, vec!["getblock".to_string()]);
+        let round_trip: ApiDefinition = wallet.to_string_pretty().unwrap().parse().unwrap();
+        assert_eq!(round_trip, wallet);
+    }
+
+    #[test]
+    fn test_parse_default() {
+        assert_eq!(BtcArgument::parse_default("default=0"), Some(serde_json::json!(0)));
+        assert_eq!(BtcArgument::parse_default("default=false"), Some(serde_json::json!(false)));
+        assert_eq!(
+            BtcArgument::parse_default("default=\"unset\""),
+            Some(serde_json::json!("unset"))
+        );
+        assert_eq!(BtcArgument::parse_default("default=[]"), Some(serde_json::json!([])));
+        assert_eq!(BtcArgument::parse_default("0.0001"), Some(serde_json::json!(0.0001)));
+        assert_eq!(BtcArgument::parse_default("default=wallet -txconfirmtarget"), None);
+
+        let arg = BtcArgument {
+            names: vec!["verbosity".to_string()],
+            type_: "number".to_string(),
+            default: Some(serde_json::json!(1)),
+            ..BtcArgument::default()
+        };
+        let json = serde_json::to_value(&arg).unwrap();
+        assert_eq!(json["default"], 1);
+        assert_eq!(serde_json::from_value::<BtcArgument>(json).unwrap(), arg);
+        assert!(serde_json::to_value(BtcArgument::default()).unwrap().get("default").is_none());
+    }
+
+    #[test]
+    fn test_argument_validate_value() {
+        use serde_json::json;
+
+        let verbosity = BtcArgument {
+            names: vec!["verbosity".to_string()],
+            type_: "number".to_string(),
+            minimum: Some(0.0),
+            maximum: Some(3.0),
+            ..BtcArgument::default()
+        };
+        assert!(verbosity.validate_value(&json!(2)).is_ok());
+        assert!(matches!(verbosity.validate_value(&json!(4)), Err(SchemaError::InvalidValue(_))));
+        assert!(verbosity.validate_value(&json!(-1)).is_err());
+        assert!(verbosity.validate_value(&json!("1")).is_err());
+
+        let estimate_mode = BtcArgument {
+            names: vec!["estimate_mode".to_string()],
+            type_: "string".to_string(),
+            allowed_values: vec![json!("unset"), json!("economical"), json!("conservative")],
+            ..BtcArgument::default()
+        };
+        assert!(estimate_mode.validate_value(&json!("economical")).is_ok());
+        let err = estimate_mode.validate_value(&json!("fast")).unwrap_err();
+        assert!(err.to_string().contains("estimate_mode"));
+
+        let fee = BtcArgument {
+            names: vec!["fee_rate".to_string()],
+            type_: "amount".to_string(),
+            minimum: Some(0.0),
+            ..BtcArgument::default()
+        };
+        assert!(fee.validate_value(&json!("0.0001")).is_ok());
+        assert!(fee.validate_value(&json!("-0.0001")).is_err());
+    }
+
+    #[test]
+    fn test_result_dynamic_keys_and_elision() {
+        let entry = BtcResult {
+            type_: "object".to_string(),
+            key_name: "transactionid".to_string(),
+            dynamic_key: true,
+            ..BtcResult::default()
+        };
+        let elision = BtcResult { type_: "elision".to_string(), ..BtcResult::default() };
+        let mempool = BtcResult {
+            type_: "object".to_string(),
+            inner: vec![entry.clone(), elision.clone()],
+            ..BtcResult::default()
+        };
+        assert!(elision.is_elision());
+        assert!(BtcResult { key_name: "...".to_string(), ..BtcResult::default() }.is_elision());
+        assert!(mempool.is_map());
+        assert_eq!(mempool.map_value(), Some(&entry));
+
+        let literal = BtcResult {
+            inner: vec![BtcResult { dynamic_key: false, ..entry.clone() }],
+            ..mempool.clone()
+        };
+        assert!(!literal.is_map());
+        assert!(BtcResult { type_: "object_dynamic".to_string(), ..BtcResult::default() }.is_map());
+        assert!(!BtcResult { type_: "object".to_string(), ..BtcResult::default() }.is_map());
+
+        let json = serde_json::to_value(&mempool).unwrap();
+        assert_eq!(json["inner"][0]["dynamic_key"], true);
+        assert_eq!(serde_json::from_value::<BtcResult>(json).unwrap(), mempool);
+    }
 }
@@ -0,0 +1,414 @@
+//! TypeScript definition export
+//!
+//! Renders an `ApiDefinition` as TypeScript declarations: one params
+//! `interface` and one result type per method, plus an interface mapping each
+//! method name to both, so web clients of the node API can share the schema
+//! with Rust code instead of maintaining their own types.
+
+use std::fmt::Write;
+
+use crate::ident::RustIdent;
+use crate::rpc_type::RpcType;
+use crate::types::{ApiDefinition, BtcArgument, BtcMethod, BtcResult};
+
+impl ApiDefinition {
+    /// Exports this definition as TypeScript declarations
+    ///
+    /// Each method gets the declarations of `BtcMethod::to_typescript`, in
+    /// name order, followed by a `BitcoinRpcMethods` interface mapping each
+    /// method name to its `{ params, result }` types.
+    pub fn to_typescript(&self) -> String {
+        let mut source = String::new();
+        let mut methods = String::new();
+        for method in self.methods() {
+            source.push_str(&method.to_typescript());
+            source.push('\n');
+            writeln!(
+                methods,
+                "  {}: {{ params: {}; result: {} }};",
+                property_name(&method.name),
+                method.typescript_params_name(),
+                method.typescript_result_name()
+            )
+            .unwrap();
+        }
+        source.push_str("/** Bitcoin Core JSON-RPC methods */\n");
+        writeln!(source, "export interface BitcoinRpcMethods {{\n{}}}", methods).unwrap();
+        source
+    }
+}
+
+impl BtcMethod {
+    /// Returns the name of the TypeScript params interface, e.g. `GetblockParams`
+    pub fn typescript_params_name(&self) -> String {
+        format!("{}Params", RustIdent::pascal_case(&self.name))
+    }
+
+    /// Returns the name of the TypeScript result type, e.g. `GetblockResult`
+    pub fn typescript_result_name(&self) -> String {
+        format!("{}Result", RustIdent::pascal_case(&self.name))
+    }
+
+    /// Exports the method's params and result as TypeScript declarations
+    ///
+    /// Params are an `interface` keyed by argument name, as sent with named
+    /// parameters; hidden arguments are omitted and optional ones are
+    /// optional properties. The result is an `interface` for objects with
+    /// described fields and a `type` otherwise, with alternative results
+    /// joined into a union.
+    pub fn to_typescript(&self) -> String {
+        let mut source = doc_comment(&self.description, "");
+        let arguments: Vec<&BtcArgument> =
+            self.arguments.iter().filter(|arg| !arg.hidden).collect();
+        writeln!(
+            source,
+            "export interface {} {}",
+            self.typescript_params_name(),
+            argument_object(&arguments, "")
+        )
+        .unwrap();
+
+        source.push('\n');
+        match self.results.as_slice() {
+            [result] if is_interface(result) => writeln!(
+                source,
+                "export interface {} {}",
+                self.typescript_result_name(),
+                result_type(result, "")
+            ),
+            results => writeln!(
+                source,
+                "export type {} = {};",
+                self.typescript_result_name(),
+                results_type(results, "")
+            ),
+        }
+        .unwrap();
+        source
+    }
+}
+
+/// Whether a result is rendered as an object type with properties
+fn is_interface(result: &BtcResult) -> bool {
+    !result.skip_type_check
+        && result.rpc_type() == RpcType::Object
+        && !result.is_map()
+        && result.inner.iter().any(|field| !field.is_elision())
+}
+
+/// Returns the TypeScript type for alternative results
+fn results_type(results: &[BtcResult], indent: &str) -> String {
+    match results {
+        [] => "null".to_string(),
+        results => union(results.iter().map(|result| result_type(result, indent)).collect()),
+    }
+}
+
+/// Returns the TypeScript type for a result node
+fn result_type(result: &BtcResult, indent: &str) -> String {
+    if result.skip_type_check {
+        return "unknown".to_string();
+    }
+    let items: Vec<&BtcResult> = result.inner.iter().filter(|inner| !inner.is_elision()).collect();
+    match result.rpc_type() {
+        RpcType::String | RpcType::Hex => "string".to_string(),
+        RpcType::Number | RpcType::Amount | RpcType::Time => "number".to_string(),
+        RpcType::Boolean => "boolean".to_string(),
+        RpcType::None => "null".to_string(),
+        RpcType::Object | RpcType::ObjectDynamic if result.is_map() => match result.map_value() {
+            Some(value) => format!("Record<string, {}>", result_type(value, indent)),
+            None => "Record<string, unknown>".to_string(),
+        },
+        RpcType::Object if !items.is_empty() => result_object(result, indent),
+        RpcType::Object => "Record<string, unknown>".to_string(),
+        RpcType::Array => match items.as_slice() {
+            [] => "unknown[]".to_string(),
+            items => array(items.iter().map(|item| result_type(item, indent)).collect()),
+        },
+        RpcType::ArrayFixed => format!(
+            "[{}]",
+            items.iter().map(|item| result_type(item, indent)).collect::<Vec<_>>().join(", ")
+        ),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Returns the TypeScript object type for a result with fields
+///
+/// Elided or dynamic entries add an index signature, since the node may
+/// return keys that are not described.
+fn result_object(result: &BtcResult, indent: &str) -> String {
+    let inner_indent = format!("{}  ", indent);
+    let mut source = String::from("{\n");
+    let mut open = false;
+    for field in &result.inner {
+        if field.is_elision() || field.dynamic_key || field.key_name.is_empty() {
+            open = true;
+            continue;
+        }
+        source.push_str(&doc_comment(&field.description, &inner_indent));
+        writeln!(
+            source,
+            "{}{}{}: {};",
+            inner_indent,
+            property_name(&field.key_name),
+            if field.optional { "?" } else { "" },
+            result_type(field, &inner_indent)
+        )
+        .unwrap();
+    }
+    if open {
+        writeln!(source, "{}[key: string]: unknown;", inner_indent).unwrap();
+    }
+    source.push_str(indent);
+    source.push('}');
+    source
+}
+
+/// Returns the TypeScript type for an argument
+fn argument_type(rpc_type: &RpcType, arg: &BtcArgument, indent: &str) -> String {
+    match rpc_type {
+        RpcType::String | RpcType::Hex => "string".to_string(),
+        RpcType::Number | RpcType::Time => "number".to_string(),
+        RpcType::Amount => "number | string".to_string(),
+        RpcType::Boolean => "boolean".to_string(),
+        RpcType::Object | RpcType::ObjectNamedParams if !arg.inner.is_empty() => {
+            let fields: Vec<&BtcArgument> = arg.inner.iter().collect();
+            argument_object(&fields, indent)
+        }
+        RpcType::Object | RpcType::ObjectNamedParams => "Record<string, unknown>".to_string(),
+        RpcType::ObjectUserKeys => match arg.inner.first() {
+            Some(value) =>
+                format!("Record<string, {}>", argument_type(&value.rpc_type(), value, indent)),
+            None => "Record<string, unknown>".to_string(),
+        },
+        RpcType::Array => match arg.inner.as_slice() {
+            [] => "unknown[]".to_string(),
+            items => array(
+                items.iter().map(|item| argument_type(&item.rpc_type(), item, indent)).collect(),
+            ),
+        },
+        RpcType::Range => "number | [number, number]".to_string(),
+        RpcType::OneOf(types) =>
+            union(types.iter().map(|rpc_type| argument_type(rpc_type, arg, indent)).collect()),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Returns the TypeScript object type for arguments, or the fields of one
+fn argument_object(fields: &[&BtcArgument], indent: &str) -> String {
+    if fields.is_empty() {
+        return "{}".to_string();
+    }
+    let inner_indent = format!("{}  ", indent);
+    let mut source = String::from("{\n");
+    for field in fields {
+        let name = field.names.first().map(String::as_str).unwrap_or_default();
+        source.push_str(&doc_comment(&field.description, &inner_indent));
+        writeln!(
+            source,
+            "{}{}{}: {};",
+            inner_indent,
+            property_name(name),
+            if field.required { "" } else { "?" },
+            argument_type(&field.rpc_type(), field, &inner_indent)
+        )
+        .unwrap();
+    }
+    source.push_str(indent);
+    source.push('}');
+    source
+}
+
+/// Joins alternative types, dropping duplicates
+fn union(types: Vec<String>) -> String { distinct(types).join(" | ") }
+
+/// Returns the array type of alternative element types
+fn array(items: Vec<String>) -> String {
+    match distinct(items).as_slice() {
+        [item] if item.starts_with('{') || !item.contains(" | ") => format!("{}[]", item),
+        items => format!("({})[]", items.join(" | ")),
+    }
+}
+
+fn distinct(types: Vec<String>) -> Vec<String> {
+    let mut distinct: Vec<String> = Vec::new();
+    for type_ in types {
+        if !distinct.contains(&type_) {
+            distinct.push(type_);
+        }
+    }
+    distinct
+}
+
+/// Returns a property name, quoted unless it is a valid identifier
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match identifier {
+        true => name.to_string(),
+        false => format!("{:?}", name),
+    }
+}
+
+/// Formats a description as a `/** */` comment with the given indentation
+fn doc_comment(description: &str, indent: &str) -> String {
+    let description = description.trim().replace("*/", "*\\/");
+    let lines: Vec<&str> = description.lines().map(str::trim_end).collect();
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{}/** {} */\n", indent, line.trim()),
+        lines => {
+            let mut comment = format!("{}/**\n", indent);
+            for line in lines {
+                match line.trim().is_empty() {
+                    true => writeln!(comment, "{} *", indent).unwrap(),
+                    false => writeln!(comment, "{} * {}", indent, line.trim()).unwrap(),
+                }
+            }
+            writeln!(comment, "{} */", indent).unwrap();
+            comment
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, type_: &str, required: bool) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            ..BtcArgument::default()
+        }
+    }
+
+    fn node(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            key_name: key_name.to_string(),
+            type_: type_.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    #[test]
+    fn test_method_to_typescript() {
+        let method = BtcMethod {
+            name: "getblock".to_string(),
+            description: "Returns block data.".to_string(),
+            arguments: vec![
+                BtcArgument {
+                    description: "The block hash".to_string(),
+                    ..arg("blockhash", "hex", true)
+                },
+                arg("verbosity", "number", false),
+                arg("fee_rate", "amount", false),
+                BtcArgument { hidden: true, ..arg("debug", "boolean", false) },
+            ],
+            results: vec![node(
+                "",
+                "object",
+                vec![
+                    BtcResult {
+                        description: "the block hash".to_string(),
+                        ..node("hash", "hex", vec![])
+                    },
+                    BtcResult { optional: true, ..node("nextblockhash", "hex", vec![]) },
+                    node("bip125-replaceable", "string", vec![]),
+                    node(
+                        "tx",
+                        "array",
+                        vec![node(
+                            "",
+                            "object",
+                            vec![node("txid", "hex", vec![]), node("", "elision", vec![])],
+                        )],
+                    ),
+                    node("fees", "object_dynamic", vec![node("", "amount", vec![])]),
+                ],
+            )],
+            ..BtcMethod::default()
+        };
+        assert_eq!(
+            method.to_typescript(),
+            r#"/** Returns block data. */
+export interface GetblockParams {
+  /** The block hash */
+  blockhash: string;
+  verbosity?: number;
+  fee_rate?: number | string;
+}
+
+export interface GetblockResult {
+  /** the block hash */
+  hash: string;
+  nextblockhash?: string;
+  "bip125-replaceable": string;
+  tx: {
+    txid: string;
+    [key: string]: unknown;
+  }[];
+  fees: Record<string, number>;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_api_to_typescript() {
+        let mut api_def = ApiDefinition::new();
+        api_def.rpcs.insert(
+            "getblockheader".to_string(),
+            BtcMethod {
+                name: "getblockheader".to_string(),
+                description: "Returns a header.\n\nSee */ below.".to_string(),
+                arguments: vec![arg("blockhash", "hex", true)],
+                results: vec![
+                    BtcResult {
+                        condition: "for verbose = false".to_string(),
+                        ..node("", "hex", vec![])
+                    },
+                    node("", "object", vec![node("height", "number", vec![])]),
+                ],
+                ..BtcMethod::default()
+            },
+        );
+        api_def.rpcs.insert(
+            "ping".to_string(),
+            BtcMethod { name: "ping".to_string(), ..BtcMethod::default() },
+        );
+
+        assert_eq!(
+            api_def.to_typescript(),
+            r#"/**
+ * Returns a header.
+ *
+ * See *\/ below.
+ */
+export interface GetblockheaderParams {
+  blockhash: string;
+}
+
+export type GetblockheaderResult = string | {
+  height: number;
+};
+
+export interface PingParams {}
+
+export type PingResult = null;
+
+/** Bitcoin Core JSON-RPC methods */
+export interface BitcoinRpcMethods {
+  getblockheader: { params: GetblockheaderParams; result: GetblockheaderResult };
+  ping: { params: PingParams; result: PingResult };
+}
+"#
+        );
+        assert_eq!(array(vec!["number | string".to_string()]), "(number | string)[]");
+        assert_eq!(property_name("24h"), "\"24h\"");
+    }
+}
@@ -0,0 +1,246 @@
+//! UTXO set result types
+//!
+//! This module provides `GetTxOutResult`, returned by `gettxout`, and
+//! `GetTxOutSetInfoResult`, returned by `gettxoutsetinfo`, with the
+//! `TxOutSetHashType` enum for its `hash_type` argument.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::{Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::decoded_tx::DecodedScriptPubKey;
+
+/// Result of `gettxout`, which is `null` when the output is spent or does
+/// not exist
+pub type GetTxOutResponse = Option<GetTxOutResult>;
+
+/// Unspent output returned by `gettxout`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetTxOutResult {
+    /// Hash of the tip the output was looked up at
+    pub bestblock: BlockHash,
+    /// Number of confirmations, 0 for a mempool output
+    pub confirmations: u32,
+    /// Value of the output
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub value: Amount,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: DecodedScriptPubKey,
+    /// Whether the output is from a coinbase
+    pub coinbase: bool,
+}
+
+impl GetTxOutResult {
+    /// Returns whether the output is only in the mempool
+    pub fn is_unconfirmed(&self) -> bool { self.confirmations == 0 }
+}
+
+/// Hash of the UTXO set that `gettxoutsetinfo` computes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxOutSetHashType {
+    /// SHA256 of the serialized UTXO set, the default
+    #[default]
+    #[serde(rename = "hash_serialized_3")]
+    HashSerialized3,
+    /// MuHash of the UTXO set
+    Muhash,
+    /// No hash
+    None,
+}
+
+impl TxOutSetHashType {
+    /// All hash types
+    pub const ALL: [TxOutSetHashType; 3] =
+        [TxOutSetHashType::HashSerialized3, TxOutSetHashType::Muhash, TxOutSetHashType::None];
+
+    /// Returns the name Bitcoin Core uses for the hash type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HashSerialized3 => "hash_serialized_3",
+            Self::Muhash => "muhash",
+            Self::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for TxOutSetHashType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Error returned when parsing an unknown hash type name
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown UTXO set hash type: {0:?}")]
+pub struct ParseTxOutSetHashTypeError(String);
+
+impl FromStr for TxOutSetHashType {
+    type Err = ParseTxOutSetHashTypeError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|hash_type| hash_type.as_str() == s)
+            .ok_or_else(|| ParseTxOutSetHashTypeError(s.to_string()))
+    }
+}
+
+/// Result of `gettxoutsetinfo`
+///
+/// Which optional fields are set depends on `hash_type` and on whether
+/// `-coinstatsindex` is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetTxOutSetInfoResult {
+    /// Height of the block the statistics are for
+    pub height: u32,
+    /// Hash of the block the statistics are for
+    pub bestblock: BlockHash,
+    /// Number of unspent outputs
+    pub txouts: u64,
+    /// Database-independent size metric of the UTXO set
+    pub bogosize: u64,
+    /// Serialized hash, for `hash_serialized_3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_serialized_3: Option<String>,
+    /// MuHash, for `muhash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muhash: Option<String>,
+    /// Number of transactions with unspent outputs, without the index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<u64>,
+    /// Size of the chainstate on disk, in bytes, without the index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<u64>,
+    /// Total value of the unspent outputs
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub total_amount: Amount,
+    /// Total value of unspendable outputs, with the index
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub total_unspendable_amount: Option<Amount>,
+    /// Changes to the UTXO set in the block, with the index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_info: Option<TxOutSetBlockInfo>,
+}
+
+impl GetTxOutSetInfoResult {
+    /// Returns the UTXO set hash, whichever type was computed
+    pub fn hash(&self) -> Option<&str> {
+        self.hash_serialized_3.as_deref().or(self.muhash.as_deref())
+    }
+}
+
+/// Changes a block made to the UTXO set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxOutSetBlockInfo {
+    /// Value of the outputs the block spent
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub prevout_spent: Amount,
+    /// Value of the coinbase outputs
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub coinbase: Amount,
+    /// Value of the other outputs the block created
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub new_outputs_ex_coinbase: Amount,
+    /// Value of the unspendable outputs the block created
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub unspendable: Amount,
+    /// Breakdown of `unspendable` by reason
+    pub unspendables: TxOutSetUnspendables,
+}
+
+/// Unspendable values of a block, by reason
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxOutSetUnspendables {
+    /// Genesis block coinbase
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub genesis_block: Amount,
+    /// Coinbases overwritten by duplicates before BIP30
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub bip30: Amount,
+    /// Provably unspendable output scripts
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub scripts: Amount,
+    /// Subsidy and fees the coinbase did not claim
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub unclaimed_rewards: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    #[test]
+    fn test_get_tx_out() {
+        let value = json!({
+            "bestblock": HASH,
+            "confirmations": 6,
+            "value": 0.5,
+            "scriptPubKey": {
+                "asm": "0 e8df018c7e326cc253faac7e46cdc51e68542c42",
+                "desc": "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)#00000000",
+                "hex": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+                "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                "type": "witness_v0_keyhash",
+            },
+            "coinbase": false,
+        });
+        let response: GetTxOutResponse = serde_json::from_value(value.clone()).unwrap();
+        let txout = response.unwrap();
+        assert_eq!(txout.value, Amount::from_sat(50_000_000));
+        assert!(!txout.is_unconfirmed());
+        assert_eq!(serde_json::to_value(&txout).unwrap(), value);
+
+        let spent: GetTxOutResponse = serde_json::from_value(json!(null)).unwrap();
+        assert_eq!(spent, None);
+    }
+
+    #[test]
+    fn test_get_tx_out_set_info() {
+        let value = json!({
+            "height": 840_000,
+            "bestblock": HASH,
+            "txouts": 177_000_000,
+            "bogosize": 13_000_000_000u64,
+            "muhash": "4b6c0b6e1a1b5d1a7b5e2a1e8a2d9c2b0c7a3f1e5d4c3b2a1908f7e6d5c4b3a2",
+            "total_amount": 19_687_500.0,
+            "total_unspendable_amount": 220.29,
+            "block_info": {
+                "prevout_spent": 1_000.0,
+                "coinbase": 3.125,
+                "new_outputs_ex_coinbase": 999.99,
+                "unspendable": 0.0,
+                "unspendables": {
+                    "genesis_block": 0.0,
+                    "bip30": 0.0,
+                    "scripts": 0.0,
+                    "unclaimed_rewards": 0.0,
+                },
+            },
+        });
+        let info: GetTxOutSetInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(info.hash(), info.muhash.as_deref());
+        assert_eq!(info.block_info.unwrap().coinbase, Amount::from_sat(312_500_000));
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+    }
+
+    #[test]
+    fn test_tx_out_set_hash_type() {
+        for hash_type in TxOutSetHashType::ALL {
+            assert_eq!(hash_type.to_string().parse::<TxOutSetHashType>(), Ok(hash_type));
+            assert_eq!(serde_json::to_value(hash_type).unwrap(), json!(hash_type.as_str()));
+        }
+        assert_eq!(TxOutSetHashType::default().as_str(), "hash_serialized_3");
+        assert!("hash_serialized_2".parse::<TxOutSetHashType>().is_err());
+    }
+}
@@ -0,0 +1,855 @@
+//! Validation of RPC requests and responses against the schema
+//!
+//! `validate_response` checks a JSON value returned by a node against a
+//! `BtcResult` tree: value types, required keys, unknown keys and nested
+//! structure. `BtcMethod::validate_params` does the same for request
+//! parameters.
+//!
+//! Each `ValidationError` carries the method name, a JSON pointer to the
+//! offending value and the schema node it was checked against. How strict
+//! validation is, and whether it stops at the first error, is controlled by
+//! `ValidationOptions`.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::call_plan::NamedParam;
+use crate::rpc_type::RpcType;
+use crate::types::{BtcArgument, BtcMethod, BtcResult, SchemaError};
+
+/// JSON value types, as reported in validation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonType {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Boolean,
+    /// Any number
+    Number,
+    /// A string
+    String,
+    /// An array
+    Array,
+    /// An object
+    Object,
+}
+
+impl JsonType {
+    /// Returns the type of a JSON value
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Boolean,
+            Value::Number(_) => Self::Number,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    /// Returns the lowercase name of the type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// The schema node a value was checked against
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaNode {
+    /// An argument, or a field of an argument
+    Argument(BtcArgument),
+    /// A result node
+    Result(BtcResult),
+}
+
+/// What is wrong with a value
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// The value does not have the schema type
+    TypeMismatch {
+        /// Type described by the schema
+        expected: RpcType,
+        /// Type of the value
+        actual: JsonType,
+    },
+    /// The value is outside an argument's bounds or allowed values
+    InvalidValue(String),
+    /// A required object key is missing
+    MissingKey(String),
+    /// An object has a key the schema does not describe
+    UnknownKey(String),
+    /// A fixed-length array has the wrong number of items
+    WrongLength {
+        /// Number of items described by the schema
+        expected: usize,
+        /// Number of items in the value
+        actual: usize,
+    },
+    /// More positional parameters than the method has arguments
+    TooManyParams {
+        /// Number of arguments of the method
+        max: usize,
+        /// Number of parameters given
+        actual: usize,
+    },
+    /// A required argument is missing
+    MissingArgument(String),
+    /// A named parameter matches no argument
+    UnknownArgument(String),
+    /// An omitted argument has no known default to send in its place, so
+    /// later arguments cannot be sent positionally
+    PositionalGap(String),
+    /// An argument is given more than once, e.g. under two of its names
+    DuplicateArgument(String),
+    /// The parameters are neither an array, an object nor `null`
+    InvalidParams(JsonType),
+}
+
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, actual } =>
+                write!(f, "expected {}, got {}", expected, actual),
+            Self::InvalidValue(reason) => f.write_str(reason),
+            Self::MissingKey(key) => write!(f, "missing required key {:?}", key),
+            Self::UnknownKey(key) => write!(f, "unknown key {:?}", key),
+            Self::WrongLength { expected, actual } =>
+                write!(f, "expected {} items, got {}", expected, actual),
+            Self::TooManyParams { max, actual } =>
+                write!(f, "expected at most {} parameters, got {}", max, actual),
+            Self::MissingArgument(name) => write!(f, "missing required argument {:?}", name),
+            Self::UnknownArgument(name) => write!(f, "unknown argument {:?}", name),
+            Self::PositionalGap(name) =>
+                write!(f, "omitted argument {:?} has no default to send positionally", name),
+            Self::DuplicateArgument(name) => write!(f, "argument {:?} given more than once", name),
+            Self::InvalidParams(actual) => write!(f, "expected array or object, got {}", actual),
+        }
+    }
+}
+
+/// A single validation failure
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// Method whose request or response was validated, when known
+    pub method: Option<String>,
+    /// JSON pointer (RFC 6901) to the offending value; empty for the root
+    pub pointer: String,
+    /// What is wrong with the value
+    pub kind: ValidationErrorKind,
+    /// Schema node the value was checked against
+    ///
+    /// For a missing key or argument this is the missing node; for an unknown
+    /// key, the object that does not describe it.
+    pub schema: Option<SchemaNode>,
+}
+
+impl ValidationError {
+    /// Returns the schema type expected by a type mismatch
+    pub fn expected_type(&self) -> Option<&RpcType> {
+        match &self.kind {
+            ValidationErrorKind::TypeMismatch { expected, .. } => Some(expected),
+            _ => None,
+        }
+    }
+
+    /// Returns the JSON type found by a type mismatch
+    pub fn actual_type(&self) -> Option<JsonType> {
+        match self.kind {
+            ValidationErrorKind::TypeMismatch { actual, .. } => Some(actual),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(method) = &self.method {
+            write!(f, "{}: ", method)?;
+        }
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        write!(f, "{}: {}", pointer, self.kind)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Whether validation reports every error or stops at the first
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Collect every error
+    #[default]
+    CollectAll,
+    /// Stop at the first error
+    FailFast,
+}
+
+/// Controls how strictly values are validated
+///
+/// The default rejects unknown keys, honours `skip_type_check`, treats `null`
+/// as a value rather than an omitted key, accepts amounts in any numeric form
+/// and collects every error. `strict` and `lenient` suit test harnesses and
+/// production proxies respectively.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Whether every error is collected or validation stops at the first
+    pub mode: ValidationMode,
+    /// Accept object keys the schema does not describe
+    ///
+    /// Newer nodes add fields to existing results, so proxies usually allow them.
+    pub allow_unknown_keys: bool,
+    /// Check results marked `skip_type_check` too
+    pub check_skipped: bool,
+    /// Accept `null` for optional result keys, as if the key were missing
+    pub null_as_missing: bool,
+    /// Require amounts to be JSON numbers
+    ///
+    /// Otherwise decimal BTC strings are accepted too.
+    pub strict_amounts: bool,
+}
+
+impl ValidationOptions {
+    /// Creates the default options
+    pub fn new() -> Self { Self::default() }
+
+    /// Options for test harnesses: everything is checked
+    pub fn strict() -> Self {
+        Self { check_skipped: true, strict_amounts: true, ..Self::default() }
+    }
+
+    /// Options for production proxies: tolerate fields added by newer nodes
+    /// and `null` for optional keys
+    pub fn lenient() -> Self {
+        Self { allow_unknown_keys: true, null_as_missing: true, ..Self::default() }
+    }
+}
+
+/// Checks a response value against a result tree with the default options
+pub fn validate_response(
+    result: &BtcResult,
+    value: &Value,
+) -> std::result::Result<(), Vec<ValidationError>> {
+    validate_response_with(result, value, &ValidationOptions::default())
+}
+
+/// Checks a response value against a result tree
+pub fn validate_response_with(
+    result: &BtcResult,
+    value: &Value,
+    options: &ValidationOptions,
+) -> std::result::Result<(), Vec<ValidationError>> {
+    let mut validator = Validator::new(options);
+    validator.result(result, value, "");
+    validator.finish(None)
+}
+
+impl BtcMethod {
+    /// Checks a response value against the method's results with the default options
+    ///
+    /// The value is valid if it matches any of the alternative top-level
+    /// results; otherwise the errors of the closest alternative are returned.
+    /// A method without results expects `null`.
+    pub fn validate_response(
+        &self,
+        value: &Value,
+    ) -> std::result::Result<(), Vec<ValidationError>> {
+        self.validate_response_with(value, &ValidationOptions::default())
+    }
+
+    /// Checks a response value against the method's results
+    pub fn validate_response_with(
+        &self,
+        value: &Value,
+        options: &ValidationOptions,
+    ) -> std::result::Result<(), Vec<ValidationError>> {
+        let none = BtcResult { type_: "none".to_string(), ..BtcResult::default() };
+        let results = if self.results.is_empty() {
+            std::slice::from_ref(&none)
+        } else {
+            self.results.as_slice()
+        };
+
+        let mut closest: Option<Vec<ValidationError>> = None;
+        for result in results {
+            let mut validator = Validator::new(options);
+            validator.result(result, value, "");
+            match validator.finish(Some(&self.name)) {
+                Ok(()) => return Ok(()),
+                Err(errors) =>
+                    if closest.as_ref().is_none_or(|closest| errors.len() < closest.len()) {
+                        closest = Some(errors);
+                    },
+            }
+        }
+        Err(closest.unwrap_or_default())
+    }
+
+    /// Checks request parameters against the method's arguments with the default options
+    ///
+    /// `params` is a positional array, an object keyed by argument name, or
+    /// `null` for no parameters. Arity, required arguments, types, bounds and
+    /// the keys of nested option objects are checked. As in Bitcoin Core,
+    /// `null` stands for an omitted argument, and fields of an
+    /// `object_named_params` argument may also be given by name at the top level.
+    pub fn validate_params(&self, params: &Value) -> std::result::Result<(), Vec<ValidationError>> {
+        self.validate_params_with(params, &ValidationOptions::default())
+    }
+
+    /// Checks request parameters against the method's arguments
+    pub fn validate_params_with(
+        &self,
+        params: &Value,
+        options: &ValidationOptions,
+    ) -> std::result::Result<(), Vec<ValidationError>> {
+        let mut validator = Validator::new(options);
+        validator.params(self, params);
+        validator.finish(Some(&self.name))
+    }
+}
+
+/// Walks a value alongside its schema, accumulating errors
+struct Validator<'a> {
+    options: &'a ValidationOptions,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(options: &'a ValidationOptions) -> Self { Self { options, errors: Vec::new() } }
+
+    /// Whether no further errors should be reported
+    fn is_done(&self) -> bool {
+        self.options.mode == ValidationMode::FailFast && !self.errors.is_empty()
+    }
+
+    /// Checks the type of a value, and the form of amounts when `strict_amounts` is set
+    ///
+    /// Amounts in results may be negative.
+    fn type_error(
+        &self,
+        rpc_type: &RpcType,
+        value: &Value,
+        result: bool,
+    ) -> Option<ValidationErrorKind> {
+        let mismatch = || ValidationErrorKind::TypeMismatch {
+            expected: rpc_type.clone(),
+            actual: JsonType::of(value),
+        };
+        let is_amount = rpc_type.alternatives().contains(&RpcType::Amount);
+        let matches = if result { rpc_type.matches_result(value) } else { rpc_type.matches(value) };
+        if !matches {
+            if is_amount && value.is_number() {
+                return Some(ValidationErrorKind::InvalidValue(format!(
+                    "{} is not a valid amount",
+                    value
+                )));
+            }
+            return Some(mismatch());
+        }
+        let other_match = rpc_type
+            .alternatives()
+            .iter()
+            .any(|alternative| *alternative != RpcType::Amount && alternative.matches(value));
+        if self.options.strict_amounts && is_amount && !other_match && !value.is_number() {
+            return Some(mismatch());
+        }
+        None
+    }
+
+    fn push(&mut self, pointer: &str, kind: ValidationErrorKind, schema: Option<SchemaNode>) {
+        if !self.is_done() {
+            self.errors.push(ValidationError {
+                method: None,
+                pointer: pointer.to_string(),
+                kind,
+                schema,
+            });
+        }
+    }
+
+    fn finish(self, method: Option<&str>) -> std::result::Result<(), Vec<ValidationError>> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        let mut errors = self.errors;
+        for error in &mut errors {
+            error.method = method.map(str::to_string);
+        }
+        Err(errors)
+    }
+
+    fn params(&mut self, method: &BtcMethod, params: &Value) {
+        let mut slots: Vec<Option<(String, &Value)>> = vec![None; method.arguments.len()];
+
+        match params {
+            Value::Null => {}
+            Value::Array(values) => {
+                if values.len() > method.arguments.len() {
+                    let kind = ValidationErrorKind::TooManyParams {
+                        max: method.arguments.len(),
+                        actual: values.len(),
+                    };
+                    self.push("", kind, None);
+                }
+                for (index, (slot, value)) in slots.iter_mut().zip(values).enumerate() {
+                    *slot = Some((format!("/{}", index), value));
+                }
+            }
+            Value::Object(map) =>
+                for (name, value) in map {
+                    let pointer = child_pointer("", name);
+                    match method.named_param(name) {
+                        Some(NamedParam::Argument(index)) =>
+                            if slots[index].replace((pointer.clone(), value)).is_some() {
+                                let schema = SchemaNode::Argument(method.arguments[index].clone());
+                                let kind = ValidationErrorKind::DuplicateArgument(name.clone());
+                                self.push(&pointer, kind, Some(schema));
+                            },
+                        Some(NamedParam::Field(_, field)) => self.argument(field, value, &pointer),
+                        None => self.push(
+                            &pointer,
+                            ValidationErrorKind::UnknownArgument(name.clone()),
+                            None,
+                        ),
+                    }
+                },
+            value => self.push("", ValidationErrorKind::InvalidParams(JsonType::of(value)), None),
+        }
+
+        for (slot, arg) in slots.iter().zip(&method.arguments) {
+            match slot {
+                Some((pointer, value)) if !value.is_null() => self.argument(arg, value, pointer),
+                _ if arg.required => {
+                    let kind = ValidationErrorKind::MissingArgument(argument_name(arg).to_string());
+                    self.push("", kind, Some(SchemaNode::Argument(arg.clone())));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn argument(&mut self, arg: &BtcArgument, value: &Value, pointer: &str) {
+        if self.is_done() {
+            return;
+        }
+
+        let rpc_type = arg.rpc_type();
+        if let Some(kind) = self.type_error(&rpc_type, value, false) {
+            self.push(pointer, kind, Some(SchemaNode::Argument(arg.clone())));
+            return;
+        }
+        if let Err(SchemaError::InvalidValue(reason)) = arg.validate_value(value) {
+            let kind = ValidationErrorKind::InvalidValue(reason);
+            self.push(pointer, kind, Some(SchemaNode::Argument(arg.clone())));
+            return;
+        }
+        if arg.inner.is_empty() {
+            return;
+        }
+
+        match value {
+            Value::Object(map) if rpc_type == RpcType::ObjectUserKeys =>
+                for (key, item) in map {
+                    self.argument_item(&arg.inner, item, &child_pointer(pointer, key));
+                },
+            Value::Object(map) => {
+                for field in &arg.inner {
+                    match map.iter().find(|(key, _)| field.names.contains(key)) {
+                        Some((key, item)) if !item.is_null() =>
+                            self.argument(field, item, &child_pointer(pointer, key)),
+                        _ if field.required => {
+                            let kind =
+                                ValidationErrorKind::MissingKey(argument_name(field).to_string());
+                            self.push(pointer, kind, Some(SchemaNode::Argument(field.clone())));
+                        }
+                        _ => {}
+                    }
+                }
+                for key in map.keys().filter(|_| !self.options.allow_unknown_keys) {
+                    if !arg.inner.iter().any(|field| field.names.contains(key)) {
+                        let kind = ValidationErrorKind::UnknownKey(key.clone());
+                        self.push(pointer, kind, Some(SchemaNode::Argument(arg.clone())));
+                    }
+                }
+            }
+            Value::Array(items) =>
+                for (index, item) in items.iter().enumerate() {
+                    self.argument_item(
+                        &arg.inner,
+                        item,
+                        &child_pointer(pointer, &index.to_string()),
+                    );
+                },
+            _ => {}
+        }
+    }
+
+    /// Validates an array element or map value against the alternatives; it must match at least one
+    fn argument_item(&mut self, schemas: &[BtcArgument], item: &Value, pointer: &str) {
+        let mut closest: Option<Vec<ValidationError>> = None;
+        for schema in schemas {
+            let mut validator = Validator::new(self.options);
+            validator.argument(schema, item, pointer);
+            if validator.errors.is_empty() {
+                return;
+            }
+            if closest.as_ref().is_none_or(|closest| validator.errors.len() < closest.len()) {
+                closest = Some(validator.errors);
+            }
+        }
+        for error in closest.unwrap_or_default() {
+            self.push(&error.pointer, error.kind, error.schema);
+        }
+    }
+
+    fn result(&mut self, result: &BtcResult, value: &Value, pointer: &str) {
+        if self.is_done() || (result.skip_type_check && !self.options.check_skipped) {
+            return;
+        }
+
+        let rpc_type = result.rpc_type();
+        if let Some(kind) = self.type_error(&rpc_type, value, true) {
+            self.push(pointer, kind, Some(SchemaNode::Result(result.clone())));
+            return;
+        }
+
+        match value {
+            Value::Object(map) if result.is_map() =>
+                if let Some(value_schema) = result.map_value() {
+                    for (key, item) in map {
+                        self.result(value_schema, item, &child_pointer(pointer, key));
+                    }
+                },
+            Value::Object(map) if rpc_type == RpcType::Object => self.object(result, map, pointer),
+            Value::Array(items) if rpc_type == RpcType::ArrayFixed => {
+                let schemas: Vec<&BtcResult> =
+                    result.inner.iter().filter(|inner| !inner.is_elision()).collect();
+                if items.len() != schemas.len() {
+                    let kind = ValidationErrorKind::WrongLength {
+                        expected: schemas.len(),
+                        actual: items.len(),
+                    };
+                    self.push(pointer, kind, Some(SchemaNode::Result(result.clone())));
+                }
+                for (index, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    self.result(schema, item, &child_pointer(pointer, &index.to_string()));
+                }
+            }
+            Value::Array(items) if rpc_type == RpcType::Array => {
+                let schemas: Vec<&BtcResult> =
+                    result.inner.iter().filter(|inner| !inner.is_elision()).collect();
+                for (index, item) in items.iter().enumerate() {
+                    self.item(&schemas, item, &child_pointer(pointer, &index.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn object(&mut self, result: &BtcResult, map: &Map<String, Value>, pointer: &str) {
+        let mut open = false;
+        for field in &result.inner {
+            if field.is_elision() || field.dynamic_key || field.key_name.is_empty() {
+                open = true;
+                continue;
+            }
+            match map.get(&field.key_name) {
+                Some(Value::Null) if field.optional && self.options.null_as_missing => {}
+                Some(item) => self.result(field, item, &child_pointer(pointer, &field.key_name)),
+                None if !field.optional => {
+                    let kind = ValidationErrorKind::MissingKey(field.key_name.clone());
+                    self.push(pointer, kind, Some(SchemaNode::Result(field.clone())));
+                }
+                None => {}
+            }
+        }
+
+        // An object without any described field is not checked for unknown keys
+        if open || result.inner.is_empty() || self.options.allow_unknown_keys {
+            return;
+        }
+        for key in map.keys() {
+            if !result.inner.iter().any(|field| field.key_name == *key) {
+                let kind = ValidationErrorKind::UnknownKey(key.clone());
+                self.push(pointer, kind, Some(SchemaNode::Result(result.clone())));
+            }
+        }
+    }
+
+    /// Validates an array element against the element schemas; it must match at least one
+    fn item(&mut self, schemas: &[&BtcResult], item: &Value, pointer: &str) {
+        let mut closest: Option<Vec<ValidationError>> = None;
+        for schema in schemas {
+            let mut validator = Validator::new(self.options);
+            validator.result(schema, item, pointer);
+            if validator.errors.is_empty() {
+                return;
+            }
+            if closest.as_ref().is_none_or(|closest| validator.errors.len() < closest.len()) {
+                closest = Some(validator.errors);
+            }
+        }
+        for error in closest.unwrap_or_default() {
+            self.push(&error.pointer, error.kind, error.schema);
+        }
+    }
+}
+
+fn argument_name(arg: &BtcArgument) -> &str {
+    arg.names.first().map(String::as_str).unwrap_or_default()
+}
+
+/// Appends a reference token to a JSON pointer, escaping `~` and `/`
+pub(crate) fn child_pointer(pointer: &str, token: &str) -> String {
+    format!("{}/{}", pointer, token.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn node(key_name: &str, type_: &str, inner: Vec<BtcResult>) -> BtcResult {
+        BtcResult {
+            key_name: key_name.to_string(),
+            type_: type_.to_string(),
+            inner,
+            ..BtcResult::default()
+        }
+    }
+
+    fn block() -> BtcResult {
+        node(
+            "",
+            "object",
+            vec![
+                node("hash", "hex", vec![]),
+                node("confirmations", "number", vec![]),
+                BtcResult { optional: true, ..node("nextblockhash", "hex", vec![]) },
+                node("tx", "array", vec![node("", "hex", vec![]), node("", "elision", vec![])]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_validate_response_ok() {
+        let value = json!({ "hash": "00ff", "confirmations": 3, "tx": ["aa", "bb"] });
+        assert_eq!(validate_response(&block(), &value), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_response_collects_errors() {
+        let value = json!({ "hash": 5, "tx": ["aa", 1], "extra": true });
+        let errors = validate_response(&block(), &value).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "/hash: expected hex, got number",
+                "/: missing required key \"confirmations\"",
+                "/tx/1: expected hex, got number",
+                "/: unknown key \"extra\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_response_maps_and_fixed_arrays() {
+        let mempool = node(
+            "",
+            "object_dynamic",
+            vec![node("", "object", vec![node("vsize", "number", vec![])])],
+        );
+        assert!(validate_response(&mempool, &json!({ "a/b": { "vsize": 1 } })).is_ok());
+        let errors = validate_response(&mempool, &json!({ "a/b": { "vsize": "1" } })).unwrap_err();
+        assert_eq!(errors[0].pointer, "/a~1b/vsize");
+
+        let pair =
+            node("", "array_fixed", vec![node("", "number", vec![]), node("", "string", vec![])]);
+        assert!(validate_response(&pair, &json!([1, "a"])).is_ok());
+        assert_eq!(validate_response(&pair, &json!([1])).unwrap_err().len(), 1);
+
+        let unchecked = BtcResult { skip_type_check: true, ..node("", "number", vec![]) };
+        assert!(validate_response(&unchecked, &json!("anything")).is_ok());
+    }
+
+    #[test]
+    fn test_method_validate_response() {
+        let getblock = BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![node("", "hex", vec![]), block()],
+            ..BtcMethod::default()
+        };
+        assert!(getblock.validate_response(&json!("00ff")).is_ok());
+        assert!(getblock
+            .validate_response(&json!({ "hash": "00", "confirmations": 1, "tx": [] }))
+            .is_ok());
+        let errors = getblock.validate_response(&json!({ "hash": "00", "tx": [] })).unwrap_err();
+        assert_eq!(errors.len(), 1);
+
+        let ping = BtcMethod { name: "ping".to_string(), ..BtcMethod::default() };
+        assert!(ping.validate_response(&Value::Null).is_ok());
+        assert!(ping.validate_response(&json!(1)).is_err());
+    }
+
+    fn arg(name: &str, type_: &str, required: bool) -> BtcArgument {
+        BtcArgument {
+            names: vec![name.to_string()],
+            type_: type_.to_string(),
+            required,
+            ..BtcArgument::default()
+        }
+    }
+
+    fn fundrawtransaction() -> BtcMethod {
+        BtcMethod {
+            name: "fundrawtransaction".to_string(),
+            arguments: vec![
+                arg("hexstring", "hex", true),
+                BtcArgument {
+                    inner: vec![
+                        arg("add_inputs", "boolean", false),
+                        BtcArgument {
+                            inner: vec![arg("vout_index", "number", false)],
+                            ..arg("subtract_fee_from_outputs", "array", false)
+                        },
+                    ],
+                    ..arg("options", "object_named_params", false)
+                },
+                BtcArgument { maximum: Some(1.0), ..arg("iswitness", "boolean", false) },
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_params_positional() {
+        let method = fundrawtransaction();
+        assert!(method.validate_params(&json!(["00ff"])).is_ok());
+        assert!(method.validate_params(&json!(["00ff", null, true])).is_ok());
+        assert!(method.validate_params(&json!(["00ff", { "add_inputs": false }])).is_ok());
+
+        let errors = method.validate_params(&json!([])).unwrap_err();
+        assert_eq!(errors[0].kind, ValidationErrorKind::MissingArgument("hexstring".to_string()));
+        assert_eq!(method.validate_params(&Value::Null).unwrap_err().len(), 1);
+
+        let errors = method.validate_params(&json!(["00ff", null, true, 1])).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "fundrawtransaction: /: expected at most 3 parameters, got 4"
+        );
+
+        let errors = method
+            .validate_params(&json!([
+                "0f0",
+                { "add_inputs": 1, "subtract_fee_from_outputs": [0, "1"], "foo": 1 }
+            ]))
+            .unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "fundrawtransaction: /0: expected hex, got string",
+                "fundrawtransaction: /1/add_inputs: expected boolean, got number",
+                "fundrawtransaction: /1/subtract_fee_from_outputs/1: expected number, got string",
+                "fundrawtransaction: /1: unknown key \"foo\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_params_named() {
+        let method = fundrawtransaction();
+        assert!(method.validate_params(&json!({ "hexstring": "00", "add_inputs": true })).is_ok());
+        assert!(method.validate_params(&json!({ "hexstring": "00", "iswitness": false })).is_ok());
+
+        let errors =
+            method.validate_params(&json!({ "add_inputs": "yes", "feerate": 1 })).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "fundrawtransaction: /add_inputs: expected boolean, got string",
+                "fundrawtransaction: /feerate: unknown argument \"feerate\"",
+                "fundrawtransaction: /: missing required argument \"hexstring\"",
+            ]
+        );
+
+        assert!(method.validate_params(&json!("00")).is_err());
+    }
+
+    #[test]
+    fn test_validation_error_details() {
+        let getblock = BtcMethod {
+            name: "getblock".to_string(),
+            results: vec![block()],
+            ..BtcMethod::default()
+        };
+        let value = json!({ "hash": "00", "confirmations": "3", "tx": [1, 2] });
+        let errors = getblock.validate_response(&value).unwrap_err();
+        assert_eq!(errors.len(), 3);
+
+        let error = &errors[0];
+        assert_eq!(error.method.as_deref(), Some("getblock"));
+        assert_eq!(error.pointer, "/confirmations");
+        assert_eq!(error.expected_type(), Some(&RpcType::Number));
+        assert_eq!(error.actual_type(), Some(JsonType::String));
+        match &error.schema {
+            Some(SchemaNode::Result(node)) => assert_eq!(node.key_name, "confirmations"),
+            other => panic!("unexpected schema node {:?}", other),
+        }
+        assert_eq!(error.to_string(), "getblock: /confirmations: expected number, got string");
+
+        let fail_fast =
+            ValidationOptions { mode: ValidationMode::FailFast, ..ValidationOptions::new() };
+        let errors = getblock.validate_response_with(&value, &fail_fast).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "/confirmations");
+
+        let errors = validate_response_with(&block(), &json!({}), &fail_fast);
+        assert_eq!(
+            errors.unwrap_err()[0].kind,
+            ValidationErrorKind::MissingKey("hash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validation_options() {
+        let value =
+            json!({ "hash": "00", "confirmations": 1, "nextblockhash": null, "tx": [], "new": 1 });
+        assert_eq!(validate_response(&block(), &value).unwrap_err().len(), 2);
+        assert!(validate_response_with(&block(), &value, &ValidationOptions::lenient()).is_ok());
+
+        let unchecked = BtcResult { skip_type_check: true, ..node("", "number", vec![]) };
+        assert!(validate_response(&unchecked, &json!("1")).is_ok());
+        assert!(
+            validate_response_with(&unchecked, &json!("1"), &ValidationOptions::strict()).is_err()
+        );
+
+        let fee = node("fee", "amount", vec![]);
+        let strict = ValidationOptions::strict();
+        assert!(validate_response(&fee, &json!("0.00000001")).is_ok());
+        assert!(validate_response(&fee, &json!("0.000000001")).is_err());
+        assert!(validate_response_with(&fee, &json!(-0.0001), &strict).is_ok());
+        let errors = validate_response_with(&fee, &json!(0.000000001), &strict).unwrap_err();
+        assert!(matches!(errors[0].kind, ValidationErrorKind::InvalidValue(_)));
+        let errors = validate_response_with(&fee, &json!("0.1"), &strict).unwrap_err();
+        assert_eq!(errors[0].actual_type(), Some(JsonType::String));
+
+        let method = fundrawtransaction();
+        let params = json!(["00", { "add_inputs": true, "foo": 1 }]);
+        assert!(method.validate_params(&params).is_err());
+        assert!(method.validate_params_with(&params, &ValidationOptions::lenient()).is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+//! Verbosity argument type
+//!
+//! This module provides the `Verbosity` type for the `verbosity` arguments of
+//! `getblock`, `getrawtransaction` and `getorphantxs`. Levels outside 0-3 are
+//! rejected on construction, and `BtcMethod::check_verbosity` checks a level
+//! against the result variants the schema lists for a method.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::types::BtcMethod;
+
+/// Verbosity level of an RPC result, from 0 to 3
+///
+/// Serializes as an integer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Verbosity(u8);
+
+impl Verbosity {
+    /// Highest verbosity level any method accepts
+    pub const MAX: u8 = 3;
+
+    /// Creates a verbosity level, failing above `Verbosity::MAX`
+    pub fn new(level: u8) -> std::result::Result<Self, VerbosityError> {
+        match level <= Self::MAX {
+            true => Ok(Self(level)),
+            false => Err(VerbosityError::OutOfRange(i64::from(level))),
+        }
+    }
+
+    /// Returns the level
+    pub fn level(&self) -> u8 { self.0 }
+}
+
+/// Error returned for a verbosity level that is out of range or not
+/// supported by a method
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VerbosityError {
+    /// Level outside 0 to `Verbosity::MAX`
+    #[error("Verbosity {0} is out of range 0 to {max}", max = Verbosity::MAX)]
+    OutOfRange(i64),
+    /// Level without a result variant for the method
+    #[error("{method} does not support verbosity {level}")]
+    Unsupported {
+        /// Name of the method
+        method: String,
+        /// Requested level
+        level: u8,
+    },
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.0) }
+}
+
+impl TryFrom<u8> for Verbosity {
+    type Error = VerbosityError;
+
+    fn try_from(level: u8) -> std::result::Result<Self, Self::Error> { Self::new(level) }
+}
+
+impl TryFrom<i64> for Verbosity {
+    type Error = VerbosityError;
+
+    fn try_from(level: i64) -> std::result::Result<Self, Self::Error> {
+        u8::try_from(level).map_err(|_| VerbosityError::OutOfRange(level)).and_then(Self::new)
+    }
+}
+
+impl From<Verbosity> for u8 {
+    fn from(verbosity: Verbosity) -> Self { verbosity.0 }
+}
+
+impl Serialize for Verbosity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Verbosity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Self::try_from(i64::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl BtcMethod {
+    /// Returns whether the schema lists a result for the verbosity level
+    pub fn supports_verbosity(&self, verbosity: Verbosity) -> bool {
+        self.verbosity_levels().contains(&verbosity.level())
+    }
+
+    /// Checks a verbosity level against the range and the method's result
+    /// variants
+    pub fn check_verbosity(&self, level: u8) -> std::result::Result<Verbosity, VerbosityError> {
+        let verbosity = Verbosity::new(level)?;
+        match self.supports_verbosity(verbosity) {
+            true => Ok(verbosity),
+            false => Err(VerbosityError::Unsupported { method: self.name.clone(), level }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BtcResult;
+
+    fn getrawtransaction() -> BtcMethod {
+        let result = |condition: &str| BtcResult {
+            type_: "object".to_string(),
+            condition: condition.to_string(),
+            ..BtcResult::default()
+        };
+        BtcMethod {
+            name: "getrawtransaction".to_string(),
+            results: vec![
+                result("if verbosity is not set or set to 0"),
+                result("if verbosity is set to 1"),
+                result("for verbosity = 2"),
+            ],
+            ..BtcMethod::default()
+        }
+    }
+
+    #[test]
+    fn test_verbosity_range() {
+        assert_eq!(Verbosity::new(2).unwrap().level(), 2);
+        assert_eq!(Verbosity::default().level(), 0);
+        assert_eq!(Verbosity::new(4), Err(VerbosityError::OutOfRange(4)));
+        assert_eq!(Verbosity::try_from(-1i64), Err(VerbosityError::OutOfRange(-1)));
+        assert_eq!(VerbosityError::OutOfRange(4).to_string(), "Verbosity 4 is out of range 0 to 3");
+
+        assert_eq!(serde_json::to_string(&Verbosity::new(3).unwrap()).unwrap(), "3");
+        assert_eq!(serde_json::from_str::<Verbosity>("1").unwrap(), Verbosity::new(1).unwrap());
+        assert!(serde_json::from_str::<Verbosity>("7").is_err());
+        assert!(serde_json::from_str::<Verbosity>("true").is_err());
+    }
+
+    #[test]
+    fn test_check_verbosity() {
+        let method = getrawtransaction();
+        assert_eq!(method.check_verbosity(2), Ok(Verbosity::new(2).unwrap()));
+        assert_eq!(
+            method.check_verbosity(3),
+            Err(VerbosityError::Unsupported { method: "getrawtransaction".to_string(), level: 3 })
+        );
+        assert_eq!(method.check_verbosity(9), Err(VerbosityError::OutOfRange(9)));
+        assert!(!BtcMethod::default().supports_verbosity(Verbosity::default()));
+    }
+}
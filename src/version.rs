@@ -0,0 +1,120 @@
+//! Bitcoin Core version type
+//!
+//! This module provides the `CoreVersion` type used to identify the Bitcoin
+//! Core release a schema describes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// A Bitcoin Core release version (`major.minor`)
+///
+/// Versions are ordered, display as `28.0`, and parse from `28`, `28.0` or
+/// `v28.0`. Serialized as a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoreVersion {
+    major: u32,
+    minor: u32,
+}
+
+impl CoreVersion {
+    /// Bitcoin Core v25.0
+    pub const V25: CoreVersion = CoreVersion::new(25, 0);
+    /// Bitcoin Core v26.0
+    pub const V26: CoreVersion = CoreVersion::new(26, 0);
+    /// Bitcoin Core v27.0
+    pub const V27: CoreVersion = CoreVersion::new(27, 0);
+    /// Bitcoin Core v28.0
+    pub const V28: CoreVersion = CoreVersion::new(28, 0);
+    /// Bitcoin Core v29.0
+    pub const V29: CoreVersion = CoreVersion::new(29, 0);
+
+    /// Creates a new version from its major and minor components
+    pub const fn new(major: u32, minor: u32) -> Self { Self { major, minor } }
+
+    /// Returns the major version component
+    pub fn major(&self) -> u32 { self.major }
+
+    /// Returns the minor version component
+    pub fn minor(&self) -> u32 { self.minor }
+}
+
+impl fmt::Display for CoreVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Error returned when parsing a `CoreVersion` fails
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid Bitcoin Core version: {0:?}")]
+pub struct ParseVersionError(String);
+
+impl FromStr for CoreVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let err = || ParseVersionError(s.to_string());
+        let trimmed = s.trim();
+        let digits = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let mut parts = digits.split('.');
+        let major = parts.next().and_then(|part| part.parse().ok()).ok_or_else(err)?;
+        let minor = match parts.next() {
+            Some(part) => part.parse().map_err(|_| err())?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return Err(err());
+        }
+        Ok(Self::new(major, minor))
+    }
+}
+
+impl Serialize for CoreVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CoreVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_version_parse() {
+        assert_eq!("28".parse::<CoreVersion>().unwrap(), CoreVersion::V28);
+        assert_eq!("28.0".parse::<CoreVersion>().unwrap(), CoreVersion::V28);
+        assert_eq!("v28.0".parse::<CoreVersion>().unwrap(), CoreVersion::V28);
+        assert_eq!("28.1".parse::<CoreVersion>().unwrap(), CoreVersion::new(28, 1));
+
+        assert!("".parse::<CoreVersion>().is_err());
+        assert!("v".parse::<CoreVersion>().is_err());
+        assert!("28.x".parse::<CoreVersion>().is_err());
+        assert!("28.0.1".parse::<CoreVersion>().is_err());
+    }
+
+    #[test]
+    fn test_core_version_ordering_and_display() {
+        assert!(CoreVersion::V25 < CoreVersion::V29);
+        assert!(CoreVersion::new(28, 1) > CoreVersion::V28);
+        assert_eq!(CoreVersion::V27.to_string(), "27.0");
+    }
+
+    #[test]
+    fn test_core_version_serde() {
+        let json = serde_json::to_string(&CoreVersion::V26).unwrap();
+        assert_eq!(json, "\"26.0\"");
+        let version: CoreVersion = serde_json::from_str("\"v26\"").unwrap();
+        assert_eq!(version, CoreVersion::V26);
+        assert!(serde_json::from_str::<CoreVersion>("\"latest\"").is_err());
+    }
+}
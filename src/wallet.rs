@@ -0,0 +1,392 @@
+//! Wallet parameter and result types
+//!
+//! This module provides `CreateWalletOptions`, the optional arguments of
+//! `createwallet` in argument order, built with `CreateWalletOptionsBuilder`,
+//! the `CreateWalletResult`, and `GetWalletInfoResult`.
+
+use bitcoin::{Amount, BlockHash};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::call_plan::CallPlan;
+use crate::types::{Result, SchemaError};
+
+/// Optional arguments of `createwallet`
+///
+/// `None` leaves an argument to Bitcoin Core's default. The fields are in
+/// argument order, after `wallet_name`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateWalletOptions {
+    /// Disable private keys; only watch-only keys can be imported
+    pub disable_private_keys: Option<bool>,
+    /// Create a wallet without keys or HD seed
+    pub blank: Option<bool>,
+    /// Encrypt the wallet with this passphrase
+    pub passphrase: Option<String>,
+    /// Track clean and dirty coins to avoid address reuse
+    pub avoid_reuse: Option<bool>,
+    /// Create a descriptor wallet
+    pub descriptors: Option<bool>,
+    /// Load the wallet on node startup; `None` leaves the setting unchanged
+    pub load_on_startup: Option<bool>,
+    /// Use an external signer such as a hardware wallet
+    pub external_signer: Option<bool>,
+}
+
+impl CreateWalletOptions {
+    /// Names of the `createwallet` arguments, in positional order
+    pub const ARGUMENT_NAMES: [&'static str; 8] = [
+        "wallet_name",
+        "disable_private_keys",
+        "blank",
+        "passphrase",
+        "avoid_reuse",
+        "descriptors",
+        "load_on_startup",
+        "external_signer",
+    ];
+
+    /// Starts building options
+    pub fn builder() -> CreateWalletOptionsBuilder { CreateWalletOptionsBuilder::default() }
+
+    /// Returns the argument values in positional order, `None` for omitted ones
+    pub fn to_args(&self, wallet_name: &str) -> Vec<Option<Value>> {
+        vec![
+            Some(Value::from(wallet_name)),
+            self.disable_private_keys.map(Value::from),
+            self.blank.map(Value::from),
+            self.passphrase.clone().map(Value::from),
+            self.avoid_reuse.map(Value::from),
+            self.descriptors.map(Value::from),
+            self.load_on_startup.map(Value::from),
+            self.external_signer.map(Value::from),
+        ]
+    }
+
+    /// Plans how to send the arguments
+    ///
+    /// The plan is positional unless an omitted argument precedes a given
+    /// one; omitted arguments are never sent as `null`.
+    pub fn call_plan(&self, wallet_name: &str) -> CallPlan {
+        CallPlan::from_named(&Self::ARGUMENT_NAMES, self.to_named(wallet_name))
+    }
+
+    /// Returns named parameters, without the omitted arguments
+    pub fn to_named(&self, wallet_name: &str) -> Map<String, Value> {
+        Self::ARGUMENT_NAMES
+            .iter()
+            .zip(self.to_args(wallet_name))
+            .filter_map(|(name, arg)| Some((name.to_string(), arg?)))
+            .collect()
+    }
+}
+
+/// Builder for `CreateWalletOptions`
+///
+/// `build()` rejects the combinations `createwallet` rejects.
+#[derive(Debug, Clone, Default)]
+pub struct CreateWalletOptionsBuilder {
+    options: CreateWalletOptions,
+}
+
+impl CreateWalletOptionsBuilder {
+    /// Sets whether private keys are disabled
+    pub fn disable_private_keys(mut self, disable_private_keys: bool) -> Self {
+        self.options.disable_private_keys = Some(disable_private_keys);
+        self
+    }
+
+    /// Sets whether the wallet is created blank
+    pub fn blank(mut self, blank: bool) -> Self {
+        self.options.blank = Some(blank);
+        self
+    }
+
+    /// Sets the passphrase to encrypt the wallet with
+    pub fn passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.options.passphrase = Some(passphrase.into());
+        self
+    }
+
+    /// Sets whether address reuse is avoided
+    pub fn avoid_reuse(mut self, avoid_reuse: bool) -> Self {
+        self.options.avoid_reuse = Some(avoid_reuse);
+        self
+    }
+
+    /// Sets whether a descriptor wallet is created
+    pub fn descriptors(mut self, descriptors: bool) -> Self {
+        self.options.descriptors = Some(descriptors);
+        self
+    }
+
+    /// Sets whether the wallet is loaded on startup
+    pub fn load_on_startup(mut self, load_on_startup: bool) -> Self {
+        self.options.load_on_startup = Some(load_on_startup);
+        self
+    }
+
+    /// Sets whether an external signer is used
+    pub fn external_signer(mut self, external_signer: bool) -> Self {
+        self.options.external_signer = Some(external_signer);
+        self
+    }
+
+    /// Validates and returns the options
+    ///
+    /// A passphrase needs private keys, and an external signer needs private
+    /// keys disabled and a descriptor wallet.
+    pub fn build(self) -> Result<CreateWalletOptions> {
+        let options = self.options;
+        let disable_private_keys = options.disable_private_keys == Some(true);
+        let invalid =
+            |reason: &str| Err(SchemaError::InvalidValue(format!("createwallet: {}", reason)));
+        if disable_private_keys && options.passphrase.as_deref().is_some_and(|p| !p.is_empty()) {
+            return invalid("a passphrase cannot be used with private keys disabled");
+        }
+        if options.external_signer == Some(true) {
+            if !disable_private_keys {
+                return invalid("an external signer requires private keys to be disabled");
+            }
+            if options.descriptors == Some(false) {
+                return invalid("an external signer requires a descriptor wallet");
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Result of `createwallet`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateWalletResult {
+    /// Name of the created wallet
+    pub name: String,
+    /// Warnings about the wallet creation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result of `getwalletinfo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetWalletInfoResult {
+    /// Name of the wallet
+    pub walletname: String,
+    /// Version of the wallet
+    pub walletversion: u32,
+    /// Database format, `bdb` or `sqlite`
+    pub format: String,
+    /// Trusted balance; deprecated in favour of `getbalances`
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub balance: Option<Amount>,
+    /// Untrusted pending balance; deprecated in favour of `getbalances`
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unconfirmed_balance: Option<Amount>,
+    /// Immature coinbase balance; deprecated in favour of `getbalances`
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub immature_balance: Option<Amount>,
+    /// Number of transactions in the wallet
+    pub txcount: u64,
+    /// Timestamp of the oldest pre-generated key, for legacy wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypoololdest: Option<u64>,
+    /// Number of pre-generated external keys
+    pub keypoolsize: u64,
+    /// Number of pre-generated internal keys, for HD wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypoolsize_hd_internal: Option<u64>,
+    /// Timestamp until which the wallet is unlocked, 0 if locked; only for
+    /// encrypted wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocked_until: Option<u64>,
+    /// Fee rate set with `settxfee`, per kvB
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub paytxfee: Amount,
+    /// Hash160 of the HD seed, for legacy HD wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdseedid: Option<String>,
+    /// Whether private keys are enabled
+    pub private_keys_enabled: bool,
+    /// Whether address reuse is avoided
+    pub avoid_reuse: bool,
+    /// Progress of a running rescan
+    pub scanning: WalletScanning,
+    /// Whether the wallet uses descriptors
+    pub descriptors: bool,
+    /// Whether an external signer is used
+    pub external_signer: bool,
+    /// Whether the wallet was created blank (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blank: Option<bool>,
+    /// Timestamp of the oldest key or descriptor (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub birthtime: Option<u64>,
+    /// Block the wallet was last synced to (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastprocessedblock: Option<LastProcessedBlock>,
+}
+
+/// Rescan state of a wallet
+///
+/// Serializes as `false` when not scanning, as Bitcoin Core does.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WalletScanning {
+    /// No rescan is running
+    #[default]
+    NotScanning,
+    /// A rescan is running
+    Scanning {
+        /// Elapsed time in seconds
+        duration: u64,
+        /// Fraction scanned, from 0 to 1
+        progress: f64,
+    },
+}
+
+impl WalletScanning {
+    /// Returns whether a rescan is running
+    pub fn is_scanning(&self) -> bool { matches!(self, Self::Scanning { .. }) }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum WalletScanningRepr {
+    Flag(bool),
+    Progress { duration: u64, progress: f64 },
+}
+
+impl Serialize for WalletScanning {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match *self {
+            Self::NotScanning => WalletScanningRepr::Flag(false),
+            Self::Scanning { duration, progress } =>
+                WalletScanningRepr::Progress { duration, progress },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for WalletScanning {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match WalletScanningRepr::deserialize(deserializer)? {
+            WalletScanningRepr::Flag(false) => Ok(Self::NotScanning),
+            WalletScanningRepr::Flag(true) =>
+                Err(de::Error::custom("expected false or a scanning progress object")),
+            WalletScanningRepr::Progress { duration, progress } =>
+                Ok(Self::Scanning { duration, progress }),
+        }
+    }
+}
+
+/// Block a wallet was last synced to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LastProcessedBlock {
+    /// Hash of the block
+    pub hash: BlockHash,
+    /// Height of the block
+    pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_create_wallet_params() {
+        let options = CreateWalletOptions::builder().blank(true).avoid_reuse(true).build().unwrap();
+        assert_eq!(
+            options.call_plan("w").into_params(),
+            json!({ "wallet_name": "w", "blank": true, "avoid_reuse": true })
+        );
+        assert_eq!(
+            Value::Object(options.to_named("w")),
+            json!({ "wallet_name": "w", "blank": true, "avoid_reuse": true })
+        );
+        let options = CreateWalletOptions::builder().disable_private_keys(true).build().unwrap();
+        assert_eq!(options.call_plan("w").into_params(), json!(["w", true]));
+        assert_eq!(CreateWalletOptions::default().call_plan("w").into_params(), json!(["w"]));
+    }
+
+    #[test]
+    fn test_create_wallet_options_build() {
+        assert!(CreateWalletOptions::builder()
+            .disable_private_keys(true)
+            .passphrase("secret")
+            .build()
+            .is_err());
+        assert!(CreateWalletOptions::builder().external_signer(true).build().is_err());
+        assert!(CreateWalletOptions::builder()
+            .external_signer(true)
+            .disable_private_keys(true)
+            .descriptors(false)
+            .build()
+            .is_err());
+        assert!(CreateWalletOptions::builder()
+            .external_signer(true)
+            .disable_private_keys(true)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_create_wallet_result() {
+        let result: CreateWalletResult = serde_json::from_value(json!({ "name": "w" })).unwrap();
+        assert_eq!(result, CreateWalletResult { name: "w".to_string(), warnings: vec![] });
+        let result: CreateWalletResult = serde_json::from_value(
+            json!({ "name": "w", "warnings": ["Empty string given as passphrase"] }),
+        )
+        .unwrap();
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_get_wallet_info() {
+        let value = json!({
+            "walletname": "w",
+            "walletversion": 169900,
+            "format": "sqlite",
+            "txcount": 3,
+            "keypoolsize": 4000,
+            "keypoolsize_hd_internal": 4000,
+            "paytxfee": 0.0,
+            "private_keys_enabled": true,
+            "avoid_reuse": false,
+            "scanning": false,
+            "descriptors": true,
+            "external_signer": false,
+            "blank": false,
+            "birthtime": 1_700_000_000,
+            "lastprocessedblock": {
+                "hash": "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
+                "height": 0,
+            },
+        });
+        let info: GetWalletInfoResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(info.scanning, WalletScanning::NotScanning);
+        assert_eq!(info.paytxfee, Amount::ZERO);
+        assert_eq!(info.lastprocessedblock.unwrap().height, 0);
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+
+        let scanning: WalletScanning =
+            serde_json::from_value(json!({ "duration": 12, "progress": 0.5 })).unwrap();
+        assert!(scanning.is_scanning());
+        assert_eq!(
+            serde_json::to_value(scanning).unwrap(),
+            json!({ "duration": 12, "progress": 0.5 })
+        );
+        assert!(serde_json::from_value::<WalletScanning>(json!(true)).is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//! Wallet transaction result types
+//!
+//! This module provides `GetTransactionResult`, returned by `gettransaction`,
+//! with the `TransactionCategory` and `Bip125Replaceable` values shared by
+//! the wallet's transaction listings, `ListTransactionsEntry`, an entry of
+//! `listtransactions` and `listsinceblock`, `ListSinceBlockResult` with the
+//! `ListSinceBlockOptions` flags, and `ListUnspentEntry`, an entry of
+//! `listunspent`.
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::{Address, Amount, BlockHash, ScriptBuf, SignedAmount, Txid, Wtxid};
+use serde::{Deserialize, Serialize};
+
+use crate::decoded_tx::DecodedTransaction;
+use crate::raw_tx::RawTxHex;
+use crate::wallet::LastProcessedBlock;
+
+/// Category of a wallet transaction entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionCategory {
+    /// Outgoing payment
+    Send,
+    /// Incoming payment
+    Receive,
+    /// Mature coinbase output
+    Generate,
+    /// Coinbase output that is not yet spendable
+    Immature,
+    /// Coinbase output of a block no longer in the main chain
+    Orphan,
+}
+
+/// BIP125 replaceability of a wallet transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bip125Replaceable {
+    /// Signals or inherits replaceability
+    Yes,
+    /// Not replaceable
+    No,
+    /// Unconfirmed with unconfirmed ancestors outside the mempool
+    Unknown,
+}
+
+/// Result of `gettransaction`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetTransactionResult {
+    /// Net amount for the wallet
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: SignedAmount,
+    /// Fee, negative, for transactions sent by the wallet
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<SignedAmount>,
+    /// Number of confirmations, negative for conflicted transactions
+    pub confirmations: i64,
+    /// Whether the transaction is a coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<bool>,
+    /// Whether an unconfirmed transaction is considered spendable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trusted: Option<bool>,
+    /// Hash of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Height of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockheight: Option<u32>,
+    /// Index of the transaction in the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockindex: Option<u32>,
+    /// Timestamp of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocktime: Option<u64>,
+    /// Transaction id
+    pub txid: Txid,
+    /// Witness transaction id
+    pub wtxid: Wtxid,
+    /// Wallet transactions spending the same inputs
+    pub walletconflicts: Vec<Txid>,
+    /// Transaction that replaced this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by_txid: Option<Txid>,
+    /// Transaction this one replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces_txid: Option<Txid>,
+    /// Mempool transactions spending the same inputs (Bitcoin Core 28+)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mempoolconflicts: Vec<Txid>,
+    /// Payment request `to` field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Time the transaction was first seen, or the block time if earlier
+    pub time: u64,
+    /// Time the wallet received the transaction
+    pub timereceived: u64,
+    /// Comment set when sending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// BIP125 replaceability
+    #[serde(rename = "bip125-replaceable")]
+    pub bip125_replaceable: Bip125Replaceable,
+    /// Descriptors of the wallet outputs the transaction pays to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_descs: Vec<String>,
+    /// Per-output breakdown of the amount
+    pub details: Vec<GetTransactionDetail>,
+    /// The transaction
+    pub hex: RawTxHex,
+    /// Decoded transaction, with `verbose` set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedTransaction>,
+    /// Block the wallet was synced to (Bitcoin Core 26+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastprocessedblock: Option<LastProcessedBlock>,
+}
+
+/// Output of a wallet transaction, in `gettransaction` details
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetTransactionDetail {
+    /// Whether the output involves a watch-only address
+    #[serde(rename = "involvesWatchonly", default, skip_serializing_if = "Option::is_none")]
+    pub involves_watchonly: Option<bool>,
+    /// Address of the output, absent for data outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address<NetworkUnchecked>>,
+    /// Category of the output
+    pub category: TransactionCategory,
+    /// Amount, negative for sends
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: SignedAmount,
+    /// Label of the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Output index
+    pub vout: u32,
+    /// Fee, negative, for sends
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<SignedAmount>,
+    /// Whether the transaction was abandoned, for sends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abandoned: Option<bool>,
+    /// Descriptors of the wallet output
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_descs: Vec<String>,
+}
+
+/// Entry of `listtransactions`, and of the `transactions` and `removed`
+/// arrays of `listsinceblock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListTransactionsEntry {
+    /// Whether the entry involves a watch-only address
+    #[serde(rename = "involvesWatchonly", default, skip_serializing_if = "Option::is_none")]
+    pub involves_watchonly: Option<bool>,
+    /// Address of the output, absent for data outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address<NetworkUnchecked>>,
+    /// Category of the entry
+    pub category: TransactionCategory,
+    /// Amount, negative for sends
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: SignedAmount,
+    /// Label of the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Output index
+    pub vout: u32,
+    /// Fee, negative, for sends
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_btc::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee: Option<SignedAmount>,
+    /// Number of confirmations, negative for conflicted transactions
+    pub confirmations: i64,
+    /// Whether the transaction is a coinbase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<bool>,
+    /// Whether an unconfirmed transaction is considered spendable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trusted: Option<bool>,
+    /// Hash of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Height of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockheight: Option<u32>,
+    /// Index of the transaction in the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockindex: Option<u32>,
+    /// Timestamp of the containing block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocktime: Option<u64>,
+    /// Transaction id
+    pub txid: Txid,
+    /// Witness transaction id
+    pub wtxid: Wtxid,
+    /// Wallet transactions spending the same inputs
+    pub walletconflicts: Vec<Txid>,
+    /// Transaction that replaced this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by_txid: Option<Txid>,
+    /// Transaction this one replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces_txid: Option<Txid>,
+    /// Mempool transactions spending the same inputs (Bitcoin Core 28+)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mempoolconflicts: Vec<Txid>,
+    /// Payment request `to` field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Time the transaction was first seen, or the block time if earlier
+    pub time: u64,
+    /// Time the wallet received the transaction
+    pub timereceived: u64,
+    /// Comment set when sending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// BIP125 replaceability
+    #[serde(rename = "bip125-replaceable")]
+    pub bip125_replaceable: Bip125Replaceable,
+    /// Descriptors of the wallet output
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_descs: Vec<String>,
+    /// Whether the transaction was abandoned, for sends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abandoned: Option<bool>,
+}
+
+impl ListTransactionsEntry {
+    /// Returns whether the transaction is in a block of the main chain
+    pub fn is_confirmed(&self) -> bool { self.confirmations > 0 }
+}
+
+/// Named parameters of `listsinceblock`
+///
+/// Unset fields are left out, so the node applies its defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListSinceBlockOptions {
+    /// Block to list transactions since, all transactions if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<BlockHash>,
+    /// Depth of the block returned as `lastblock`, 1 (the tip) by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_confirmations: Option<u32>,
+    /// Whether to include watch-only addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_watchonly: Option<bool>,
+    /// Whether to report transactions removed by reorgs in `removed`, true
+    /// by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_removed: Option<bool>,
+    /// Whether to include change outputs in `transactions`, false by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_change: Option<bool>,
+    /// Only list transactions with this label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Result of `listsinceblock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListSinceBlockResult {
+    /// Entries of transactions since the block, with change outputs if
+    /// `include_change` is set
+    pub transactions: Vec<ListTransactionsEntry>,
+    /// Entries of transactions removed from the main chain by reorgs since
+    /// the block, absent if `include_removed` is false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Vec<ListTransactionsEntry>>,
+    /// Hash of the block `target_confirmations` deep, to pass as the next
+    /// `blockhash`
+    pub lastblock: BlockHash,
+}
+
+impl ListSinceBlockResult {
+    /// Returns the entries removed by reorgs, empty if none were or they
+    /// were not requested
+    pub fn removed(&self) -> &[ListTransactionsEntry] { self.removed.as_deref().unwrap_or(&[]) }
+}
+
+/// Entry of `listunspent`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListUnspentEntry {
+    /// Transaction id of the output
+    pub txid: Txid,
+    /// Output index
+    pub vout: u32,
+    /// Address of the output, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address<NetworkUnchecked>>,
+    /// Label of the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Output script
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: ScriptBuf,
+    /// Amount of the output
+    #[serde(with = "bitcoin::amount::serde::as_btc")]
+    pub amount: Amount,
+    /// Number of confirmations
+    pub confirmations: u32,
+    /// Number of in-mempool ancestors, including this transaction, if
+    /// unconfirmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ancestorcount: Option<u64>,
+    /// Virtual size of the in-mempool ancestors, if unconfirmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ancestorsize: Option<u64>,
+    /// Modified fees of the in-mempool ancestors, if unconfirmed; sent in
+    /// satoshis
+    #[serde(
+        default,
+        with = "bitcoin::amount::serde::as_sat::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ancestorfees: Option<Amount>,
+    /// Redeem script, for P2SH outputs
+    #[serde(rename = "redeemScript", default, skip_serializing_if = "Option::is_none")]
+    pub redeem_script: Option<ScriptBuf>,
+    /// Witness script, for P2WSH and P2SH-P2WSH outputs
+    #[serde(rename = "witnessScript", default, skip_serializing_if = "Option::is_none")]
+    pub witness_script: Option<ScriptBuf>,
+    /// Whether the wallet has the keys to spend the output
+    pub spendable: bool,
+    /// Whether the wallet knows how to spend the output
+    pub solvable: bool,
+    /// Whether the address was used before, with `avoid_reuse`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reused: Option<bool>,
+    /// Descriptor of the output, if solvable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Descriptors of the wallet that match the output
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_descs: Vec<String>,
+    /// Whether the output is considered safe to spend
+    pub safe: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    #[test]
+    fn test_get_transaction() {
+        let value = json!({
+            "amount": -0.1,
+            "fee": -0.0000141,
+            "confirmations": 2,
+            "blockhash": HASH,
+            "blockheight": 840_000,
+            "blockindex": 5,
+            "blocktime": 1_713_571_767,
+            "txid": TXID,
+            "wtxid": TXID,
+            "walletconflicts": [],
+            "time": 1_713_571_700,
+            "timereceived": 1_713_571_700,
+            "bip125-replaceable": "no",
+            "details": [{
+                "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                "category": "send",
+                "amount": -0.1,
+                "vout": 0,
+                "fee": -0.0000141,
+                "abandoned": false,
+            }],
+            "hex": "0200",
+            "lastprocessedblock": { "hash": HASH, "height": 840_001 },
+        });
+        let tx: GetTransactionResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(tx.amount, SignedAmount::from_sat(-10_000_000));
+        assert_eq!(tx.fee, Some(SignedAmount::from_sat(-1410)));
+        assert_eq!(tx.bip125_replaceable, Bip125Replaceable::No);
+        assert_eq!(tx.details[0].category, TransactionCategory::Send);
+        assert_eq!(serde_json::to_value(&tx).unwrap(), value);
+    }
+
+    #[test]
+    fn test_list_unspent_entry() {
+        let value = json!({
+            "txid": TXID,
+            "vout": 1,
+            "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "label": "",
+            "scriptPubKey": "0014e8df018c7e326cc253faac7e46cdc51e68542c42",
+            "amount": 0.25,
+            "confirmations": 0,
+            "ancestorcount": 1,
+            "ancestorsize": 141,
+            "ancestorfees": 1410,
+            "spendable": true,
+            "solvable": true,
+            "desc": "wpkh([d34db33f/84h/0h/0h/0/1]02...)#00000000",
+            "parent_descs": ["wpkh(xpub/0/*)#00000000"],
+            "safe": false,
+        });
+        let entry: ListUnspentEntry = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(entry.amount, Amount::from_sat(25_000_000));
+        assert_eq!(entry.ancestorfees, Some(Amount::from_sat(1410)));
+        assert!(entry.script_pubkey.is_p2wpkh());
+        assert_eq!(serde_json::to_value(&entry).unwrap(), value);
+    }
+
+    #[test]
+    fn test_list_transactions_entry() {
+        let value = json!({
+            "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "category": "receive",
+            "amount": 0.5,
+            "label": "savings",
+            "vout": 0,
+            "confirmations": 0,
+            "trusted": false,
+            "txid": TXID,
+            "wtxid": TXID,
+            "walletconflicts": [],
+            "mempoolconflicts": [TXID],
+            "time": 1_713_571_700,
+            "timereceived": 1_713_571_700,
+            "bip125-replaceable": "yes",
+            "parent_descs": ["wpkh(xpub/0/*)#00000000"],
+        });
+        let entry: ListTransactionsEntry = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(entry.category, TransactionCategory::Receive);
+        assert_eq!(entry.amount, SignedAmount::from_sat(50_000_000));
+        assert!(!entry.is_confirmed());
+        assert_eq!(entry.mempoolconflicts.len(), 1);
+        assert_eq!(serde_json::to_value(&entry).unwrap(), value);
+    }
+
+    #[test]
+    fn test_list_since_block() {
+        let entry = json!({
+            "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "category": "send",
+            "amount": -0.1,
+            "vout": 1,
+            "fee": -0.00000141,
+            "confirmations": 2,
+            "blockhash": HASH,
+            "blockheight": 840_000,
+            "blockindex": 7,
+            "blocktime": 1_713_571_767,
+            "txid": TXID,
+            "wtxid": TXID,
+            "walletconflicts": [],
+            "time": 1_713_571_700,
+            "timereceived": 1_713_571_700,
+            "bip125-replaceable": "no",
+            "abandoned": false,
+        });
+        let value = json!({ "transactions": [entry], "removed": [], "lastblock": HASH });
+        let result: ListSinceBlockResult = serde_json::from_value(value.clone()).unwrap();
+        assert!(result.transactions[0].is_confirmed());
+        assert_eq!(result.removed, Some(Vec::new()));
+        assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+        let value = json!({ "transactions": [], "lastblock": HASH });
+        let result: ListSinceBlockResult = serde_json::from_value(value).unwrap();
+        assert!(result.removed().is_empty());
+
+        let options = ListSinceBlockOptions {
+            blockhash: Some(HASH.parse().unwrap()),
+            include_removed: Some(false),
+            include_change: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({ "blockhash": HASH, "include_removed": false, "include_change": true })
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Node warnings field type
+//!
+//! `getblockchaininfo`, `getnetworkinfo` and `getmininginfo` return their
+//! `warnings` as a string before Bitcoin Core 28 and as an array of strings
+//! since. `Warnings` reads both forms.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Warnings reported by the node
+///
+/// Deserializes from a string, empty when there are no warnings, or from an
+/// array of strings. Serializes as an array.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Warnings(pub Vec<String>);
+
+impl Warnings {
+    /// Returns whether there are no warnings
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Iterates over the warnings
+    pub fn iter(&self) -> impl Iterator<Item = &str> { self.0.iter().map(String::as_str) }
+}
+
+impl<'de> Deserialize<'de> for Warnings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Single(String),
+            List(Vec<String>),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Single(warning) if warning.is_empty() => Self::default(),
+            Repr::Single(warning) => Self(vec![warning]),
+            Repr::List(warnings) => Self(warnings),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_warnings() {
+        assert!(serde_json::from_value::<Warnings>(json!("")).unwrap().is_empty());
+        let single: Warnings = serde_json::from_value(json!("This is a pre-release")).unwrap();
+        assert_eq!(single.iter().collect::<Vec<_>>(), ["This is a pre-release"]);
+        let list: Warnings = serde_json::from_value(json!(["a", "b"])).unwrap();
+        assert_eq!(list.0.len(), 2);
+        assert_eq!(serde_json::to_value(&single).unwrap(), json!(["This is a pre-release"]));
+        assert!(serde_json::from_value::<Warnings>(json!(1)).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! ZMQ notification result types
+//!
+//! This module provides `ZmqNotification`, an entry of
+//! `getzmqnotifications`, and the `ZmqNotificationType` of each publisher.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Type of a ZMQ publisher, as in its `-zmqpub<type>` option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZmqNotificationType {
+    /// Hashes of new blocks
+    PubHashBlock,
+    /// Hashes of new transactions
+    PubHashTx,
+    /// Serialized new blocks
+    PubRawBlock,
+    /// Serialized new transactions
+    PubRawTx,
+    /// Block connections and mempool additions and removals
+    PubSequence,
+}
+
+impl ZmqNotificationType {
+    /// All notification types
+    pub const ALL: [ZmqNotificationType; 5] = [
+        ZmqNotificationType::PubHashBlock,
+        ZmqNotificationType::PubHashTx,
+        ZmqNotificationType::PubRawBlock,
+        ZmqNotificationType::PubRawTx,
+        ZmqNotificationType::PubSequence,
+    ];
+
+    /// Returns the name Bitcoin Core uses for the notification type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PubHashBlock => "pubhashblock",
+            Self::PubHashTx => "pubhashtx",
+            Self::PubRawBlock => "pubrawblock",
+            Self::PubRawTx => "pubrawtx",
+            Self::PubSequence => "pubsequence",
+        }
+    }
+
+    /// Returns the topic messages of this type are published under
+    pub fn topic(&self) -> &'static str { &self.as_str()["pub".len()..] }
+}
+
+impl fmt::Display for ZmqNotificationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Error returned when parsing an unknown notification type name
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown ZMQ notification type: {0:?}")]
+pub struct ParseZmqNotificationTypeError(String);
+
+impl FromStr for ZmqNotificationType {
+    type Err = ParseZmqNotificationTypeError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|notification_type| notification_type.as_str() == s)
+            .ok_or_else(|| ParseZmqNotificationTypeError(s.to_string()))
+    }
+}
+
+/// Entry of `getzmqnotifications`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZmqNotification {
+    /// Type of the publisher
+    #[serde(rename = "type")]
+    pub type_: ZmqNotificationType,
+    /// Address the publisher binds to, e.g. `tcp://127.0.0.1:28332`
+    pub address: String,
+    /// Outbound message high water mark
+    pub hwm: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_zmq_notifications() {
+        let value = json!([
+            { "type": "pubhashblock", "address": "tcp://127.0.0.1:28332", "hwm": 1000 },
+            { "type": "pubrawtx", "address": "tcp://127.0.0.1:28333", "hwm": 1000 },
+        ]);
+        let notifications: Vec<ZmqNotification> = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(notifications[0].type_, ZmqNotificationType::PubHashBlock);
+        assert_eq!(notifications[1].type_.topic(), "rawtx");
+        assert_eq!(serde_json::to_value(&notifications).unwrap(), value);
+    }
+
+    #[test]
+    fn test_zmq_notification_type_names() {
+        for notification_type in ZmqNotificationType::ALL {
+            let name = notification_type.to_string();
+            assert_eq!(name.parse::<ZmqNotificationType>(), Ok(notification_type));
+            assert_eq!(serde_json::to_value(notification_type).unwrap(), json!(name));
+        }
+        assert_eq!(ZmqNotificationType::PubSequence.topic(), "sequence");
+        assert!("hashblock".parse::<ZmqNotificationType>().is_err());
+    }
+}